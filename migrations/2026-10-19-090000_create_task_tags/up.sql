@@ -0,0 +1,6 @@
+CREATE TABLE task_tags (
+  task_id INTEGER NOT NULL,
+  tag VARCHAR(255) NOT NULL,
+  PRIMARY KEY (task_id, tag),
+  FOREIGN KEY (task_id) REFERENCES tasks (id)
+);
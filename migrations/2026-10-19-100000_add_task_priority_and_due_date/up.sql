@@ -0,0 +1,2 @@
+ALTER TABLE tasks ADD COLUMN priority VARCHAR(255) CHECK (priority IN ('low', 'medium', 'high', 'urgent'));
+ALTER TABLE tasks ADD COLUMN due_date DATE;
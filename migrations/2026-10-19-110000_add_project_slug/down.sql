@@ -0,0 +1,2 @@
+DROP INDEX projects_slug;
+ALTER TABLE projects DROP COLUMN slug;
@@ -0,0 +1,7 @@
+ALTER TABLE projects ADD COLUMN slug VARCHAR(255) NOT NULL DEFAULT '';
+
+-- Placeholders no slug can look like, replaced by slugs derived from the titles once the
+-- migrations have run, see `Project::backfill_slugs`. SQL has no way to match `slugify`.
+UPDATE projects SET slug = '#' || id;
+
+CREATE UNIQUE INDEX projects_slug ON projects (slug);
@@ -0,0 +1,12 @@
+CREATE TABLE task_status_changes (
+  id INTEGER NOT NULL PRIMARY KEY,
+  task_id INTEGER NOT NULL,
+  from_status VARCHAR(255) NOT NULL,
+  to_status VARCHAR(255) NOT NULL,
+  reason TEXT,
+  changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (task_id) REFERENCES tasks (id),
+  CHECK (from_status IN ('todo', 'in_progress', 'blocked', 'in_review', 'completed', 'on_hold', 'canceled')),
+  CHECK (to_status IN ('todo', 'in_progress', 'blocked', 'in_review', 'completed', 'on_hold', 'canceled'))
+);
+CREATE INDEX task_status_changes_task_id ON task_status_changes (task_id);
@@ -0,0 +1,9 @@
+CREATE TABLE task_dependencies (
+  task_id INTEGER NOT NULL,
+  blocker_id INTEGER NOT NULL,
+  PRIMARY KEY (task_id, blocker_id),
+  FOREIGN KEY (task_id) REFERENCES tasks (id),
+  FOREIGN KEY (blocker_id) REFERENCES tasks (id),
+  CHECK (task_id != blocker_id)
+);
+CREATE INDEX task_dependencies_blocker_id ON task_dependencies (blocker_id);
@@ -0,0 +1,8 @@
+CREATE TABLE task_comments (
+  id INTEGER NOT NULL PRIMARY KEY,
+  task_id INTEGER NOT NULL,
+  body TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (task_id) REFERENCES tasks (id)
+);
+CREATE INDEX task_comments_task_id ON task_comments (task_id);
@@ -0,0 +1,10 @@
+CREATE TABLE task_links (
+  id INTEGER NOT NULL PRIMARY KEY,
+  task_id INTEGER NOT NULL,
+  target TEXT NOT NULL,
+  label TEXT,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (task_id) REFERENCES tasks (id),
+  UNIQUE (task_id, target)
+);
+CREATE INDEX task_links_task_id ON task_links (task_id);
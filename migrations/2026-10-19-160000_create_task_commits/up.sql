@@ -0,0 +1,10 @@
+CREATE TABLE task_commits (
+  id INTEGER NOT NULL PRIMARY KEY,
+  task_id INTEGER NOT NULL,
+  sha TEXT NOT NULL,
+  summary TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (task_id) REFERENCES tasks (id),
+  UNIQUE (task_id, sha)
+);
+CREATE INDEX task_commits_task_id ON task_commits (task_id);
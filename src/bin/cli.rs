@@ -1,12 +1,39 @@
 use clap::Parser;
 use on_a_roll::{
     cli::{run_cli, Cli},
-    db::connection::establish_connection,
+    config::{self, Config},
+    db::connection::{database_location, try_establish_connection_at},
+    notify,
 };
+use std::process;
 
 fn main() {
+    // Statuses in the arguments may use aliases from the config, so load it first.
+    match Config::load() {
+        Ok(config) => config::init(config),
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            process::exit(1);
+        }
+    }
     let cli = Cli::parse();
-    let mut conn = establish_connection();
+    if cli.run_without_database() {
+        return;
+    }
+    let connection = database_location(cli.workspace())
+        .and_then(|location| try_establish_connection_at(&location));
+    let mut conn = match connection {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("Error opening the database: {}", e);
+            process::exit(1);
+        }
+    };
 
     run_cli(cli, &mut conn);
+    // Webhooks and hook scripts for the changes made are still running.
+    notify::wait();
+    for error in notify::take_errors() {
+        eprintln!("{}", error);
+    }
 }
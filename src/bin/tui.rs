@@ -1,10 +1,41 @@
-use on_a_roll::tui::app::App;
+use clap::Parser;
+use on_a_roll::config::{self, Config};
+use on_a_roll::db::connection::{database_location, try_establish_connection_at};
+use on_a_roll::notify;
+use on_a_roll::tui::{self, app::App, TuiCli};
 use std::io;
+use std::process;
 
 fn main() -> io::Result<()> {
-    let mut terminal = ratatui::init();
-    terminal.clear()?;
-    let app_result = App::default().run(&mut terminal);
-    ratatui::restore();
+    let cli = TuiCli::parse();
+    match Config::load() {
+        Ok(config) => config::init(config),
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            process::exit(1);
+        }
+    }
+    let opened = database_location(cli.workspace.as_deref()).and_then(|database| {
+        let conn = try_establish_connection_at(&database)?;
+        Ok((database, conn))
+    });
+    let (database, conn) = match opened {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("Error opening the database: {}", e);
+            process::exit(1);
+        }
+    };
+    let app = match App::new(database, conn) {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Error loading the database: {}", e);
+            process::exit(1);
+        }
+    };
+    let mut terminal = tui::init()?;
+    let app_result = terminal.clear().and_then(|()| app.run(&mut terminal));
+    tui::restore();
+    notify::wait();
     app_result
 }
@@ -0,0 +1,230 @@
+use super::Cli;
+use crate::models::task_status::TaskStatus;
+use crate::models::{Project, Task};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use diesel::prelude::*;
+use std::io;
+
+const BIN_NAME: &str = "roll";
+
+/// Options that take a value, so the word following them is not a subcommand or an id.
+const VALUE_OPTIONS: [&str; 19] = [
+    "-t",
+    "--title",
+    "-d",
+    "--description",
+    "-s",
+    "--status",
+    "-p",
+    "--project",
+    "-w",
+    "--where",
+    "-r",
+    "--reason",
+    "--on",
+    "-l",
+    "--label",
+    "-C",
+    "--repo",
+    "--workspace",
+    "--bind",
+];
+
+/// Wraps the generated bash completion, asking `roll __complete` for ids and project slugs
+/// before falling back to the static completion.
+const BASH_DYNAMIC: &str = r#"
+_roll_dynamic() {
+    local candidates
+    candidates="$(roll __complete -- "${COMP_WORDS[@]:0:COMP_CWORD}" 2>/dev/null | cut -f1)"
+    if [[ -n "$candidates" ]]; then
+        COMPREPLY=($(compgen -W "$candidates" -- "${COMP_WORDS[COMP_CWORD]}"))
+    else
+        _roll "$@"
+    fi
+}
+complete -F _roll_dynamic -o bashdefault -o default roll
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+_roll_dynamic() {
+    local -a candidates
+    candidates=(${(f)"$(roll __complete -- ${words[1,CURRENT-1]} 2>/dev/null)"})
+    if (( ${#candidates} )); then
+        candidates=(${candidates//$'\t'/:})
+        _describe 'value' candidates
+    else
+        _roll "$@"
+    fi
+}
+compdef _roll_dynamic roll
+"#;
+
+/// Only applies where `roll __complete` has candidates, so files are still completed elsewhere.
+const FISH_DYNAMIC: &str = r#"
+function __roll_dynamic
+    roll __complete -- (commandline -opc) 2>/dev/null
+end
+complete -c roll -n 'count (__roll_dynamic) >/dev/null' -f -a '(__roll_dynamic)'
+"#;
+
+/// What the word under the cursor refers to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompletionTarget {
+    Tasks,
+    Projects,
+}
+
+/// Prints the completion script for `shell`. Bash, zsh and fish also complete task ids and
+/// project slugs from the database; elvish and PowerShell only get the static completion.
+pub fn handle_completions(shell: Shell) {
+    let mut stdout = io::stdout();
+    clap_complete::generate(shell, &mut Cli::command(), BIN_NAME, &mut stdout);
+    match shell {
+        Shell::Bash => print!("{}", BASH_DYNAMIC),
+        Shell::Zsh => print!("{}", ZSH_DYNAMIC),
+        Shell::Fish => print!("{}", FISH_DYNAMIC),
+        _ => {}
+    }
+}
+
+/// Prints the candidates for the word following `words`, one `value<TAB>description` per line.
+/// Prints nothing if the shell should fall back to the static completion.
+pub fn handle_complete(conn: &mut SqliteConnection, words: &[String]) {
+    match candidates(conn, words) {
+        Ok(candidates) => {
+            for (value, description) in candidates {
+                println!("{}\t{}", value, description);
+            }
+        }
+        Err(e) => eprintln!("Error completing: {}", e),
+    }
+}
+
+fn candidates(
+    conn: &mut SqliteConnection,
+    words: &[String],
+) -> Result<Vec<(String, String)>, diesel::result::Error> {
+    match completion_target(words) {
+        Some(CompletionTarget::Tasks) => Ok(Task::list(conn)?
+            .into_iter()
+            .filter(|task| !matches!(task.status, TaskStatus::Completed | TaskStatus::Canceled))
+            .map(|task| (task.id.to_string(), task.title))
+            .collect()),
+        Some(CompletionTarget::Projects) => Ok(Project::list(conn)?
+            .into_iter()
+            .map(|project| (project.slug, project.title))
+            .collect()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Works out what to complete from the words before the cursor, starting with the binary name.
+fn completion_target(words: &[String]) -> Option<CompletionTarget> {
+    let args = words.get(1..).unwrap_or_default();
+    let mut positionals = Vec::new();
+    let mut previous: Option<&str> = None;
+    for word in args {
+        if !word.starts_with('-') && !previous.is_some_and(|p| VALUE_OPTIONS.contains(&p)) {
+            positionals.push(word.as_str());
+        }
+        previous = Some(word);
+    }
+    if let Some(option) = previous.filter(|p| VALUE_OPTIONS.contains(p)) {
+        return match (positionals.first(), option) {
+            (Some(&"task"), "-p" | "--project") => Some(CompletionTarget::Projects),
+            (Some(&"task"), "--on") => Some(CompletionTarget::Tasks),
+            _ => None,
+        };
+    }
+    match positionals.as_slice() {
+        ["task", "update" | "delete", ..]
+        | ["task", "edit" | "read" | "comment" | "link" | "unlink" | "block" | "unblock" | "deps"] => {
+            Some(CompletionTarget::Tasks)
+        }
+        ["git", "start"] => Some(CompletionTarget::Tasks),
+        ["project", "read" | "update" | "delete"] => Some(CompletionTarget::Projects),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::run_migrations;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_completion_target() {
+        let target = |line| completion_target(&words(line));
+
+        assert_eq!(target("roll task update"), Some(CompletionTarget::Tasks));
+        assert_eq!(
+            target("roll task update 3 -y"),
+            Some(CompletionTarget::Tasks)
+        );
+        assert_eq!(target("roll task read"), Some(CompletionTarget::Tasks));
+        assert_eq!(target("roll task read 3"), None);
+        assert_eq!(
+            target("roll task update 3 -p"),
+            Some(CompletionTarget::Projects)
+        );
+        assert_eq!(target("roll task update 3 --title"), None);
+        assert_eq!(target("roll task block"), Some(CompletionTarget::Tasks));
+        assert_eq!(target("roll task link"), Some(CompletionTarget::Tasks));
+        assert_eq!(
+            target("roll git -C repo start"),
+            Some(CompletionTarget::Tasks)
+        );
+        assert_eq!(
+            target("roll task link -l PR"),
+            Some(CompletionTarget::Tasks)
+        );
+        assert_eq!(
+            target("roll task block 3 --on"),
+            Some(CompletionTarget::Tasks)
+        );
+        assert_eq!(
+            target("roll task update -t read --project"),
+            Some(CompletionTarget::Projects)
+        );
+        assert_eq!(
+            target("roll project delete"),
+            Some(CompletionTarget::Projects)
+        );
+        assert_eq!(target("roll project add"), None);
+        assert_eq!(target("roll"), None);
+        assert_eq!(target(""), None);
+    }
+
+    #[test]
+    fn test_candidates() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        run_migrations(&mut conn);
+        let open = Task::create(&mut conn, Some("Open task"), None, None, None).unwrap();
+        Task::create(
+            &mut conn,
+            Some("Done task"),
+            None,
+            Some(TaskStatus::Completed),
+            None,
+        )
+        .unwrap();
+        Project::create(&mut conn, Some("Garden Shed"), None, None).unwrap();
+
+        assert_eq!(
+            candidates(&mut conn, &words("roll task edit")).unwrap(),
+            vec![(open.id.to_string(), "Open task".to_string())]
+        );
+        assert_eq!(
+            candidates(&mut conn, &words("roll task update 1 --project")).unwrap(),
+            vec![("garden-shed".to_string(), "Garden Shed".to_string())]
+        );
+        assert!(candidates(&mut conn, &words("roll task list"))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,17 @@
+use crate::db::connection::{database_location, init_local_database};
+use std::path::Path;
+
+pub fn handle_init(dir: &Path) {
+    match init_local_database(dir) {
+        Ok((path, true)) => println!("Created {}", path.display()),
+        Ok((path, false)) => println!("{} already exists", path.display()),
+        Err(e) => eprintln!("Error creating database: {}", e),
+    }
+}
+
+pub fn handle_where(workspace: Option<&str>) {
+    match database_location(workspace) {
+        Ok(location) => println!("{}", location),
+        Err(e) => eprintln!("Error finding the database: {}", e),
+    }
+}
@@ -0,0 +1,170 @@
+use crate::config;
+use crate::git;
+use crate::models::task_status::TaskStatus;
+use crate::models::{branch_name, CommitReference, Task, TaskCommit};
+use clap::{Args, Subcommand};
+use diesel::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct GitArgs {
+    /// Repository to work in, defaults to the current directory
+    #[arg(short = 'C', long, global = true, default_value = ".")]
+    repo: PathBuf,
+    #[command(subcommand)]
+    command: GitCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum GitCommands {
+    /// Check out the branch of a task, such as '123-fix-login-bug', creating it if needed,
+    /// and move the task to In Progress
+    Start {
+        /// Task id of task to start
+        #[arg(required = true)]
+        task_id: i32,
+        /// Why the task moves to In Progress, if the workflow requires one
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
+    /// Record commits mentioning a task as '#123' against it, see `roll help git`
+    #[command(arg_required_else_help = true)]
+    Hook {
+        #[command(subcommand)]
+        command: HookCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum HookCommands {
+    /// Install the commit-msg and post-commit hooks into the repository
+    Install {
+        /// Replace existing hooks not installed by roll
+        #[arg(long)]
+        force: bool,
+    },
+    /// Warn about mentioned tasks that don't exist, run by the commit-msg hook
+    CommitMsg {
+        /// File holding the commit message
+        #[arg(required = true)]
+        file: PathBuf,
+    },
+    /// Record the last commit against the tasks it mentions, run by the post-commit hook
+    PostCommit,
+}
+
+fn handle_git_start(conn: &mut SqliteConnection, repo: &Path, task_id: i32, reason: Option<&str>) {
+    let task = match Task::find(conn, task_id) {
+        Ok(task) => task,
+        Err(diesel::result::Error::NotFound) => return eprintln!("Task not found"),
+        Err(e) => return eprintln!("Error finding task: {}", e),
+    };
+    let branch = branch_name(&task);
+    match git::start_branch(repo, &branch) {
+        Ok(true) => println!("Switched to a new branch '{}'", branch),
+        Ok(false) => println!("Switched to branch '{}'", branch),
+        Err(e) => return eprintln!("Error starting branch: {}", e),
+    }
+    if task.status == TaskStatus::InProgress {
+        return;
+    }
+    match Task::update_with_reason(
+        conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::InProgress),
+        None,
+        reason,
+    ) {
+        Ok(task) => println!("Task {} moved to {}", task.id, task.status),
+        Err(e) => eprintln!("Error moving task: {}", e),
+    }
+}
+
+fn handle_hook_install(repo: &Path, force: bool) {
+    match git::install_hooks(repo, force) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Installed {}", path.display());
+            }
+        }
+        Err(e) => eprintln!("Error installing hooks: {}", e),
+    }
+}
+
+fn handle_hook_commit_msg(conn: &mut SqliteConnection, file: &Path) {
+    let message = match fs::read_to_string(file) {
+        Ok(message) => message,
+        Err(e) => return eprintln!("Error reading {}: {}", file.display(), e),
+    };
+    for reference in CommitReference::parse(&message, &config::get().git.keywords) {
+        match Task::find(conn, reference.task_id) {
+            Ok(_) => {}
+            Err(diesel::result::Error::NotFound) => eprintln!(
+                "Warning: the commit message mentions task #{}, which doesn't exist",
+                reference.task_id
+            ),
+            Err(e) => eprintln!("Error finding task: {}", e),
+        }
+    }
+}
+
+fn handle_hook_post_commit(conn: &mut SqliteConnection, repo: &Path) {
+    let commit = match git::head_commit(repo) {
+        Ok(commit) => commit,
+        Err(e) => return eprintln!("Error reading commit: {}", e),
+    };
+    let settings = &config::get().git;
+    for reference in CommitReference::parse(&commit.message, &settings.keywords) {
+        let task = match Task::find(conn, reference.task_id) {
+            Ok(task) => task,
+            Err(diesel::result::Error::NotFound) => continue,
+            Err(e) => {
+                eprintln!("Error finding task: {}", e);
+                continue;
+            }
+        };
+        let recorded = match TaskCommit::record(conn, task.id, &commit.sha, commit.summary()) {
+            Ok(Some(recorded)) => recorded,
+            // Seen before, so the task has been moved already.
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("Error recording commit: {}", e);
+                continue;
+            }
+        };
+        println!(
+            "Recorded commit {} against task {}",
+            recorded.short_sha(),
+            task.id
+        );
+        let Some(status) = reference.status.filter(|_| settings.move_tasks) else {
+            continue;
+        };
+        if status == task.status {
+            continue;
+        }
+        let reason = format!("Commit {}", recorded.short_sha());
+        let moved =
+            Task::update_with_reason(conn, task.id, None, None, Some(status), None, Some(&reason));
+        match moved {
+            Ok(task) => println!("Task {} moved to {}", task.id, task.status),
+            Err(e) => eprintln!("Error moving task {}: {}", task.id, e),
+        }
+    }
+}
+
+pub fn handle_git_args(args: GitArgs, conn: &mut SqliteConnection) {
+    match args.command {
+        GitCommands::Start { task_id, reason } => {
+            handle_git_start(conn, &args.repo, task_id, reason.as_deref())
+        }
+        GitCommands::Hook { command } => match command {
+            HookCommands::Install { force } => handle_hook_install(&args.repo, force),
+            HookCommands::CommitMsg { file } => handle_hook_commit_msg(conn, &file),
+            HookCommands::PostCommit => handle_hook_post_commit(conn, &args.repo),
+        },
+    }
+}
@@ -0,0 +1,373 @@
+use super::Cli;
+use crate::models::project_status::ProjectStatus;
+use crate::models::status_name::{possible_value, StatusName};
+use crate::models::task_status::TaskStatus;
+use clap::CommandFactory;
+use strum::{EnumIter, IntoEnumIterator};
+
+const QUICK_ADD_HELP: &str = "\
+`roll add` and the `n` key in roll-tui create a task from a single line, such as
+
+    roll add Fix login bug +backend #urgent !high due:fri @garden-shed
+
+Words starting with a marker set a field, all other words make up the title:
+
+  +tag, #tag   Adds a tag. Repeated tags are added once.
+  !priority    Sets the priority: low, medium (or med), high or urgent.
+  due:date     Sets the due date: today, tomorrow (or tmr), a weekday such as fri or friday
+               (the next one, today included), a number of days or weeks from today such as
+               3d or 2w, or an ISO date such as 2024-12-24.
+  @project     Puts the task in a project, given by id, slug, title (ignoring case, `-` and
+               `_`) or an unambiguous slug prefix.
+
+A marker on its own, such as a lone `#`, is part of the title. Giving the priority, due date
+or project twice is an error.
+";
+
+const CONFIG_HELP: &str = "\
+roll reads its config file from ~/.config/on-a-roll/config.toml (the platform's config
+directory), or from the path in $ROLL_CONFIG. The file is optional:
+
+    # Extra names for statuses, mapping an alias to a status.
+    [aliases.task_status]
+    finished = \"completed\"
+
+    [aliases.project_status]
+    live = \"active\"
+
+    # Let projects follow the status their tasks suggest, such as Completed once all tasks
+    # are, instead of only showing the suggestion in `roll project read`.
+    [projects]
+    auto_status = true
+
+    # Command opening task links with the `o` key in roll-tui. Defaults to xdg-open, or
+    # open on macOS.
+    [links]
+    opener = \"firefox --new-tab\"
+
+    # Let commits mentioning `fixes #12` move task 12, see `roll help git`.
+    [git]
+    move_tasks = true
+
+    # Token requests to `roll serve` must carry, see `roll help api`.
+    [server]
+    token = \"s3cret\"
+
+    # Where changes are sent, see `roll help events`.
+    [[events.webhooks]]
+    url = \"https://example.com/roll\"
+    events = [\"task_completed\"]
+
+    # Which task status changes are allowed. Without a [workflow] section any change is.
+    [workflow]
+    reason_required = [\"blocked\", \"canceled\"]   # `roll task update -r` must give a reason
+    terminal = [\"completed\", \"canceled\"]        # tasks never leave these statuses
+
+    [workflow.transitions]                            # statuses not listed may move anywhere
+    todo = [\"in_progress\", \"canceled\"]
+    in_progress = [\"in_review\", \"blocked\"]
+
+Status changes and their reasons are kept, and shown by `roll task read`.
+
+Some settings come from the environment. A `.env` file in the current directory is loaded
+first, so they can be kept next to a project.
+
+  DATABASE_URL   Path of the SQLite database used outside of directories with their own, see
+                 below. Defaults to on-a-roll/roll.db in the platform's data directory, such
+                 as ~/.local/share. Created and migrated on first use.
+  VISUAL, EDITOR Editor used by `roll task edit` and the `e` key in roll-tui, such as
+                 `code --wait`. Defaults to vi.
+  ROLL_WORKSPACES  File listing the workspaces, defaults to workspaces.toml next to the
+                 config file.
+
+Like git, roll looks for a database of its own in the current directory and each directory
+above it, before using DATABASE_URL. The first directory with either of these wins:
+
+  .roll.toml     Names the database relative to itself: database = \"tasks/roll.db\"
+  .roll/roll.db  Created by `roll init`.
+
+Workspaces give names to databases kept elsewhere, such as `work` and `personal`. Add one
+with `roll workspace add work ~/tasks/work.db`, and pick the one used outside of directories
+with their own with `roll workspace switch`, or the `W` key in roll-tui. Until then, adding
+workspaces doesn't change the database in use. `--workspace <name>`
+uses a workspace for a single command, even inside such a directory. So the database is the
+first of:
+
+  1. The workspace given with --workspace.
+  2. .roll.toml or .roll/roll.db in the current directory or above.
+  3. The active workspace.
+  4. DATABASE_URL.
+  5. on-a-roll/roll.db in the platform's data directory.
+
+`roll where` prints which database is in use, roll-tui shows it in its title bar.
+";
+
+const GIT_HELP: &str = "\
+`roll git start <id>` checks out a branch named after the task, such as 123-fix-login-bug,
+creating it from the current commit if needed, and moves the task to In Progress, with the
+reason given by --reason if the workflow asks for one.
+
+`roll git hook install` adds two hooks to the repository, so commits mentioning a task as
+#123 anywhere in their message are shown in `roll task read` and the activity feed of roll-tui:
+
+  commit-msg   Warns about mentioned tasks that don't exist, while the message can be fixed.
+  post-commit  Records the commit against the tasks it mentions.
+
+A keyword before a mention, as in `Fixes #123` or `closes #4, #5 and #6`, can also move the
+tasks: fix, close and resolve (in any tense) to Completed, implement to In Review. This is
+off by default, and the workflow still applies:
+
+    [git]
+    move_tasks = true
+
+    [git.keywords]              # replaces the default keywords
+    fixes = \"completed\"
+    ships = \"in_review\"
+
+The hooks run roll in the repository, so its database is found the same way as in a shell
+there. Only the local git command is used, nothing goes over the network.
+";
+
+const API_HELP: &str = "\
+`roll serve` answers HTTP requests on 127.0.0.1:7878, or the address given with --bind, so
+scripts and editor plugins can work with tasks and projects without running roll:
+
+  GET    /tasks            Lists tasks, filtered like `roll task list --where`:
+                           /tasks?status=blocked&project=garden&ready=true
+  POST   /tasks            Creates a task from title, description, status and project.
+  GET    /tasks/<id>
+  PATCH  /tasks/<id>       Changes the fields given, plus reason for a status change.
+  DELETE /tasks/<id>
+  GET    /projects
+  POST   /projects         Creates a project from title, description and status.
+  GET    /projects/<project>
+  PATCH  /projects/<project>
+  DELETE /projects/<project>
+
+Bodies and responses are JSON, such as
+
+    curl -X POST localhost:7878/tasks -H 'Content-Type: application/json' \\
+        -d '{\"title\": \"Water plants\", \"project\": \"garden\"}'
+
+Statuses are written as on the command line, projects are given by id, slug, title or an
+unambiguous slug prefix. Bodies must be sent as application/json and are limited to 1 MiB.
+Failed requests answer with a status code and {\"error\": \"...\"}.
+
+Requests from web pages are refused unless they come from a loopback origin, so sites you
+visit can't reach the API through your browser.
+
+Any local program can use the API, unless a token is set in the config file. Requests must
+then carry it in an `Authorization: Bearer <token>` header:
+
+    [server]
+    token = \"s3cret\"
+";
+
+const EVENTS_HELP: &str = "\
+Every change to a task or project, whether made by roll, roll-tui or `roll serve`, is an
+event. Each has a name, and a status change also goes by one naming the new status:
+
+  task_created, task_updated, task_deleted
+  task_status_changed          also task_todo, task_in_progress, ..., task_completed
+  project_created, project_updated, project_deleted
+  project_status_changed       also project_planning, project_active, ...
+
+Webhooks get the events they list posted as JSON, or all of them without a list. A failed
+post is retried, with a growing delay in between:
+
+    [events]
+    retries = 3                                       # the default
+
+    [[events.webhooks]]
+    url = \"https://example.com/roll\"
+    events = [\"task_completed\", \"task_blocked\"]
+
+Hook scripts are executables in ~/.config/on-a-roll/hooks, next to the config file, or the
+directory set as hooks_dir in [events]. They are named on- and the event with - between
+words, such as on-task-completed, and get the event as JSON on stdin and its name in
+$ROLL_EVENT. The JSON looks like
+
+    {\"event\": \"task_status_changed\", \"task\": {\"id\": 12, \"title\": \"...\", ...},
+     \"from\": \"In Review\", \"to\": \"Completed\", \"reason\": null}
+
+Changes undone because a command failed send no events. roll waits for webhooks and hook
+scripts to finish before it exits, and reports the ones that failed.
+";
+
+/// Topics explained by `roll help <topic>`, besides the help of each subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum HelpTopic {
+    Status,
+    QuickAdd,
+    Git,
+    Api,
+    Events,
+    Config,
+}
+
+impl HelpTopic {
+    pub fn name(self) -> &'static str {
+        match self {
+            HelpTopic::Status => "status",
+            HelpTopic::QuickAdd => "quick-add",
+            HelpTopic::Git => "git",
+            HelpTopic::Api => "api",
+            HelpTopic::Events => "events",
+            HelpTopic::Config => "config",
+        }
+    }
+
+    fn summary(self) -> &'static str {
+        match self {
+            HelpTopic::Status => "Task and project statuses and how to write them",
+            HelpTopic::QuickAdd => "The quick-add syntax of `roll add`",
+            HelpTopic::Git => "Task branches and recording commits with git hooks",
+            HelpTopic::Api => "The HTTP API of `roll serve`",
+            HelpTopic::Events => "Webhooks and hook scripts run on changes",
+            HelpTopic::Config => "Settings and where roll reads them from",
+        }
+    }
+
+    /// Finds a topic by name, also accepting `workflow` for the status topic.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "workflow" => Some(HelpTopic::Status),
+            name => HelpTopic::iter().find(|topic| topic.name() == name),
+        }
+    }
+
+    pub fn text(self) -> String {
+        match self {
+            HelpTopic::Status => status_help(),
+            HelpTopic::QuickAdd => QUICK_ADD_HELP.to_string(),
+            HelpTopic::Git => GIT_HELP.to_string(),
+            HelpTopic::Api => API_HELP.to_string(),
+            HelpTopic::Events => EVENTS_HELP.to_string(),
+            HelpTopic::Config => CONFIG_HELP.to_string(),
+        }
+    }
+}
+
+fn status_help() -> String {
+    fn list<T: StatusName>() -> String {
+        T::VARIANTS
+            .iter()
+            .map(|status| {
+                let mut names = vec![possible_value(*status).get_name().to_string()];
+                names.extend(status.aliases().iter().map(|alias| alias.to_string()));
+                format!("  {:<12} {}\n", status.name(), names.join(", "))
+            })
+            .collect()
+    }
+    format!(
+        "Task statuses, new tasks start as {}:\n\n{}\nProject statuses, new projects start as {}:\n\n{}\n\
+         Statuses can be written in any case, with spaces, `-` or `_` between words, or by one of\n\
+         the names listed after them: `-s in_progress`, `-s \"In Progress\"` and `-s wip` are the same.\n\
+         More aliases can be added in the config file, see `roll help config`.\n\n\
+         `roll task block <id> --on <other>` moves a task to Blocked until the other task is\n\
+         completed, then back to the status it had before.\n",
+        TaskStatus::Todo,
+        list::<TaskStatus>(),
+        ProjectStatus::Planning,
+        list::<ProjectStatus>(),
+    )
+}
+
+/// Lists the help topics, one per line with a summary.
+pub fn topics_overview() -> String {
+    let mut overview = String::from("Help topics, shown by `roll help <topic>`:\n\n");
+    for topic in HelpTopic::iter() {
+        overview.push_str(&format!("  {:<10} {}\n", topic.name(), topic.summary()));
+    }
+    overview
+}
+
+/// Prints a help topic, or the help of the subcommand named by `words` such as `task update`.
+pub fn handle_help(words: &[String]) {
+    if words.is_empty() {
+        let mut cmd = Cli::command();
+        cmd.print_long_help().ok();
+        return println!("\n{}", topics_overview());
+    }
+    if let Some(topic) = find_topic(words) {
+        return print!("{}", topic.text());
+    }
+    let mut cmd = Cli::command().disable_help_subcommand(true);
+    cmd.build();
+    match find_subcommand(&mut cmd, words) {
+        Some(subcommand) => {
+            subcommand.print_long_help().ok();
+        }
+        None => {
+            eprintln!("Unknown help topic or command: {}\n", words.join(" "));
+            eprint!("{}", topics_overview());
+        }
+    }
+}
+
+/// The topic named by `words`. Topics are a single word, so `git start` is the subcommand
+/// even though `git` is a topic too.
+fn find_topic(words: &[String]) -> Option<HelpTopic> {
+    match words {
+        [word] => HelpTopic::from_name(word),
+        _ => None,
+    }
+}
+
+/// The visible subcommand named by `words`, such as `task update`.
+fn find_subcommand<'a>(
+    cmd: &'a mut clap::Command,
+    words: &[String],
+) -> Option<&'a mut clap::Command> {
+    let mut current = cmd;
+    for word in words {
+        current = current
+            .find_subcommand_mut(word)
+            .filter(|subcommand| !subcommand.is_hide_set())?;
+    }
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_from_name() {
+        for topic in HelpTopic::iter() {
+            assert_eq!(HelpTopic::from_name(topic.name()), Some(topic));
+        }
+        assert_eq!(HelpTopic::from_name("Workflow"), Some(HelpTopic::Status));
+        assert_eq!(HelpTopic::from_name("task"), None);
+    }
+
+    #[test]
+    fn test_help_for_subcommand_of_topic() {
+        let words = |words: &[&str]| {
+            words
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(find_topic(&words(&["git"])), Some(HelpTopic::Git));
+        assert_eq!(find_topic(&words(&["git", "start"])), None);
+
+        let mut cmd = Cli::command();
+        cmd.build();
+        let start = find_subcommand(&mut cmd, &words(&["git", "start"])).unwrap();
+        assert_eq!(start.get_name(), "start");
+        assert!(find_subcommand(&mut cmd, &words(&["git", "stop"])).is_none());
+    }
+
+    #[test]
+    fn test_status_help_lists_every_status() {
+        let text = HelpTopic::Status.text();
+
+        for status in TaskStatus::iter() {
+            assert!(text.contains(&status.to_string()));
+        }
+        for status in ProjectStatus::iter() {
+            assert!(text.contains(&status.to_string()));
+        }
+    }
+}
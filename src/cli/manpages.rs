@@ -0,0 +1,22 @@
+use super::Cli;
+use crate::tui::TuiCli;
+use clap::CommandFactory;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes man pages for `roll`, each of its subcommands and `roll-tui` to `out_dir`, such as
+/// `roll.1`, `roll-task-update.1` and `roll-tui.1`.
+pub fn generate_manpages(out_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(out_dir)?;
+    clap_mangen::generate_to(Cli::command(), out_dir)?;
+    clap_mangen::Man::new(TuiCli::command()).generate_to(out_dir)?;
+    Ok(())
+}
+
+pub fn handle_manpage(out_dir: &Path) {
+    match generate_manpages(out_dir) {
+        Ok(()) => println!("Man pages written to {}", out_dir.display()),
+        Err(e) => eprintln!("Error writing man pages: {}", e),
+    }
+}
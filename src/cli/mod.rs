@@ -1,30 +1,135 @@
+mod completions;
+mod database;
+mod git;
+mod help;
+mod manpages;
 mod projects;
+mod selection;
+mod serve;
 mod tasks;
+mod workspaces;
 use clap::{Parser, Subcommand};
 
 #[derive(Debug, Parser)]
 #[command(name = "roll")]
 #[command(author, version, about, long_about = None)]
+#[command(disable_help_subcommand = true)]
+#[command(
+    after_long_help = "Run `roll help <topic>` for the topics status, quick-add, git, api, events and \
+         config."
+)]
 pub struct Cli {
+    /// Use the database of this workspace, see `roll workspace`
+    #[arg(long, global = true)]
+    workspace: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
+    /// Add a task using quick-add syntax, such as
+    /// 'Fix login bug +backend #urgent !high due:fri @project-name'
+    #[command(arg_required_else_help = true)]
+    Add {
+        /// Title words, tags (+tag or #tag), priority (!high), due date (due:fri) and project
+        /// (@name)
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
     #[command(arg_required_else_help = true)]
     Task(tasks::TaskArgs),
     #[command(arg_required_else_help = true)]
     Project(projects::ProjectArgs),
+    /// Start branches for tasks and record the commits mentioning them
+    #[command(arg_required_else_help = true)]
+    Git(git::GitArgs),
+    /// Create a database for the tasks of this directory and its subdirectories, in .roll/roll.db
+    Init {
+        /// Directory to create the database in
+        #[arg(default_value = ".")]
+        dir: std::path::PathBuf,
+    },
+    /// Print which database is in use and why
+    Where,
+    /// Serve a JSON API for tasks and projects over HTTP, see `roll help api`
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = crate::server::DEFAULT_BIND)]
+        bind: String,
+    },
+    /// Keep tasks in separate named workspaces, each with its own database
+    #[command(arg_required_else_help = true)]
+    Workspace(workspaces::WorkspaceArgs),
+    /// Print a shell completion script, e.g. `source <(roll completions bash)`
+    Completions {
+        /// Shell to generate the completion script for
+        shell: clap_complete::Shell,
+    },
+    /// Print help for a topic (status, quick-add, git, api, events, config) or a subcommand such as 'task update'
+    Help {
+        /// Topic or subcommand words
+        topic: Vec<String>,
+    },
+    /// Write man pages for roll, its subcommands and roll-tui
+    Manpage {
+        /// Directory to write the man pages to, created if missing
+        #[arg(long)]
+        out_dir: std::path::PathBuf,
+    },
+    /// Print completion candidates for the words before the cursor
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Words of the command line before the cursor, starting with the binary name
+        #[arg(last = true)]
+        words: Vec<String>,
+    },
+}
+
+impl Cli {
+    /// The workspace given with `--workspace`, whose database overrides any other.
+    pub fn workspace(&self) -> Option<&str> {
+        self.workspace.as_deref()
+    }
+
+    /// Runs the commands that don't need the database, so `roll manpage` works without one.
+    /// Returns `false` if the command needs a connection and [`run_cli`] should run it instead.
+    pub fn run_without_database(&self) -> bool {
+        match &self.command {
+            Commands::Init { dir } => database::handle_init(dir),
+            Commands::Where => database::handle_where(self.workspace()),
+            Commands::Workspace(workspace_args) => {
+                workspaces::handle_workspace_args(workspace_args)
+            }
+            Commands::Completions { shell } => completions::handle_completions(*shell),
+            Commands::Help { topic } => help::handle_help(topic),
+            Commands::Manpage { out_dir } => manpages::handle_manpage(out_dir),
+            _ => return false,
+        }
+        true
+    }
 }
 
 pub fn run_cli(cli: Cli, conn: &mut diesel::SqliteConnection) {
+    if cli.run_without_database() {
+        return;
+    }
     match cli.command {
+        Commands::Add { text } => tasks::handle_quick_add(conn, &text.join(" ")),
         Commands::Task(task_args) => {
             tasks::handle_task_args(task_args, conn);
         }
         Commands::Project(project_args) => {
             projects::handle_project_args(project_args, conn);
         }
+        Commands::Git(git_args) => git::handle_git_args(git_args, conn),
+        Commands::Serve { bind } => serve::handle_serve(conn, &bind),
+        Commands::Init { .. }
+        | Commands::Where
+        | Commands::Workspace(_)
+        | Commands::Completions { .. }
+        | Commands::Help { .. }
+        | Commands::Manpage { .. } => {}
+        Commands::Complete { words } => completions::handle_complete(conn, &words),
     }
 }
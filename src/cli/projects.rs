@@ -1,5 +1,6 @@
 use crate::models::project_status::ProjectStatus;
-use crate::models::Project;
+use crate::models::status_name::{StatusName, StatusValueParser};
+use crate::models::{Project, Task};
 use clap::{Args, Subcommand};
 use diesel::prelude::*;
 
@@ -17,13 +18,14 @@ enum ProjectCommands {
         /// Optional project description
         description: Option<String>,
         /// Optional project status, defaults to 'Planning'
+        #[arg(value_parser = StatusValueParser::<ProjectStatus>::new())]
         status: Option<ProjectStatus>,
     },
     /// Update an existing project
     Update {
-        /// Project id of project to update
+        /// Id, slug or unambiguous slug prefix of the project to update
         #[arg(required = true)]
-        project_id: i32,
+        project: String,
         /// New project title
         #[arg(short, long)]
         title: Option<String>,
@@ -31,20 +33,20 @@ enum ProjectCommands {
         #[arg(short, long)]
         description: Option<String>,
         /// New project status
-        #[arg(short, long)]
+        #[arg(short, long, value_parser = StatusValueParser::<ProjectStatus>::new())]
         status: Option<ProjectStatus>,
     },
     /// Delete an existing project
     Delete {
-        /// project id of project to delete
+        /// Id, slug or unambiguous slug prefix of the project to delete
         #[arg(required = true)]
-        project_id: i32,
+        project: String,
     },
     /// Read an existing project
     Read {
-        /// project id of project to view
+        /// Id, slug or unambiguous slug prefix of the project to view
         #[arg(required = true)]
-        project_id: i32,
+        project: String,
     },
     /// List all projects
     List,
@@ -58,13 +60,13 @@ pub fn handle_project_args(args: ProjectArgs, connection: &mut SqliteConnection)
             status,
         } => handle_project_add(connection, title, description, status),
         ProjectCommands::Update {
-            project_id,
+            project,
             title,
             description,
             status,
-        } => handle_project_update(connection, project_id, title, description, status),
-        ProjectCommands::Delete { project_id } => handle_project_delete(connection, project_id),
-        ProjectCommands::Read { project_id } => handle_project_read(connection, project_id),
+        } => handle_project_update(connection, &project, title, description, status),
+        ProjectCommands::Delete { project } => handle_project_delete(connection, &project),
+        ProjectCommands::Read { project } => handle_project_read(connection, &project),
         ProjectCommands::List => handle_project_list(connection),
     }
 }
@@ -87,11 +89,15 @@ fn handle_project_add(
 
 fn handle_project_update(
     conn: &mut SqliteConnection,
-    project_id: i32,
+    reference: &str,
     title: Option<String>,
     description: Option<String>,
     status: Option<ProjectStatus>,
 ) {
+    let project_id = match Project::resolve(conn, reference) {
+        Ok(project) => project.id,
+        Err(e) => return eprintln!("Error updating project: {}", e),
+    };
     println!(
         "Updating project: {} with title: {:?}, description: {:?} and status: {:?}",
         project_id, title, description, status
@@ -107,19 +113,46 @@ fn handle_project_update(
         Err(e) => eprintln!("Error updating project: {}", e),
     }
 }
-fn handle_project_delete(conn: &mut SqliteConnection, task_id: i32) {
-    println!("Deleting project: {}", task_id);
-    match Project::delete(conn, task_id) {
+fn handle_project_delete(conn: &mut SqliteConnection, reference: &str) {
+    let project_id = match Project::resolve(conn, reference) {
+        Ok(project) => project.id,
+        Err(e) => return eprintln!("Error deleting project: {}", e),
+    };
+    println!("Deleting project: {}", project_id);
+    match Project::delete(conn, project_id) {
         Ok(amount) => println!("Deleted {} project(s)", amount),
         Err(e) => eprintln!("Error deleting project: {}", e),
     }
 }
 
-fn handle_project_read(conn: &mut SqliteConnection, task_id: i32) {
-    match Project::find(conn, task_id) {
-        Ok(project) => println!("Task found: {:?}", project),
-        Err(diesel::result::Error::NotFound) => eprintln!("project not found"),
-        Err(e) => eprintln!("Error finding project: {}", e),
+fn handle_project_read(conn: &mut SqliteConnection, reference: &str) {
+    let project = match Project::resolve(conn, reference) {
+        Ok(project) => project,
+        Err(e) => return eprintln!("Error finding project: {}", e),
+    };
+    println!("Project found: {:?}", project);
+    let rollup = match Project::rollup(conn, project.id) {
+        Ok(rollup) => rollup,
+        Err(e) => return eprintln!("Error summing up tasks: {}", e),
+    };
+    println!("Tasks: {}", rollup.summary());
+    for (status, count) in &rollup.counts {
+        println!("  {}: {}", status, count);
+    }
+    if let Some(task) = rollup
+        .oldest_open_task
+        .and_then(|id| Task::find(conn, id).ok())
+    {
+        println!("Oldest open task: {}: {}", task.id, task.title);
+    }
+    if let Some(suggestion) = rollup.suggested_status(project.status) {
+        println!(
+            "Suggested status: {}, as {} (apply with `roll project update {} -s {}`)",
+            suggestion.status,
+            suggestion.reason,
+            project.slug,
+            suggestion.status.key().replace('_', "-")
+        );
     }
 }
 
@@ -0,0 +1,77 @@
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// Bulk operations touching more than this many items ask for confirmation first.
+pub const CONFIRMATION_THRESHOLD: usize = 10;
+
+/// A single id such as `3` or an inclusive range of ids such as `3-9`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdSelection {
+    Single(i32),
+    Range(i32, i32),
+}
+
+impl IdSelection {
+    pub fn contains(&self, id: i32) -> bool {
+        match *self {
+            IdSelection::Single(single) => single == id,
+            IdSelection::Range(start, end) => (start..=end).contains(&id),
+        }
+    }
+}
+
+impl FromStr for IdSelection {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parse_id = |id: &str| {
+            id.trim()
+                .parse::<i32>()
+                .map_err(|_| format!("Invalid id: {}", id))
+        };
+        match input.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_id(start)?, parse_id(end)?);
+                if start > end {
+                    return Err(format!("Invalid id range: {}", input));
+                }
+                Ok(IdSelection::Range(start, end))
+            }
+            None => parse_id(input).map(IdSelection::Single),
+        }
+    }
+}
+
+/// Asks the user to confirm an action on stdin. Anything but `y` or `yes` declines.
+pub fn confirm(prompt: &str) -> bool {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush().ok();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_id_selection() {
+        assert_eq!(IdSelection::from_str("3"), Ok(IdSelection::Single(3)));
+        assert_eq!(IdSelection::from_str("3-9"), Ok(IdSelection::Range(3, 9)));
+        assert!(IdSelection::from_str("9-3").is_err());
+        assert!(IdSelection::from_str("three").is_err());
+        assert!(IdSelection::from_str("3-").is_err());
+    }
+
+    #[test]
+    fn test_id_selection_contains() {
+        assert!(IdSelection::Single(3).contains(3));
+        assert!(!IdSelection::Single(3).contains(4));
+        assert!(IdSelection::Range(3, 9).contains(3));
+        assert!(IdSelection::Range(3, 9).contains(9));
+        assert!(!IdSelection::Range(3, 9).contains(10));
+    }
+}
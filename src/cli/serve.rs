@@ -0,0 +1,19 @@
+use crate::config;
+use crate::server::Server;
+use diesel::SqliteConnection;
+
+pub fn handle_serve(conn: &mut SqliteConnection, bind: &str) {
+    let token = config::get().server.token.clone();
+    let server = match Server::bind(bind, token) {
+        Ok(server) => server,
+        Err(e) => return eprintln!("Error starting server: {}", e),
+    };
+    match server.local_addr() {
+        Some(address) => println!("Serving the API on http://{}", address),
+        None => println!("Serving the API on {}", bind),
+    }
+    if config::get().server.token.is_none() {
+        println!("No [server] token is set, so any local program can use the API");
+    }
+    server.run(conn);
+}
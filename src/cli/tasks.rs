@@ -1,5 +1,13 @@
+use super::selection::{confirm, IdSelection, CONFIRMATION_THRESHOLD};
+use crate::config;
+use crate::editor;
+use crate::models::status_name::StatusValueParser;
 use crate::models::task_status::TaskStatus;
-use crate::models::Task;
+use crate::models::{
+    normalize_target, Link, Project, ProjectLookupError, QuickAdd, Task, TaskActivity, TaskComment,
+    TaskDependency, TaskFilter, TaskLink,
+};
+use chrono::Local;
 use clap::{Args, Subcommand};
 use diesel::prelude::*;
 
@@ -17,16 +25,16 @@ enum TaskCommands {
         title: Option<String>,
         /// Optional task description
         description: Option<String>,
-        /// Optional task status, defaults to 'pending'
+        /// Optional task status, defaults to 'Todo'
+        #[arg(value_parser = StatusValueParser::<TaskStatus>::new())]
         status: Option<TaskStatus>,
-        /// Optional project id
-        project_id: Option<i32>,
+        /// Optional project id, slug or unambiguous slug prefix
+        project: Option<String>,
     },
-    /// Update an existing task
+    /// Update one or more existing tasks
     Update {
-        /// Task id of task to update
-        #[arg(required = true)]
-        task_id: i32,
+        #[command(flatten)]
+        selection: TaskSelection,
         /// New task title
         #[arg(short, long)]
         title: Option<String>,
@@ -34,15 +42,23 @@ enum TaskCommands {
         #[arg(short, long)]
         description: Option<String>,
         /// New task status
-        #[arg(short, long)]
+        #[arg(short, long, value_parser = StatusValueParser::<TaskStatus>::new())]
         status: Option<TaskStatus>,
-        /// New project id
-        #[arg(short, long = "project")]
-        project_id: Option<i32>,
+        /// New project id, slug or unambiguous slug prefix
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Why the status changes, required by the workflow for some statuses
+        #[arg(short, long, requires = "status")]
+        reason: Option<String>,
     },
-    /// Delete an existing task
+    /// Delete one or more existing tasks
     Delete {
-        /// Task id of task to delete
+        #[command(flatten)]
+        selection: TaskSelection,
+    },
+    /// Edit an existing task in $VISUAL or $EDITOR
+    Edit {
+        /// Task id of task to edit
         #[arg(required = true)]
         task_id: i32,
     },
@@ -52,8 +68,114 @@ enum TaskCommands {
         #[arg(required = true)]
         task_id: i32,
     },
+    /// Comment on a task, keeping a timestamped thread next to its description
+    Comment {
+        /// Task id of task to comment on
+        #[arg(required = true)]
+        task_id: i32,
+        /// Comment text
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// Link a URL or file path to a task, such as a pull request or a design doc
+    Link {
+        /// Task id of task to link to
+        #[arg(required = true)]
+        task_id: i32,
+        /// URL or file path, relative paths are made absolute
+        #[arg(required = true)]
+        target: String,
+        /// Optional label, such as 'PR'
+        #[arg(short, long)]
+        label: Option<String>,
+    },
+    /// Remove a link from a task
+    Unlink {
+        /// Task id of task to remove the link from
+        #[arg(required = true)]
+        task_id: i32,
+        /// URL or file path of the link, as shown by `roll task read`
+        #[arg(required = true)]
+        target: String,
+    },
+    /// Mark a task as blocked by another one, moving it to Blocked until that one is completed
+    Block {
+        /// Task id of the blocked task
+        #[arg(required = true)]
+        task_id: i32,
+        /// Task id of the task it waits for
+        #[arg(long, required = true)]
+        on: i32,
+    },
+    /// Remove a blocker from a task
+    Unblock {
+        /// Task id of the blocked task
+        #[arg(required = true)]
+        task_id: i32,
+        /// Task id of the blocker to remove
+        #[arg(long, required = true)]
+        on: i32,
+    },
+    /// Show the tasks a task waits for (upstream) and the tasks waiting for it (downstream)
+    Deps {
+        /// Task id of task to show the dependencies of
+        #[arg(required = true)]
+        task_id: i32,
+    },
     /// List all tasks
-    List,
+    List {
+        /// Only list tasks matching the filter, such as 'status=Blocked,project=garden,tag=urgent' or
+        /// 'ready=true' for tasks whose blockers are all completed or canceled
+        #[arg(short = 'w', long = "where")]
+        filter: Option<TaskFilter>,
+    },
+}
+
+/// Selects the tasks a bulk command applies to.
+#[derive(Debug, Args)]
+struct TaskSelection {
+    /// Task ids or ranges of task ids, such as '3' or '3-9'
+    #[arg(required_unless_present = "filter")]
+    task_ids: Vec<IdSelection>,
+    /// Only select tasks matching the filter, such as 'status=Blocked,project=garden,ready=true'
+    #[arg(short = 'w', long = "where")]
+    filter: Option<TaskFilter>,
+    /// Print what would change without changing anything
+    #[arg(long)]
+    dry_run: bool,
+    /// Don't ask for confirmation when many tasks are selected
+    #[arg(short = 'y', long)]
+    yes: bool,
+}
+
+impl TaskSelection {
+    /// Looks up the selected tasks. Fails if an explicitly given id does not exist or does not
+    /// match the filter.
+    fn resolve(&self, conn: &mut SqliteConnection) -> Result<Vec<Task>, String> {
+        let filter = self.filter.clone().unwrap_or_default();
+        let candidates = Task::list_filtered(conn, &filter).map_err(|e| e.to_string())?;
+        if self.task_ids.is_empty() {
+            return Ok(candidates);
+        }
+        for selection in &self.task_ids {
+            if let IdSelection::Single(id) = selection {
+                if !candidates.iter().any(|task| task.id == *id) {
+                    return Err(format!("Task {} not found", id));
+                }
+            }
+        }
+        Ok(candidates
+            .into_iter()
+            .filter(|task| self.task_ids.iter().any(|s| s.contains(task.id)))
+            .collect())
+    }
+
+    /// Asks for confirmation if the selection is large, unless `--yes` was given.
+    fn confirm(&self, action: &str, count: usize) -> bool {
+        self.yes
+            || count <= CONFIRMATION_THRESHOLD
+            || confirm(&format!("{} {} tasks?", action, count))
+    }
 }
 
 fn handle_task_add(
@@ -61,8 +183,12 @@ fn handle_task_add(
     title: Option<String>,
     description: Option<String>,
     status: Option<TaskStatus>,
-    project_id: Option<i32>,
+    project: Option<String>,
 ) {
+    let project_id = match resolve_project(conn, project.as_deref()) {
+        Ok(project_id) => project_id,
+        Err(e) => return eprintln!("Error creating task: {}", e),
+    };
     println!(
         "Adding task: {:?} with description: {:?} and status: {:?}",
         title, description, status
@@ -79,49 +205,291 @@ fn handle_task_add(
     }
 }
 
+pub fn handle_quick_add(conn: &mut SqliteConnection, text: &str) {
+    let quick_add = match QuickAdd::parse(text, Local::now().date_naive()) {
+        Ok(quick_add) => quick_add,
+        Err(e) => return eprintln!("Error parsing task: {}", e),
+    };
+    match Task::quick_add(conn, &quick_add) {
+        Ok(task) => println!("Task created with id: {}", task.id),
+        Err(e) => eprintln!("Error creating task: {}", e),
+    }
+}
+
 fn handle_task_update(
     conn: &mut SqliteConnection,
-    task_id: i32,
+    selection: TaskSelection,
     title: Option<String>,
     description: Option<String>,
     status: Option<TaskStatus>,
-    project_id: Option<i32>,
+    project: Option<String>,
+    reason: Option<String>,
 ) {
+    let project_id = match resolve_project(conn, project.as_deref()) {
+        Ok(project_id) => project_id,
+        Err(e) => return eprintln!("Error updating tasks: {}", e),
+    };
+    let selected = match selection.resolve(conn) {
+        Ok(selected) => selected,
+        Err(e) => return eprintln!("Error selecting tasks: {}", e),
+    };
+    if selected.is_empty() {
+        return println!("No tasks selected");
+    }
+    if selection.dry_run {
+        for task in &selected {
+            let mut changes = describe_changes(task, &title, &description, status, project_id);
+            if let Some(status) = status {
+                if let Err(e) = config::get()
+                    .workflow
+                    .check(task.status, status, reason.as_deref())
+                {
+                    changes.push(format!("rejected: {}", e));
+                }
+            }
+            println!(
+                "Would update task {} ({}): {}",
+                task.id,
+                task.title,
+                changes.join(", ")
+            );
+        }
+        return;
+    }
+    if !selection.confirm("Update", selected.len()) {
+        return println!("Aborted");
+    }
     println!(
-        "Updating task: {} with title: {:?}, description: {:?} and status: {:?}",
-        task_id, title, description, status
+        "Updating {} task(s) with title: {:?}, description: {:?} and status: {:?}",
+        selected.len(),
+        title,
+        description,
+        status
     );
-    match Task::update(
+    let ids: Vec<i32> = selected.iter().map(|task| task.id).collect();
+    match Task::update_many(
         conn,
-        task_id,
+        &ids,
         title.as_deref(),
         description.as_deref(),
         status,
         project_id,
+        reason.as_deref(),
     ) {
-        Ok(task) => println!("Task updated: {:?}", task),
-        Err(e) => eprintln!("Error updating task: {}", e),
+        Ok(tasks) => {
+            for task in tasks {
+                println!("Task updated: {:?}", task)
+            }
+        }
+        Err(e) => eprintln!("Error updating tasks: {}", e),
     }
 }
-fn handle_task_delete(conn: &mut SqliteConnection, task_id: i32) {
-    println!("Deleting task: {}", task_id);
-    match Task::delete(conn, task_id) {
+
+/// Resolves an optional project reference given on the command line to its id.
+fn resolve_project(
+    conn: &mut SqliteConnection,
+    reference: Option<&str>,
+) -> Result<Option<i32>, ProjectLookupError> {
+    reference
+        .map(|reference| Project::resolve(conn, reference).map(|project| project.id))
+        .transpose()
+}
+
+/// Describes how an update would change a task, one entry per changed field.
+fn describe_changes(
+    task: &Task,
+    title: &Option<String>,
+    description: &Option<String>,
+    status: Option<TaskStatus>,
+    project_id: Option<i32>,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    if let Some(title) = title {
+        changes.push(format!("title: {:?} -> {:?}", task.title, title));
+    }
+    if let Some(description) = description {
+        changes.push(format!(
+            "description: {:?} -> {:?}",
+            task.description.as_deref().unwrap_or(""),
+            description
+        ));
+    }
+    if let Some(status) = status {
+        changes.push(format!("status: {} -> {}", task.status, status));
+    }
+    if let Some(project_id) = project_id {
+        changes.push(format!("project: {:?} -> {}", task.project_id, project_id));
+    }
+    if changes.is_empty() {
+        changes.push("no changes".to_string());
+    }
+    changes
+}
+
+fn handle_task_delete(conn: &mut SqliteConnection, selection: TaskSelection) {
+    let selected = match selection.resolve(conn) {
+        Ok(selected) => selected,
+        Err(e) => return eprintln!("Error selecting tasks: {}", e),
+    };
+    if selected.is_empty() {
+        return println!("No tasks selected");
+    }
+    if selection.dry_run {
+        for task in &selected {
+            println!("Would delete task {} ({})", task.id, task.title);
+        }
+        return;
+    }
+    if !selection.confirm("Delete", selected.len()) {
+        return println!("Aborted");
+    }
+    let ids: Vec<i32> = selected.iter().map(|task| task.id).collect();
+    println!("Deleting tasks: {:?}", ids);
+    match Task::delete_many(conn, &ids) {
         Ok(amount) => println!("Deleted {} task(s)", amount),
-        Err(e) => eprintln!("Error deleting task: {}", e),
+        Err(e) => eprintln!("Error deleting tasks: {}", e),
+    }
+}
+
+fn handle_task_edit(conn: &mut SqliteConnection, task_id: i32) {
+    match editor::edit_task(conn, task_id) {
+        Ok(Some(task)) => println!("Task updated: {:?}", task),
+        Ok(None) => println!("Task unchanged"),
+        Err(e) => eprintln!("Error editing task: {}", e),
     }
 }
 
 fn handle_task_read(conn: &mut SqliteConnection, task_id: i32) {
     match Task::find(conn, task_id) {
         Ok(task) => println!("Task found: {:?}", task),
-        Err(diesel::result::Error::NotFound) => eprintln!("Task not found"),
-        Err(e) => eprintln!("Error finding task: {}", e),
+        Err(diesel::result::Error::NotFound) => return eprintln!("Task not found"),
+        Err(e) => return eprintln!("Error finding task: {}", e),
+    }
+    match Link::list_for_task(conn, task_id) {
+        Ok(links) => {
+            for link in links {
+                println!("Link: {}", link);
+            }
+        }
+        Err(e) => eprintln!("Error listing links: {}", e),
+    }
+    match TaskActivity::list_for_task(conn, task_id) {
+        Ok(activity) => {
+            for line in activity.iter().flat_map(TaskActivity::lines) {
+                println!("{}", line);
+            }
+        }
+        Err(e) => eprintln!("Error listing activity: {}", e),
+    }
+}
+
+fn handle_task_comment(conn: &mut SqliteConnection, task_id: i32, text: &str) {
+    if text.trim().is_empty() {
+        return eprintln!("Error adding comment: Comment must not be empty");
+    }
+    match Task::find(conn, task_id) {
+        Ok(_) => {}
+        Err(diesel::result::Error::NotFound) => return eprintln!("Task not found"),
+        Err(e) => return eprintln!("Error finding task: {}", e),
+    }
+    match TaskComment::add(conn, task_id, text) {
+        Ok(comment) => println!("Comment added to task {}", comment.task_id),
+        Err(e) => eprintln!("Error adding comment: {}", e),
+    }
+}
+
+fn handle_task_link(conn: &mut SqliteConnection, task_id: i32, target: &str, label: Option<&str>) {
+    if target.trim().is_empty() {
+        return eprintln!("Error adding link: Link must not be empty");
+    }
+    let base = match std::env::current_dir() {
+        Ok(base) => base,
+        Err(e) => return eprintln!("Error adding link: {}", e),
+    };
+    match Task::find(conn, task_id) {
+        Ok(_) => {}
+        Err(diesel::result::Error::NotFound) => return eprintln!("Task not found"),
+        Err(e) => return eprintln!("Error finding task: {}", e),
+    }
+    match TaskLink::add(conn, task_id, &normalize_target(target, &base), label) {
+        Ok(link) => println!("Linked {} to task {}", Link::from(link), task_id),
+        Err(e) => eprintln!("Error adding link: {}", e),
+    }
+}
+
+fn handle_task_unlink(conn: &mut SqliteConnection, task_id: i32, target: &str) {
+    match TaskLink::remove(conn, task_id, target) {
+        Ok(0) => println!("Task {} has no link {}", task_id, target),
+        Ok(_) => println!("Removed link {} from task {}", target, task_id),
+        Err(e) => eprintln!("Error removing link: {}", e),
+    }
+}
+
+fn handle_task_block(conn: &mut SqliteConnection, task_id: i32, blocker_id: i32) {
+    match Task::block(conn, task_id, blocker_id) {
+        Ok(task) => println!(
+            "Task {} is blocked by task {}, status: {}",
+            task.id, blocker_id, task.status
+        ),
+        Err(e) => eprintln!("Error blocking task: {}", e),
+    }
+}
+
+fn handle_task_unblock(conn: &mut SqliteConnection, task_id: i32, blocker_id: i32) {
+    match Task::unblock(conn, task_id, blocker_id) {
+        Ok(0) => println!("Task {} is not blocked by task {}", task_id, blocker_id),
+        Ok(_) => println!(
+            "Task {} is no longer blocked by task {}",
+            task_id, blocker_id
+        ),
+        Err(e) => eprintln!("Error unblocking task: {}", e),
+    }
+}
+
+fn handle_task_deps(conn: &mut SqliteConnection, task_id: i32) {
+    let task = match Task::find(conn, task_id) {
+        Ok(task) => task,
+        Err(diesel::result::Error::NotFound) => return eprintln!("Task not found"),
+        Err(e) => return eprintln!("Error finding task: {}", e),
+    };
+    println!("{}: {} ({})", task.id, task.title, task.status);
+    println!("Blocked by:");
+    if let Err(e) = print_dependency_tree(conn, task.id, 1, TaskDependency::blockers) {
+        eprintln!("Error listing blockers: {}", e);
+    }
+    println!("Blocking:");
+    if let Err(e) = print_dependency_tree(conn, task.id, 1, TaskDependency::dependents) {
+        eprintln!("Error listing dependents: {}", e);
+    }
+}
+
+/// Prints the tasks `next` finds for a task, indented by depth, each followed by its own.
+fn print_dependency_tree(
+    conn: &mut SqliteConnection,
+    task_id: i32,
+    depth: usize,
+    next: fn(&mut SqliteConnection, i32) -> Result<Vec<Task>, diesel::result::Error>,
+) -> Result<(), diesel::result::Error> {
+    let tasks = next(conn, task_id)?;
+    if tasks.is_empty() && depth == 1 {
+        println!("  -");
+    }
+    for task in tasks {
+        println!(
+            "{}{}: {} ({})",
+            "  ".repeat(depth),
+            task.id,
+            task.title,
+            task.status
+        );
+        print_dependency_tree(conn, task.id, depth + 1, next)?;
     }
+    Ok(())
 }
 
-fn handle_task_list(conn: &mut SqliteConnection) {
+fn handle_task_list(conn: &mut SqliteConnection, filter: Option<TaskFilter>) {
     println!("Listing tasks");
-    match Task::list(conn) {
+    match Task::list_filtered(conn, &filter.unwrap_or_default()) {
         Ok(tasks) => {
             if tasks.is_empty() {
                 println!("No tasks found");
@@ -140,17 +508,41 @@ pub fn handle_task_args(args: TaskArgs, connection: &mut SqliteConnection) {
             title,
             description,
             status,
-            project_id,
-        } => handle_task_add(connection, title, description, status, project_id),
+            project,
+        } => handle_task_add(connection, title, description, status, project),
         TaskCommands::Update {
-            task_id,
+            selection,
+            title,
+            description,
+            status,
+            project,
+            reason,
+        } => handle_task_update(
+            connection,
+            selection,
             title,
             description,
             status,
-            project_id,
-        } => handle_task_update(connection, task_id, title, description, status, project_id),
-        TaskCommands::Delete { task_id } => handle_task_delete(connection, task_id),
+            project,
+            reason,
+        ),
+        TaskCommands::Delete { selection } => handle_task_delete(connection, selection),
+        TaskCommands::Edit { task_id } => handle_task_edit(connection, task_id),
         TaskCommands::Read { task_id } => handle_task_read(connection, task_id),
-        TaskCommands::List => handle_task_list(connection),
+        TaskCommands::Comment { task_id, text } => {
+            handle_task_comment(connection, task_id, &text.join(" "))
+        }
+        TaskCommands::Link {
+            task_id,
+            target,
+            label,
+        } => handle_task_link(connection, task_id, &target, label.as_deref()),
+        TaskCommands::Unlink { task_id, target } => {
+            handle_task_unlink(connection, task_id, &target)
+        }
+        TaskCommands::Block { task_id, on } => handle_task_block(connection, task_id, on),
+        TaskCommands::Unblock { task_id, on } => handle_task_unblock(connection, task_id, on),
+        TaskCommands::Deps { task_id } => handle_task_deps(connection, task_id),
+        TaskCommands::List { filter } => handle_task_list(connection, filter),
     }
 }
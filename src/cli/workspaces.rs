@@ -0,0 +1,110 @@
+use crate::workspaces::Workspaces;
+use clap::{Args, Subcommand};
+use std::path::{self, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct WorkspaceArgs {
+    #[command(subcommand)]
+    command: WorkspaceCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum WorkspaceCommands {
+    /// Add a workspace with its own database, created on first use
+    Add {
+        /// Workspace name, such as 'work'
+        #[arg(required = true)]
+        name: String,
+        /// Path of the workspace's database
+        #[arg(required = true)]
+        database: PathBuf,
+    },
+    /// List the workspaces, marking the active one with '*'
+    List,
+    /// Make a workspace the active one
+    Switch {
+        /// Workspace name
+        #[arg(required = true)]
+        name: String,
+    },
+    /// Remove a workspace, keeping its database
+    Remove {
+        /// Workspace name
+        #[arg(required = true)]
+        name: String,
+    },
+}
+
+/// Loads the workspaces, changes them and saves them again.
+fn update_workspaces(change: impl FnOnce(&mut Workspaces) -> Result<String, String>) {
+    let result = Workspaces::load().and_then(|mut workspaces| {
+        let message = change(&mut workspaces)?;
+        workspaces.save()?;
+        Ok(message)
+    });
+    match result {
+        Ok(message) => println!("{}", message),
+        Err(e) => eprintln!("Error updating workspaces: {}", e),
+    }
+}
+
+fn handle_workspace_add(name: &str, database: PathBuf) {
+    let database = match path::absolute(&database) {
+        Ok(database) => database,
+        Err(e) => return eprintln!("Error adding workspace: {}", e),
+    };
+    update_workspaces(|workspaces| {
+        workspaces.add(name, database.clone())?;
+        Ok(format!(
+            "Workspace '{}' added with database {}, use it with `roll workspace switch {}`",
+            name,
+            database.display(),
+            name
+        ))
+    });
+}
+
+fn handle_workspace_list() {
+    let workspaces = match Workspaces::load() {
+        Ok(workspaces) => workspaces,
+        Err(e) => return eprintln!("Error listing workspaces: {}", e),
+    };
+    if workspaces.workspaces.is_empty() {
+        return println!("No workspaces, add one with `roll workspace add <name> <database>`");
+    }
+    for (name, database) in &workspaces.workspaces {
+        let marker = if workspaces.active.as_ref() == Some(name) {
+            '*'
+        } else {
+            ' '
+        };
+        println!("{} {} {}", marker, name, database.display());
+    }
+}
+
+fn handle_workspace_switch(name: &str) {
+    update_workspaces(|workspaces| {
+        workspaces.switch(name)?;
+        Ok(format!("Switched to workspace '{}'", name))
+    });
+}
+
+fn handle_workspace_remove(name: &str) {
+    update_workspaces(|workspaces| {
+        let database = workspaces.remove(name)?;
+        Ok(format!(
+            "Workspace '{}' removed, its database {} is kept",
+            name,
+            database.display()
+        ))
+    });
+}
+
+pub fn handle_workspace_args(args: &WorkspaceArgs) {
+    match &args.command {
+        WorkspaceCommands::Add { name, database } => handle_workspace_add(name, database.clone()),
+        WorkspaceCommands::List => handle_workspace_list(),
+        WorkspaceCommands::Switch { name } => handle_workspace_switch(name),
+        WorkspaceCommands::Remove { name } => handle_workspace_remove(name),
+    }
+}
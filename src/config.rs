@@ -0,0 +1,271 @@
+//! Settings read from `~/.config/on-a-roll/config.toml`, or the file named by `$ROLL_CONFIG`.
+//!
+//! ```toml
+//! [aliases.task_status]
+//! finished = "completed"
+//!
+//! [aliases.project_status]
+//! live = "active"
+//!
+//! [projects]
+//! auto_status = true
+//!
+//! [links]
+//! opener = "xdg-open"
+//!
+//! [git]
+//! move_tasks = true
+//!
+//! [git.keywords]
+//! fixes = "completed"
+//! implements = "in_review"
+//!
+//! [server]
+//! token = "s3cret"
+//!
+//! [events]
+//! hooks_dir = "/home/me/roll-hooks"
+//!
+//! [[events.webhooks]]
+//! url = "https://example.com/roll"
+//! events = ["task_completed"]
+//!
+//! [workflow]
+//! reason_required = ["blocked", "canceled"]
+//! terminal = ["completed", "canceled"]
+//!
+//! [workflow.transitions]
+//! todo = ["in_progress", "canceled"]
+//! in_progress = ["in_review", "blocked"]
+//! ```
+//!
+//! The binaries load the file once at startup with [`init`]; everything else reads it with
+//! [`get`], which falls back to the defaults when nothing was loaded, as in tests.
+use crate::models::task_status::TaskStatus;
+use crate::models::workflow::Workflow;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const CONFIG_ENV: &str = "ROLL_CONFIG";
+pub(crate) const APP_DIR: &str = "on-a-roll";
+const CONFIG_FILE: &str = "config.toml";
+const HOOKS_DIR: &str = "hooks";
+const DEFAULT_WEBHOOK_RETRIES: u32 = 3;
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+static DEFAULT_CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub aliases: Aliases,
+    pub projects: ProjectSettings,
+    pub links: LinkSettings,
+    pub git: GitSettings,
+    pub server: ServerSettings,
+    pub events: EventSettings,
+    pub workflow: Workflow,
+}
+
+/// Extra names for statuses, mapping an alias to a status name such as `in progress`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Aliases {
+    pub task_status: HashMap<String, String>,
+    pub project_status: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectSettings {
+    /// Whether projects follow the status their tasks suggest, instead of only showing it.
+    pub auto_status: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinkSettings {
+    /// Command opening task links, such as `firefox --new-tab`. Defaults to the system opener.
+    pub opener: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitSettings {
+    /// Whether commits move the tasks they mention with a keyword, as in `fixes #12`.
+    pub move_tasks: bool,
+    /// Keywords before a task mention in a commit message, mapped to the status they move the
+    /// task to. Replaces the default keywords if given.
+    pub keywords: HashMap<String, TaskStatus>,
+}
+
+impl Default for GitSettings {
+    fn default() -> Self {
+        let completing = [
+            "fix", "fixes", "fixed", "close", "closes", "closed", "resolve", "resolves", "resolved",
+        ];
+        let keywords = completing
+            .into_iter()
+            .map(|keyword| (keyword.to_string(), TaskStatus::Completed))
+            .chain(
+                ["implement", "implements", "implemented"]
+                    .into_iter()
+                    .map(|keyword| (keyword.to_string(), TaskStatus::InReview)),
+            )
+            .collect();
+        Self {
+            move_tasks: false,
+            keywords,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    /// Bearer token every request to `roll serve` must carry. Without one, any local program
+    /// can use the API.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventSettings {
+    /// Directory of scripts run on changes, named after the event such as
+    /// `on-task-completed`. [`Config::load`] defaults it to `hooks` next to the config file.
+    pub hooks_dir: Option<PathBuf>,
+    pub webhooks: Vec<Webhook>,
+    /// How often a failed webhook delivery is retried.
+    pub retries: u32,
+}
+
+impl Default for EventSettings {
+    fn default() -> Self {
+        Self {
+            hooks_dir: None,
+            webhooks: Vec::new(),
+            retries: DEFAULT_WEBHOOK_RETRIES,
+        }
+    }
+}
+
+/// A URL change events are posted to as JSON.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Webhook {
+    pub url: String,
+    /// Names of the events to post, such as `task_completed`. Empty for all of them.
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl Webhook {
+    /// Whether the webhook wants an event known by any of `names`.
+    pub fn wants(&self, names: &[String]) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| names.contains(event))
+    }
+}
+
+impl Config {
+    /// Path of the config file: `$ROLL_CONFIG` if set, otherwise `config.toml` in the
+    /// `on-a-roll` directory of the platform's config directory.
+    pub fn path() -> Option<PathBuf> {
+        match env::var_os(CONFIG_ENV) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|dir| dir.join(APP_DIR).join(CONFIG_FILE)),
+        }
+    }
+
+    /// Loads the config file, or the defaults if there is none. Unlike [`Config::default`],
+    /// which tests use, the hook scripts are looked for next to the config file.
+    pub fn load() -> Result<Self, String> {
+        let path = Self::path();
+        let mut config = match path.as_ref().filter(|path| path.exists()) {
+            Some(path) => {
+                let text = fs::read_to_string(path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                Self::parse(&text)
+                    .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?
+            }
+            None => Self::default(),
+        };
+        if config.events.hooks_dir.is_none() {
+            config.events.hooks_dir = path
+                .as_ref()
+                .and_then(|path| path.parent())
+                .map(|dir| dir.join(HOOKS_DIR));
+        }
+        Ok(config)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+}
+
+/// Makes `config` the one returned by [`get`]. Only the first call has an effect.
+pub fn init(config: Config) {
+    CONFIG.set(config).ok();
+}
+
+/// Returns the config passed to [`init`], or the defaults. Reading the defaults doesn't stop
+/// a later [`init`], which matters while the config file itself is parsed.
+pub fn get() -> &'static Config {
+    CONFIG
+        .get()
+        .unwrap_or_else(|| DEFAULT_CONFIG.get_or_init(Config::default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            "[aliases.task_status]\nfinished = \"completed\"\n\n[aliases.project_status]\nlive = \"active\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.aliases.task_status["finished"], "completed");
+        assert_eq!(config.aliases.project_status["live"], "active");
+        assert!(Config::parse("").unwrap().aliases.task_status.is_empty());
+        assert!(Config::parse("[aliases]\nunknown = {}\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_git_keywords() {
+        let defaults = Config::parse("[git]\nmove_tasks = true\n").unwrap().git;
+        assert!(defaults.move_tasks);
+        assert_eq!(defaults.keywords["fixes"], TaskStatus::Completed);
+
+        let config = Config::parse("[git.keywords]\nships = \"in_review\"\n").unwrap();
+        assert_eq!(
+            config.git.keywords,
+            HashMap::from([("ships".to_string(), TaskStatus::InReview)])
+        );
+        assert!(Config::parse("[git.keywords]\nships = \"someday\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_webhooks() {
+        let events = Config::parse(
+            "[[events.webhooks]]\nurl = \"http://localhost/a\"\n\n\
+             [[events.webhooks]]\nurl = \"http://localhost/b\"\nevents = [\"task_completed\"]\n",
+        )
+        .unwrap()
+        .events;
+        assert_eq!(events.retries, 3);
+        let names = [
+            "task_status_changed".to_string(),
+            "task_completed".to_string(),
+        ];
+        assert!(events.webhooks[0].wants(&names[..1]));
+        assert!(!events.webhooks[1].wants(&names[..1]));
+        assert!(events.webhooks[1].wants(&names));
+    }
+}
@@ -1,19 +1,71 @@
+use super::discovery::{DatabaseLocation, LOCAL_DATABASE, LOCAL_DIR};
+use crate::models::Project;
 use diesel::prelude::*;
 use dotenvy::dotenv;
-use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
 pub fn run_migrations(connection: &mut SqliteConnection) {
-    connection.run_pending_migrations(MIGRATIONS).unwrap();
+    migrate(connection).unwrap();
 }
-pub fn establish_connection() -> SqliteConnection {
-    dotenv().ok();
 
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let mut connection = SqliteConnection::establish(&database_url)
-        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url));
-    run_migrations(&mut connection);
+/// Runs the pending migrations, then the data migrations SQL can't express.
+fn migrate(connection: &mut SqliteConnection) -> Result<(), String> {
     connection
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|e| e.to_string())?;
+    Project::backfill_slugs(connection).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Finds the database for the current directory, or of the given workspace, see
+/// [`DatabaseLocation::discover`].
+pub fn database_location(workspace: Option<&str>) -> Result<DatabaseLocation, String> {
+    dotenv().ok();
+    DatabaseLocation::discover(workspace)
+}
+
+pub fn establish_connection() -> SqliteConnection {
+    let location =
+        database_location(None).unwrap_or_else(|e| panic!("Error finding the database: {}", e));
+    establish_connection_at(&location)
+}
+
+pub fn establish_connection_at(location: &DatabaseLocation) -> SqliteConnection {
+    try_establish_connection_at(location).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Connects to the database at `location`, creating and migrating it if needed.
+pub fn try_establish_connection_at(
+    location: &DatabaseLocation,
+) -> Result<SqliteConnection, String> {
+    if location.is_file() {
+        if let Some(dir) = Path::new(&location.url).parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Error creating {}: {}", dir.display(), e))?;
+        }
+    }
+    let mut connection = SqliteConnection::establish(&location.url)
+        .map_err(|e| format!("Error connecting to {}: {}", location.url, e))?;
+    migrate(&mut connection).map_err(|e| format!("Error migrating {}: {}", location.url, e))?;
+    Ok(connection)
+}
+
+/// Creates `.roll/roll.db` in `dir`. Returns its path, and whether it was created rather than
+/// there already.
+pub fn init_local_database(dir: &Path) -> Result<(PathBuf, bool), String> {
+    let local_dir = dir.join(LOCAL_DIR);
+    let path = local_dir.join(LOCAL_DATABASE);
+    if path.exists() {
+        return Ok((path, false));
+    }
+    fs::create_dir_all(&local_dir)
+        .map_err(|e| format!("Failed to create {}: {}", local_dir.display(), e))?;
+    let url = path.display().to_string();
+    let mut connection = SqliteConnection::establish(&url).map_err(|e| e.to_string())?;
+    migrate(&mut connection)?;
+    Ok((path, true))
 }
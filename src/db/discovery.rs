@@ -0,0 +1,191 @@
+//! Finding the database to use, like git finds its repository.
+//!
+//! A workspace given with `--workspace` always wins. Otherwise, starting in the current
+//! directory and walking up, the first directory with either of these holds the tasks:
+//!
+//! - `.roll.toml`, naming the database relative to the file: `database = "tasks/roll.db"`
+//! - `.roll/roll.db`, as created by `roll init`
+//!
+//! Without one, the database of the active workspace is used, then the one in `$DATABASE_URL`,
+//! and finally the global one in the platform's data directory, such as
+//! `~/.local/share/on-a-roll/roll.db`. No workspace is active until one is switched to, so
+//! adding workspaces leaves `$DATABASE_URL` in use.
+use crate::config::APP_DIR;
+use crate::workspaces::Workspaces;
+use serde::Deserialize;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const LOCAL_DIR: &str = ".roll";
+pub const LOCAL_DATABASE: &str = "roll.db";
+pub const POINTER_FILE: &str = ".roll.toml";
+
+/// The contents of a `.roll.toml` file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Pointer {
+    database: PathBuf,
+}
+
+/// Why a database was picked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseSource {
+    /// `.roll/roll.db` in a directory.
+    Local(PathBuf),
+    /// A `.roll.toml` file naming the database.
+    Pointer(PathBuf),
+    /// A workspace, given with `--workspace` or the active one.
+    Workspace(String),
+    /// `$DATABASE_URL`.
+    Environment,
+    Global,
+}
+
+/// The database to use and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseLocation {
+    /// Path of the database, or a SQLite URL when given by `$DATABASE_URL`.
+    pub url: String,
+    pub source: DatabaseSource,
+}
+
+impl DatabaseLocation {
+    /// Finds the database for the current directory, or of the given workspace.
+    pub fn discover(workspace: Option<&str>) -> Result<Self, String> {
+        let workspaces = Workspaces::load()?;
+        if let Some(name) = workspace {
+            return Ok(Self::workspace(name, workspaces.get(name)?));
+        }
+        let current_dir = env::current_dir().map_err(|e| e.to_string())?;
+        if let Some(location) = Self::find_local(&current_dir)? {
+            return Ok(location);
+        }
+        if let Some((name, path)) = workspaces.active() {
+            return Ok(Self::workspace(name, path));
+        }
+        if let Ok(url) = env::var("DATABASE_URL") {
+            return Ok(Self {
+                url,
+                source: DatabaseSource::Environment,
+            });
+        }
+        Self::global()
+    }
+
+    pub fn workspace(name: &str, database: &Path) -> Self {
+        Self {
+            url: database.display().to_string(),
+            source: DatabaseSource::Workspace(name.to_string()),
+        }
+    }
+
+    /// The database in the platform's data directory.
+    pub fn global() -> Result<Self, String> {
+        let data_dir = dirs::data_dir().ok_or("No data directory found, set DATABASE_URL")?;
+        Ok(Self {
+            url: data_dir
+                .join(APP_DIR)
+                .join(LOCAL_DATABASE)
+                .display()
+                .to_string(),
+            source: DatabaseSource::Global,
+        })
+    }
+
+    /// Looks for `.roll.toml` or `.roll/roll.db` in `start` and its ancestors. A `.roll.toml`
+    /// wins over a `.roll` directory next to it.
+    pub fn find_local(start: &Path) -> Result<Option<Self>, String> {
+        for dir in start.ancestors() {
+            let pointer = dir.join(POINTER_FILE);
+            if pointer.is_file() {
+                return Self::read_pointer(&pointer).map(Some);
+            }
+            let database = dir.join(LOCAL_DIR).join(LOCAL_DATABASE);
+            if database.is_file() {
+                return Ok(Some(Self {
+                    url: database.display().to_string(),
+                    source: DatabaseSource::Local(dir.to_path_buf()),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_pointer(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let pointer: Pointer =
+            toml::from_str(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Ok(Self {
+            url: dir.join(pointer.database).display().to_string(),
+            source: DatabaseSource::Pointer(path.to_path_buf()),
+        })
+    }
+
+    /// Whether the database is a file that can be created, so its directory is created first.
+    pub fn is_file(&self) -> bool {
+        !matches!(self.source, DatabaseSource::Environment)
+    }
+}
+
+impl fmt::Display for DatabaseLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            DatabaseSource::Local(dir) => write!(f, "{} (found in {})", self.url, dir.display()),
+            DatabaseSource::Pointer(path) => {
+                write!(f, "{} (named by {})", self.url, path.display())
+            }
+            DatabaseSource::Workspace(name) => write!(f, "{} (workspace {})", self.url, name),
+            DatabaseSource::Environment => write!(f, "{} (from DATABASE_URL)", self.url),
+            DatabaseSource::Global => write!(f, "{} (global)", self.url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_local_database_in_ancestor() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("src/deep");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(DatabaseLocation::find_local(&nested).unwrap(), None);
+
+        fs::create_dir(root.path().join(LOCAL_DIR)).unwrap();
+        fs::write(root.path().join(LOCAL_DIR).join(LOCAL_DATABASE), "").unwrap();
+
+        let location = DatabaseLocation::find_local(&nested).unwrap().unwrap();
+        assert_eq!(
+            location.url,
+            root.path().join(".roll/roll.db").display().to_string()
+        );
+        assert_eq!(
+            location.source,
+            DatabaseSource::Local(root.path().to_path_buf())
+        );
+    }
+
+    #[test]
+    fn test_pointer_file_wins() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join(LOCAL_DIR)).unwrap();
+        fs::write(root.path().join(LOCAL_DIR).join(LOCAL_DATABASE), "").unwrap();
+        let pointer = root.path().join(POINTER_FILE);
+        fs::write(&pointer, "database = \"tasks/shared.db\"\n").unwrap();
+
+        let location = DatabaseLocation::find_local(root.path()).unwrap().unwrap();
+        assert_eq!(
+            location.url,
+            root.path().join("tasks/shared.db").display().to_string()
+        );
+        assert_eq!(location.source, DatabaseSource::Pointer(pointer.clone()));
+
+        fs::write(&pointer, "path = \"roll.db\"\n").unwrap();
+        assert!(DatabaseLocation::find_local(root.path()).is_err());
+    }
+}
@@ -1,2 +1,9 @@
 mod projects;
+mod task_activity;
+mod task_comments;
+mod task_commits;
+mod task_dependencies;
+mod task_links;
+mod task_status_changes;
+mod task_tags;
 mod tasks;
@@ -1,9 +1,15 @@
+use crate::events::{self, Event};
 use crate::models::project_status::ProjectStatus;
-use crate::models::{NewProject, Project, UpdateProject};
+use crate::models::task_status::TaskStatus;
+use crate::models::{
+    normalize_project_name, slugify, NewProject, Project, ProjectLookupError, ProjectRollup,
+    UpdateProject, DEFAULT_PROJECT_TITLE,
+};
 use crate::schema::projects::dsl::projects;
 use diesel::prelude::*;
 use diesel::result::Error;
 use diesel::SqliteConnection;
+use std::collections::HashMap;
 impl Project {
     pub fn find(conn: &mut SqliteConnection, id: i32) -> Result<Self, Error> {
         projects.find(id).first(conn)
@@ -11,21 +17,109 @@ impl Project {
     pub fn list(conn: &mut SqliteConnection) -> Result<Vec<Self>, Error> {
         projects.load::<Self>(conn)
     }
+    /// Finds the projects whose title matches `name`, ignoring case, `-` and `_`.
+    pub fn find_by_name(conn: &mut SqliteConnection, name: &str) -> Result<Vec<Self>, Error> {
+        let name = normalize_project_name(name);
+        Ok(Self::list(conn)?
+            .into_iter()
+            .filter(|project| normalize_project_name(&project.title) == name)
+            .collect())
+    }
+    pub fn find_by_slug(conn: &mut SqliteConnection, project_slug: &str) -> Result<Self, Error> {
+        use crate::schema::projects::dsl::slug;
+        projects.filter(slug.eq(project_slug)).first(conn)
+    }
+
+    /// Resolves a project reference, which may be an id, a slug, a title or an unambiguous
+    /// prefix of a slug, in that order.
+    pub fn resolve(
+        conn: &mut SqliteConnection,
+        reference: &str,
+    ) -> Result<Self, ProjectLookupError> {
+        let reference = reference.trim();
+        if let Ok(id) = reference.parse::<i32>() {
+            match Self::find(conn, id) {
+                Err(Error::NotFound) => {}
+                result => return Ok(result?),
+            }
+        }
+        match Self::find_by_slug(conn, &reference.to_lowercase()) {
+            Err(Error::NotFound) => {}
+            result => return Ok(result?),
+        }
+        let mut candidates = Self::find_by_name(conn, reference)?;
+        if candidates.is_empty() {
+            let prefix = reference.to_lowercase();
+            candidates = Self::list(conn)?
+                .into_iter()
+                .filter(|project| project.slug.starts_with(&prefix))
+                .collect();
+        }
+        match candidates.len() {
+            0 => Err(ProjectLookupError::NotFound(reference.to_string())),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(ProjectLookupError::Ambiguous(
+                reference.to_string(),
+                candidates,
+            )),
+        }
+    }
+
     pub fn create(
         conn: &mut SqliteConnection,
         title: Option<&str>,
         description: Option<&str>,
         status: Option<ProjectStatus>,
     ) -> Result<Self, Error> {
-        let new_project = NewProject {
-            title,
-            description,
-            status,
-        };
-        diesel::insert_into(projects)
-            .values(&new_project)
-            .returning(Self::as_returning())
-            .get_result(conn)
+        events::transaction(conn, |conn| {
+            let project_slug = Self::unique_slug(conn, title.unwrap_or(DEFAULT_PROJECT_TITLE))?;
+            let new_project = NewProject {
+                title,
+                description,
+                status,
+                slug: &project_slug,
+            };
+            let project: Self = diesel::insert_into(projects)
+                .values(&new_project)
+                .returning(Self::as_returning())
+                .get_result(conn)?;
+            events::emit(conn, Event::ProjectCreated(project.clone()));
+            Ok(project)
+        })
+    }
+
+    /// Gives the projects that were migrated without a slug one derived from their title, oldest
+    /// first. Returns how many were given one.
+    pub fn backfill_slugs(conn: &mut SqliteConnection) -> Result<usize, Error> {
+        use crate::schema::projects::dsl::{id, slug};
+        conn.transaction(|conn| {
+            let unslugged: Vec<Self> = projects.filter(slug.like("#%")).order(id).load(conn)?;
+            for project in &unslugged {
+                let project_slug = Self::unique_slug(conn, &project.title)?;
+                diesel::update(projects.find(project.id))
+                    .set(slug.eq(project_slug))
+                    .execute(conn)?;
+            }
+            Ok(unslugged.len())
+        })
+    }
+
+    /// Derives a slug from the title, appending a number if the slug is already taken.
+    fn unique_slug(conn: &mut SqliteConnection, title: &str) -> Result<String, Error> {
+        use crate::schema::projects::dsl::slug;
+        let base = slugify(title);
+        let taken: Vec<String> = projects
+            .select(slug)
+            .filter(slug.eq(&base).or(slug.like(format!("{}-%", base))))
+            .load(conn)?;
+        if !taken.contains(&base) {
+            return Ok(base);
+        }
+        let candidate = (2..)
+            .map(|n| format!("{}-{}", base, n))
+            .find(|candidate| !taken.contains(candidate))
+            .expect("an unused slug exists");
+        Ok(candidate)
     }
 
     pub fn update(
@@ -41,13 +135,111 @@ impl Project {
             description,
             status,
         };
-        update_project.save_changes(conn)
+        events::transaction(conn, |conn| {
+            let project = Self::find(conn, id)?;
+            let updated: Self = update_project.save_changes(conn)?;
+            if updated.status != project.status {
+                let changed = Event::ProjectStatusChanged {
+                    project: updated.clone(),
+                    from: project.status,
+                    to: updated.status,
+                };
+                events::emit(conn, changed);
+            }
+            if (&updated.title, &updated.description) != (&project.title, &project.description) {
+                events::emit(conn, Event::ProjectUpdated(updated.clone()));
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Updates the status of all given projects in a single transaction.
+    pub fn update_many(
+        conn: &mut SqliteConnection,
+        ids: &[i32],
+        status: ProjectStatus,
+    ) -> Result<Vec<Self>, Error> {
+        events::transaction(conn, |conn| {
+            ids.iter()
+                .map(|&id| Self::update(conn, id, None, None, Some(status)))
+                .collect()
+        })
+    }
+
+    /// Sums up the tasks of a project.
+    pub fn rollup(conn: &mut SqliteConnection, project_id: i32) -> Result<ProjectRollup, Error> {
+        Ok(Self::rollups_where(conn, Some(project_id))?
+            .remove(&project_id)
+            .unwrap_or_default())
+    }
+
+    /// Sums up the tasks of every project with tasks, in a single query.
+    pub fn rollups(conn: &mut SqliteConnection) -> Result<HashMap<i32, ProjectRollup>, Error> {
+        Self::rollups_where(conn, None)
+    }
+
+    fn rollups_where(
+        conn: &mut SqliteConnection,
+        only_project_id: Option<i32>,
+    ) -> Result<HashMap<i32, ProjectRollup>, Error> {
+        use crate::schema::tasks::dsl::{id, project_id, status, tasks};
+        use diesel::dsl::{count_star, min};
+        let mut query = tasks
+            .filter(project_id.is_not_null())
+            .group_by((project_id, status))
+            .select((project_id, status, count_star(), min(id)))
+            .into_boxed();
+        if let Some(only_project_id) = only_project_id {
+            query = query.filter(project_id.eq(only_project_id));
+        }
+        let groups: Vec<(Option<i32>, TaskStatus, i64, Option<i32>)> = query.load(conn)?;
+        let mut rollups: HashMap<i32, ProjectRollup> = HashMap::new();
+        for (group_project_id, group_status, count, first_id) in groups {
+            if let Some(group_project_id) = group_project_id {
+                rollups
+                    .entry(group_project_id)
+                    .or_default()
+                    .add(group_status, count, first_id);
+            }
+        }
+        Ok(rollups)
+    }
+
+    /// Moves a project to the status its tasks suggest, see
+    /// [`ProjectRollup::suggested_status`]. Returns the project if its status changed.
+    pub fn apply_suggested_status(
+        conn: &mut SqliteConnection,
+        id: i32,
+    ) -> Result<Option<Self>, Error> {
+        let project = Self::find(conn, id)?;
+        match Self::rollup(conn, id)?.suggested_status(project.status) {
+            Some(suggestion) => {
+                Self::update(conn, id, None, None, Some(suggestion.status)).map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     pub fn delete(conn: &mut SqliteConnection, project_id: i32) -> Result<usize, Error> {
         use crate::schema::projects::id;
-        diesel::delete(projects)
-            .filter(id.eq(&project_id))
-            .execute(conn)
+        events::transaction(conn, |conn| {
+            let project = Self::find(conn, project_id).optional()?;
+            let deleted = diesel::delete(projects)
+                .filter(id.eq(&project_id))
+                .execute(conn)?;
+            if let Some(project) = project.filter(|_| deleted > 0) {
+                events::emit(conn, Event::ProjectDeleted(project));
+            }
+            Ok(deleted)
+        })
+    }
+
+    /// Deletes all given projects in a single transaction.
+    pub fn delete_many(conn: &mut SqliteConnection, ids: &[i32]) -> Result<usize, Error> {
+        events::transaction(conn, |conn| {
+            ids.iter()
+                .map(|&id| Self::delete(conn, id))
+                .sum::<Result<usize, Error>>()
+        })
     }
 }
@@ -0,0 +1,12 @@
+use crate::models::{TaskActivity, TaskComment, TaskCommit, TaskStatusChange};
+use diesel::result::Error;
+use diesel::SqliteConnection;
+impl TaskActivity {
+    /// Lists the comments, status changes and commits of a task, oldest first.
+    pub fn list_for_task(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Self>, Error> {
+        let comments = TaskComment::list_for_task(conn, task)?;
+        let changes = TaskStatusChange::list_for_task(conn, task)?;
+        let commits = TaskCommit::list_for_task(conn, task)?;
+        Ok(Self::merge(comments, changes, commits))
+    }
+}
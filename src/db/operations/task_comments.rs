@@ -0,0 +1,30 @@
+use crate::models::{NewTaskComment, TaskComment};
+use crate::schema::task_comments::dsl::{created_at, id, task_comments, task_id};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::SqliteConnection;
+impl TaskComment {
+    /// Lists the comments on a task, oldest first.
+    pub fn list_for_task(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Self>, Error> {
+        task_comments
+            .filter(task_id.eq(task))
+            .order((created_at, id))
+            .load(conn)
+    }
+
+    /// Adds a comment to a task, without surrounding whitespace.
+    pub fn add(conn: &mut SqliteConnection, task: i32, body: &str) -> Result<Self, Error> {
+        let new_comment = NewTaskComment {
+            task_id: task,
+            body: body.trim(),
+        };
+        diesel::insert_into(task_comments)
+            .values(&new_comment)
+            .returning(Self::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn remove_all(conn: &mut SqliteConnection, task: i32) -> Result<usize, Error> {
+        diesel::delete(task_comments.filter(task_id.eq(task))).execute(conn)
+    }
+}
@@ -0,0 +1,38 @@
+use crate::models::{NewTaskCommit, TaskCommit};
+use crate::schema::task_commits::dsl::{created_at, id, task_commits, task_id};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::SqliteConnection;
+impl TaskCommit {
+    /// Lists the commits mentioning a task, oldest first.
+    pub fn list_for_task(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Self>, Error> {
+        task_commits
+            .filter(task_id.eq(task))
+            .order((created_at, id))
+            .load(conn)
+    }
+
+    /// Records a commit against a task. Returns `None` if it was recorded before.
+    pub fn record(
+        conn: &mut SqliteConnection,
+        task: i32,
+        sha: &str,
+        summary: &str,
+    ) -> Result<Option<Self>, Error> {
+        let new_commit = NewTaskCommit {
+            task_id: task,
+            sha,
+            summary: summary.trim(),
+        };
+        diesel::insert_into(task_commits)
+            .values(&new_commit)
+            .on_conflict_do_nothing()
+            .returning(Self::as_returning())
+            .get_result(conn)
+            .optional()
+    }
+
+    pub fn remove_all(conn: &mut SqliteConnection, task: i32) -> Result<usize, Error> {
+        diesel::delete(task_commits.filter(task_id.eq(task))).execute(conn)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::models::{DependencyError, Task, TaskDependency};
+use crate::schema::task_dependencies::dsl::{blocker_id, task_dependencies, task_id};
+use crate::schema::tasks;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::SqliteConnection;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+impl TaskDependency {
+    /// Makes `task` depend on `blocker`. Fails if either task doesn't exist or if the blocker
+    /// already depends on the task, directly or through other tasks. Adding a dependency that
+    /// exists is a no-op.
+    pub fn add(
+        conn: &mut SqliteConnection,
+        task: i32,
+        blocker: i32,
+    ) -> Result<usize, DependencyError> {
+        if task == blocker {
+            return Err(DependencyError::SelfDependency(task));
+        }
+        conn.transaction(|conn| {
+            for id in [task, blocker] {
+                if Task::find(conn, id).optional()?.is_none() {
+                    return Err(DependencyError::TaskNotFound(id));
+                }
+            }
+            if let Some(chain) = Self::blocker_chain(conn, blocker, task)? {
+                return Err(DependencyError::Cycle(
+                    std::iter::once(task).chain(chain).collect(),
+                ));
+            }
+            Ok(diesel::insert_or_ignore_into(task_dependencies)
+                .values(&TaskDependency {
+                    task_id: task,
+                    blocker_id: blocker,
+                })
+                .execute(conn)?)
+        })
+    }
+
+    pub fn remove(conn: &mut SqliteConnection, task: i32, blocker: i32) -> Result<usize, Error> {
+        diesel::delete(task_dependencies.filter(task_id.eq(task).and(blocker_id.eq(blocker))))
+            .execute(conn)
+    }
+
+    /// Removes the dependencies of a task in both directions.
+    pub fn remove_all(conn: &mut SqliteConnection, task: i32) -> Result<usize, Error> {
+        diesel::delete(task_dependencies.filter(task_id.eq(task).or(blocker_id.eq(task))))
+            .execute(conn)
+    }
+
+    /// Lists the tasks `task` directly depends on, by id.
+    pub fn blockers(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Task>, Error> {
+        tasks::table
+            .filter(
+                tasks::id.eq_any(
+                    task_dependencies
+                        .filter(task_id.eq(task))
+                        .select(blocker_id),
+                ),
+            )
+            .order(tasks::id)
+            .load(conn)
+    }
+
+    /// Lists the tasks directly depending on `task`, by id.
+    pub fn dependents(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Task>, Error> {
+        tasks::table
+            .filter(
+                tasks::id.eq_any(
+                    task_dependencies
+                        .filter(blocker_id.eq(task))
+                        .select(task_id),
+                ),
+            )
+            .order(tasks::id)
+            .load(conn)
+    }
+
+    /// Finds the shortest chain of blockers leading from `from` to `to`, both included.
+    fn blocker_chain(
+        conn: &mut SqliteConnection,
+        from: i32,
+        to: i32,
+    ) -> Result<Option<Vec<i32>>, Error> {
+        let edges: Vec<(i32, i32)> = task_dependencies.select((task_id, blocker_id)).load(conn)?;
+        let mut reached_from = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut chain = vec![to];
+                let mut step = to;
+                while step != from {
+                    step = reached_from[&step];
+                    chain.push(step);
+                }
+                chain.reverse();
+                return Ok(Some(chain));
+            }
+            for &(_, next) in edges.iter().filter(|(task, _)| *task == current) {
+                if let Entry::Vacant(entry) = reached_from.entry(next) {
+                    entry.insert(current);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
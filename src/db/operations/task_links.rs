@@ -0,0 +1,71 @@
+use crate::models::{detect_links, Link, NewTaskLink, Task, TaskLink};
+use crate::schema::task_links::dsl::{created_at, id, label, target, task_id, task_links};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::SqliteConnection;
+impl TaskLink {
+    /// Lists the links added to a task, oldest first.
+    pub fn list_for_task(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Self>, Error> {
+        task_links
+            .filter(task_id.eq(task))
+            .order((created_at, id))
+            .load(conn)
+    }
+
+    /// Links a URL or file path to a task. Linking the same target again only updates its
+    /// label.
+    pub fn add(
+        conn: &mut SqliteConnection,
+        task: i32,
+        link_target: &str,
+        link_label: Option<&str>,
+    ) -> Result<Self, Error> {
+        let new_link = NewTaskLink {
+            task_id: task,
+            target: link_target.trim(),
+            label: link_label.map(str::trim).filter(|l| !l.is_empty()),
+        };
+        diesel::insert_into(task_links)
+            .values(&new_link)
+            .on_conflict((task_id, target))
+            .do_update()
+            .set(label.eq(new_link.label))
+            .returning(Self::as_returning())
+            .get_result(conn)
+    }
+
+    /// Removes a link from a task, returning how many were removed.
+    pub fn remove(
+        conn: &mut SqliteConnection,
+        task: i32,
+        link_target: &str,
+    ) -> Result<usize, Error> {
+        diesel::delete(
+            task_links
+                .filter(task_id.eq(task))
+                .filter(target.eq(link_target.trim())),
+        )
+        .execute(conn)
+    }
+
+    pub fn remove_all(conn: &mut SqliteConnection, task: i32) -> Result<usize, Error> {
+        diesel::delete(task_links.filter(task_id.eq(task))).execute(conn)
+    }
+}
+
+impl Link {
+    /// Lists the links added to a task, followed by the ones found in its description.
+    pub fn list_for_task(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Self>, Error> {
+        let description = Task::find(conn, task)?.description.unwrap_or_default();
+        let mut links: Vec<Self> = TaskLink::list_for_task(conn, task)?
+            .into_iter()
+            .map(Self::from)
+            .collect();
+        for link in detect_links(&description) {
+            if !links.iter().any(|known| known.target == link.target) {
+                links.push(link);
+            }
+        }
+        Ok(links)
+    }
+}
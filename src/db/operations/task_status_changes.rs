@@ -0,0 +1,39 @@
+use crate::models::task_status::TaskStatus;
+use crate::models::{NewTaskStatusChange, TaskStatusChange};
+use crate::schema::task_status_changes::dsl::{changed_at, id, task_id, task_status_changes};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::SqliteConnection;
+impl TaskStatusChange {
+    /// Lists the status changes of a task, oldest first.
+    pub fn list_for_task(conn: &mut SqliteConnection, task: i32) -> Result<Vec<Self>, Error> {
+        task_status_changes
+            .filter(task_id.eq(task))
+            .order((changed_at, id))
+            .load(conn)
+    }
+
+    /// Records a status change. A blank reason is stored as no reason.
+    pub fn record(
+        conn: &mut SqliteConnection,
+        task: i32,
+        from_status: TaskStatus,
+        to_status: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<Self, Error> {
+        let new_change = NewTaskStatusChange {
+            task_id: task,
+            from_status,
+            to_status,
+            reason: reason.map(str::trim).filter(|reason| !reason.is_empty()),
+        };
+        diesel::insert_into(task_status_changes)
+            .values(&new_change)
+            .returning(Self::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn remove_all(conn: &mut SqliteConnection, task: i32) -> Result<usize, Error> {
+        diesel::delete(task_status_changes.filter(task_id.eq(task))).execute(conn)
+    }
+}
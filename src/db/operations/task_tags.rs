@@ -0,0 +1,43 @@
+use crate::models::TaskTag;
+use crate::schema::task_tags::dsl::{tag, task_id, task_tags};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::SqliteConnection;
+impl TaskTag {
+    pub fn list_for_task(conn: &mut SqliteConnection, id: i32) -> Result<Vec<String>, Error> {
+        task_tags
+            .filter(task_id.eq(id))
+            .select(tag)
+            .order(tag)
+            .load(conn)
+    }
+
+    /// Every tag in use, in alphabetical order.
+    pub fn list_all(conn: &mut SqliteConnection) -> Result<Vec<String>, Error> {
+        task_tags.select(tag).distinct().order(tag).load(conn)
+    }
+
+    /// Tags a task. Adding a tag the task already has is a no-op.
+    pub fn add(conn: &mut SqliteConnection, id: i32, name: &str) -> Result<usize, Error> {
+        diesel::insert_or_ignore_into(task_tags)
+            .values((task_id.eq(id), tag.eq(name)))
+            .execute(conn)
+    }
+
+    /// Tags all given tasks in a single transaction.
+    pub fn add_many(conn: &mut SqliteConnection, ids: &[i32], name: &str) -> Result<usize, Error> {
+        conn.transaction(|conn| {
+            ids.iter()
+                .map(|&id| Self::add(conn, id, name))
+                .sum::<Result<usize, Error>>()
+        })
+    }
+
+    pub fn remove(conn: &mut SqliteConnection, id: i32, name: &str) -> Result<usize, Error> {
+        diesel::delete(task_tags.filter(task_id.eq(id).and(tag.eq(name)))).execute(conn)
+    }
+
+    pub fn remove_all(conn: &mut SqliteConnection, id: i32) -> Result<usize, Error> {
+        diesel::delete(task_tags.filter(task_id.eq(id))).execute(conn)
+    }
+}
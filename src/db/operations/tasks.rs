@@ -1,6 +1,13 @@
+use crate::config;
+use crate::events::{self, Event};
 use crate::models::task_status::TaskStatus;
-use crate::models::{NewTask, Task, UpdateTask};
+use crate::models::{
+    is_open, DependencyError, NewTask, Project, ProjectLookupError, QuickAdd, QuickAddError, Task,
+    TaskComment, TaskCommit, TaskDependency, TaskFilter, TaskLink, TaskStatusChange, TaskTag,
+    TaskUpdateError, UpdateTask,
+};
 use crate::schema::tasks::dsl::tasks;
+use crate::schema::{task_dependencies, task_tags};
 use diesel::prelude::*;
 use diesel::result::Error;
 use diesel::SqliteConnection;
@@ -11,6 +18,47 @@ impl Task {
     pub fn list(conn: &mut SqliteConnection) -> Result<Vec<Self>, Error> {
         tasks.load::<Self>(conn)
     }
+    /// Lists the tasks matching the filter. Fails if the filter's project can't be resolved.
+    pub fn list_filtered(
+        conn: &mut SqliteConnection,
+        filter: &TaskFilter,
+    ) -> Result<Vec<Self>, ProjectLookupError> {
+        use crate::schema::tasks::dsl::{id, project_id, status};
+        let filter_project_id = match &filter.project {
+            Some(reference) => Some(Project::resolve(conn, reference)?.id),
+            None => None,
+        };
+        let mut query = tasks.into_boxed();
+        if let Some(filter_status) = filter.status {
+            query = query.filter(status.eq(filter_status));
+        }
+        if let Some(filter_project_id) = filter_project_id {
+            query = query.filter(project_id.eq(filter_project_id));
+        }
+        if let Some(filter_tag) = &filter.tag {
+            let tagged = task_tags::table
+                .filter(task_tags::tag.eq(filter_tag.clone()))
+                .select(task_tags::task_id);
+            query = query.filter(id.eq_any(tagged));
+        }
+        if let Some(ready) = filter.ready {
+            let blockers = diesel::alias!(crate::schema::tasks as blockers);
+            let waiting = task_dependencies::table
+                .inner_join(blockers.on(blockers.field(id).eq(task_dependencies::blocker_id)))
+                .filter(
+                    blockers
+                        .field(status)
+                        .ne_all([TaskStatus::Completed, TaskStatus::Canceled]),
+                )
+                .select(task_dependencies::task_id);
+            query = if ready {
+                query.filter(id.ne_all(waiting))
+            } else {
+                query.filter(id.eq_any(waiting))
+            };
+        }
+        Ok(query.load::<Self>(conn)?)
+    }
     pub fn create(
         conn: &mut SqliteConnection,
         title: Option<&str>,
@@ -23,11 +71,59 @@ impl Task {
             description,
             status,
             project_id,
+            priority: None,
+            due_date: None,
         };
-        diesel::insert_into(tasks)
-            .values(&new_task)
-            .returning(Self::as_returning())
-            .get_result(conn)
+        events::transaction(conn, |conn| {
+            let task: Self = diesel::insert_into(tasks)
+                .values(&new_task)
+                .returning(Self::as_returning())
+                .get_result(conn)?;
+            events::emit(conn, Event::TaskCreated(task.clone()));
+            Self::follow_project_statuses(conn, &[task.project_id])?;
+            Ok(task)
+        })
+    }
+
+    /// Creates a task from quick-add syntax together with its tags, in a single transaction.
+    pub fn quick_add(
+        conn: &mut SqliteConnection,
+        quick_add: &QuickAdd,
+    ) -> Result<Self, QuickAddError> {
+        events::transaction(conn, |conn| {
+            let project_id = match &quick_add.project {
+                Some(name) => match Project::resolve(conn, name) {
+                    Ok(project) => Some(project.id),
+                    Err(ProjectLookupError::NotFound(_)) => {
+                        return Err(QuickAddError::UnknownProject(name.clone()))
+                    }
+                    Err(ProjectLookupError::Ambiguous(_, matches)) => {
+                        let titles = matches.into_iter().map(|p| p.title).collect();
+                        return Err(QuickAddError::AmbiguousProject(name.clone(), titles));
+                    }
+                    Err(ProjectLookupError::Database(error)) => return Err(error.into()),
+                },
+                None => None,
+            };
+            let new_task = NewTask {
+                title: Some(&quick_add.title),
+                description: None,
+                status: None,
+                project_id,
+                priority: quick_add.priority,
+                due_date: quick_add.due_date,
+            };
+            let task: Self = diesel::insert_into(tasks)
+                .values(&new_task)
+                .returning(Self::as_returning())
+                .get_result(conn)?;
+            for tag in &quick_add.tags {
+                TaskTag::add(conn, task.id, tag)?;
+            }
+            events::emit(conn, Event::TaskCreated(task.clone()));
+            Self::follow_project_statuses(conn, &[task.project_id])?;
+            Ok(task)
+        })
     }
 
     pub fn update(
@@ -37,19 +133,247 @@ impl Task {
         description: Option<&str>,
         status: Option<TaskStatus>,
         project_id: Option<i32>,
+    ) -> Result<Self, TaskUpdateError> {
+        Self::update_with_reason(conn, id, title, description, status, project_id, None)
+    }
+
+    /// Updates a task. A status change is checked against the configured workflow and recorded
+    /// together with `reason`.
+    pub fn update_with_reason(
+        conn: &mut SqliteConnection,
+        id: i32,
+        title: Option<&str>,
+        description: Option<&str>,
+        status: Option<TaskStatus>,
+        project_id: Option<i32>,
+        reason: Option<&str>,
+    ) -> Result<Self, TaskUpdateError> {
+        events::transaction(conn, |conn| {
+            let task = Self::find(conn, id)?;
+            if let Some(status) = status.filter(|status| *status != task.status) {
+                config::get().workflow.check(task.status, status, reason)?;
+                TaskStatusChange::record(conn, id, task.status, status, reason)?;
+            }
+            let update_task = UpdateTask {
+                id,
+                title,
+                description,
+                status,
+                project_id,
+            };
+            let updated: Self = update_task.save_changes(conn)?;
+            if updated.status != task.status {
+                let changed = Event::TaskStatusChanged {
+                    task: updated.clone(),
+                    from: task.status,
+                    to: updated.status,
+                    reason: reason.map(str::to_string),
+                };
+                events::emit(conn, changed);
+            }
+            if (&updated.title, &updated.description, updated.project_id)
+                != (&task.title, &task.description, task.project_id)
+            {
+                events::emit(conn, Event::TaskUpdated(updated.clone()));
+            }
+            if !is_open(updated.status) && is_open(task.status) {
+                Self::unblock_dependents(conn, &updated)?;
+            }
+            if updated.status != task.status || updated.project_id != task.project_id {
+                Self::follow_project_statuses(conn, &[task.project_id, updated.project_id])?;
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Makes a task depend on a blocker. If the blocker is still open, an open task also moves
+    /// to Blocked, as far as the workflow allows.
+    pub fn block(
+        conn: &mut SqliteConnection,
+        id: i32,
+        blocker_id: i32,
+    ) -> Result<Self, DependencyError> {
+        events::transaction(conn, |conn| {
+            TaskDependency::add(conn, id, blocker_id)?;
+            let task = Self::find(conn, id)?;
+            let blocker = Self::find(conn, blocker_id)?;
+            if !is_open(blocker.status)
+                || matches!(
+                    task.status,
+                    TaskStatus::Blocked | TaskStatus::Completed | TaskStatus::Canceled
+                )
+            {
+                return Ok(task);
+            }
+            let reason = format!("Blocked by task {} ({})", blocker.id, blocker.title);
+            match Self::set_status(conn, id, TaskStatus::Blocked, &reason) {
+                Ok(task) => Ok(task),
+                Err(TaskUpdateError::Workflow(_)) => Ok(task),
+                Err(TaskUpdateError::Database(error)) => Err(error.into()),
+            }
+        })
+    }
+
+    /// Removes a dependency. A blocked task whose remaining blockers are all resolved moves
+    /// back to the status it had before it was blocked. Returns the number of removed
+    /// dependencies.
+    pub fn unblock(
+        conn: &mut SqliteConnection,
+        id: i32,
+        blocker_id: i32,
+    ) -> Result<usize, TaskUpdateError> {
+        events::transaction(conn, |conn| {
+            let removed = TaskDependency::remove(conn, id, blocker_id)?;
+            if removed > 0 {
+                let task = Self::find(conn, id)?;
+                let reason = format!("No longer blocked by task {}", blocker_id);
+                Self::release_if_unblocked(conn, &task, &reason)?;
+            }
+            Ok(removed)
+        })
+    }
+
+    /// Releases the blocked dependents of a task that was just completed or canceled.
+    fn unblock_dependents(conn: &mut SqliteConnection, blocker: &Self) -> Result<(), Error> {
+        let reason = format!("Unblocked by task {} ({})", blocker.id, blocker.title);
+        for dependent in TaskDependency::dependents(conn, blocker.id)? {
+            Self::release_if_unblocked(conn, &dependent, &reason)?;
+        }
+        Ok(())
+    }
+
+    /// Moves a blocked task whose blockers are all completed or canceled back to the status it
+    /// had before it was blocked, or to Todo. Leaves it blocked if the workflow doesn't allow
+    /// the move.
+    fn release_if_unblocked(
+        conn: &mut SqliteConnection,
+        task: &Self,
+        reason: &str,
+    ) -> Result<(), Error> {
+        if task.status != TaskStatus::Blocked
+            || TaskDependency::blockers(conn, task.id)?
+                .iter()
+                .any(|blocker| is_open(blocker.status))
+        {
+            return Ok(());
+        }
+        let previous = TaskStatusChange::list_for_task(conn, task.id)?
+            .into_iter()
+            .rev()
+            .find(|change| change.to_status == TaskStatus::Blocked)
+            .map(|change| change.from_status)
+            .filter(|status| !matches!(status, TaskStatus::Blocked | TaskStatus::Completed))
+            .unwrap_or(TaskStatus::Todo);
+        match Self::set_status(conn, task.id, previous, reason) {
+            Ok(_) | Err(TaskUpdateError::Workflow(_)) => Ok(()),
+            Err(TaskUpdateError::Database(error)) => Err(error),
+        }
+    }
+
+    fn set_status(
+        conn: &mut SqliteConnection,
+        id: i32,
+        status: TaskStatus,
+        reason: &str,
+    ) -> Result<Self, TaskUpdateError> {
+        Self::update_with_reason(conn, id, None, None, Some(status), None, Some(reason))
+    }
+
+    /// Assigns a task to a project, or removes it from its project if `project_id` is `None`.
+    pub fn set_project(
+        conn: &mut SqliteConnection,
+        id: i32,
+        project_id: Option<i32>,
     ) -> Result<Self, Error> {
-        let update_task = UpdateTask {
-            id,
-            title,
-            description,
-            status,
-            project_id,
-        };
-        update_task.save_changes(conn)
+        use crate::schema::tasks::dsl::project_id as project_id_column;
+        events::transaction(conn, |conn| {
+            let previous_project_id = Self::find(conn, id)?.project_id;
+            let task: Self = diesel::update(tasks.find(id))
+                .set(project_id_column.eq(project_id))
+                .returning(Self::as_returning())
+                .get_result(conn)?;
+            if task.project_id != previous_project_id {
+                events::emit(conn, Event::TaskUpdated(task.clone()));
+            }
+            Self::follow_project_statuses(conn, &[previous_project_id, task.project_id])?;
+            Ok(task)
+        })
+    }
+
+    /// Moves the given projects to the status their tasks suggest, if the config asks for it.
+    fn follow_project_statuses(
+        conn: &mut SqliteConnection,
+        project_ids: &[Option<i32>],
+    ) -> Result<(), Error> {
+        if config::get().projects.auto_status {
+            for &project_id in project_ids.iter().flatten() {
+                Project::apply_suggested_status(conn, project_id)?;
+            }
+        }
+        Ok(())
     }
 
+    /// Applies the same update to all given tasks in a single transaction.
+    pub fn update_many(
+        conn: &mut SqliteConnection,
+        ids: &[i32],
+        title: Option<&str>,
+        description: Option<&str>,
+        status: Option<TaskStatus>,
+        project_id: Option<i32>,
+        reason: Option<&str>,
+    ) -> Result<Vec<Self>, TaskUpdateError> {
+        events::transaction(conn, |conn| {
+            ids.iter()
+                .map(|&id| {
+                    Self::update_with_reason(
+                        conn,
+                        id,
+                        title,
+                        description,
+                        status,
+                        project_id,
+                        reason,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Deletes a task with everything attached to it, releasing the tasks it was blocking.
     pub fn delete(conn: &mut SqliteConnection, task_id: i32) -> Result<usize, Error> {
         use crate::schema::tasks::dsl::id;
-        diesel::delete(tasks).filter(id.eq(&task_id)).execute(conn)
+        events::transaction(conn, |conn| {
+            let task = Self::find(conn, task_id).optional()?;
+            let project_id = task.as_ref().and_then(|task| task.project_id);
+            let dependents = TaskDependency::dependents(conn, task_id)?;
+            TaskTag::remove_all(conn, task_id)?;
+            TaskStatusChange::remove_all(conn, task_id)?;
+            TaskComment::remove_all(conn, task_id)?;
+            TaskCommit::remove_all(conn, task_id)?;
+            TaskLink::remove_all(conn, task_id)?;
+            TaskDependency::remove_all(conn, task_id)?;
+            let deleted = diesel::delete(tasks)
+                .filter(id.eq(&task_id))
+                .execute(conn)?;
+            if let Some(task) = task.filter(|_| deleted > 0) {
+                let reason = format!("Blocking task {} ({}) was deleted", task.id, task.title);
+                events::emit(conn, Event::TaskDeleted(task));
+                for dependent in &dependents {
+                    Self::release_if_unblocked(conn, dependent, &reason)?;
+                }
+            }
+            Self::follow_project_statuses(conn, &[project_id])?;
+            Ok(deleted)
+        })
+    }
+
+    /// Deletes all given tasks in a single transaction.
+    pub fn delete_many(conn: &mut SqliteConnection, ids: &[i32]) -> Result<usize, Error> {
+        events::transaction(conn, |conn| {
+            ids.iter()
+                .map(|&id| Self::delete(conn, id))
+                .sum::<Result<usize, Error>>()
+        })
     }
 }
@@ -0,0 +1,384 @@
+//! Editing tasks in an external editor.
+//!
+//! A task is written to a temporary file as front-matter of `key: value` lines holding its
+//! fields, followed by its description as a Markdown body:
+//!
+//! ```text
+//! ---
+//! title: "Fix login bug"
+//! status: In Progress
+//! project_id: 2
+//! reason:
+//! ---
+//!
+//! Users get logged out after *five* minutes.
+//! ```
+//!
+//! Values may be quoted like `"..."` or `'...'`. A `project_id` of `null` or nothing removes
+//! the task from its project, and `reason` explains a status change.
+use crate::events;
+use crate::models::task_status::TaskStatus;
+use crate::models::workflow::WorkflowError;
+use crate::models::{Task, TaskUpdateError};
+use diesel::SqliteConnection;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+const FRONT_MATTER_DELIMITER: &str = "---";
+const DEFAULT_EDITOR: &str = "vi";
+
+/// The fields of a task as read back from an edited document.
+#[derive(Debug, PartialEq)]
+pub struct TaskDocument {
+    pub title: String,
+    pub status: TaskStatus,
+    /// The project to move the task to, `Some(None)` to remove it from its project. `None` if
+    /// the `project_id` line was deleted, which keeps the project.
+    pub project_id: Option<Option<i32>>,
+    pub reason: Option<String>,
+    pub description: String,
+}
+
+/// Renders a task as front-matter plus Markdown body.
+pub fn render_document(task: &Task) -> String {
+    let project_id = match task.project_id {
+        Some(project_id) => project_id.to_string(),
+        None => "null".to_string(),
+    };
+    format!(
+        "{delimiter}\ntitle: {}\nstatus: {}\nproject_id: {}\nreason:\n{delimiter}\n\n{}\n",
+        quote(&task.title),
+        task.status,
+        project_id,
+        task.description.as_deref().unwrap_or("").trim_end(),
+        delimiter = FRONT_MATTER_DELIMITER,
+    )
+}
+
+/// Parses a document written by [`render_document`]. Comment lines starting with `#` before the
+/// front-matter are ignored. Returns `Ok(None)` if the document is empty, which aborts the edit.
+pub fn parse_document(text: &str) -> Result<Option<TaskDocument>, String> {
+    let mut lines = text
+        .lines()
+        .skip_while(|line| line.trim().is_empty() || line.starts_with('#'))
+        .peekable();
+    if lines.peek().is_none() {
+        return Ok(None);
+    }
+    if lines.next().map(str::trim_end) != Some(FRONT_MATTER_DELIMITER) {
+        return Err(format!(
+            "The document must start with a '{}' line",
+            FRONT_MATTER_DELIMITER
+        ));
+    }
+    let mut title = None;
+    let mut status = None;
+    let mut project_id = None;
+    let mut reason = None;
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == FRONT_MATTER_DELIMITER {
+            closed = true;
+            break;
+        }
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid front-matter line, expected 'key: value': {}", line))?;
+        let key = key.trim();
+        let value = unquote(value.trim())?;
+        let field = match key {
+            "title" => &mut title,
+            "status" => &mut status,
+            "project_id" => &mut project_id,
+            "reason" => &mut reason,
+            _ => return Err(format!("Unknown front-matter key: {}", key)),
+        };
+        if field.replace(value).is_some() {
+            return Err(format!("Duplicate front-matter key: {}", key));
+        }
+    }
+    if !closed {
+        return Err(format!(
+            "The front-matter must be closed by a '{}' line",
+            FRONT_MATTER_DELIMITER
+        ));
+    }
+    let title = title.unwrap_or_default().trim().to_string();
+    if title.is_empty() {
+        return Err("The title must not be empty".to_string());
+    }
+    let status = status
+        .ok_or("The front-matter must have a status")?
+        .parse::<TaskStatus>()?;
+    let project_id = match project_id.as_deref().map(str::trim) {
+        None => None,
+        Some("" | "null" | "~") => Some(None),
+        Some(project_id) => {
+            Some(Some(project_id.parse().map_err(|_| {
+                format!("Invalid project_id: {}", project_id)
+            })?))
+        }
+    };
+    let reason = reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    let description = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    Ok(Some(TaskDocument {
+        title,
+        status,
+        project_id,
+        reason,
+        description,
+    }))
+}
+
+/// Quotes a value in double quotes, escaping backslashes, quotes and line breaks.
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// Reads a front-matter value, which is taken as is unless it is quoted like `"..."`, with
+/// backslash escapes, or `'...'`, with `''` for a quote.
+fn unquote(value: &str) -> Result<String, String> {
+    let unterminated = || format!("Unterminated quote: {}", value);
+    if let Some(quoted) = value.strip_prefix('"') {
+        let quoted = quoted.strip_suffix('"').ok_or_else(unterminated)?;
+        let mut unquoted = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unquoted.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('\\') => unquoted.push('\\'),
+                Some('"') => unquoted.push('"'),
+                Some('n') => unquoted.push('\n'),
+                Some('t') => unquoted.push('\t'),
+                Some(c) => return Err(format!("Invalid escape \\{} in {}", c, value)),
+                None => return Err(unterminated()),
+            }
+        }
+        Ok(unquoted)
+    } else if let Some(quoted) = value.strip_prefix('\'') {
+        let quoted = quoted.strip_suffix('\'').ok_or_else(unterminated)?;
+        Ok(quoted.replace("''", "'"))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Opens a task in the editor configured by `$VISUAL` or `$EDITOR` and saves the result.
+///
+/// Returns `Ok(None)` if the edit was aborted by emptying the document or left the task
+/// unchanged.
+pub fn edit_task(conn: &mut SqliteConnection, id: i32) -> Result<Option<Task>, String> {
+    edit_task_with(conn, id, &editor_command())
+}
+
+/// Like [`edit_task`], but with an explicit editor command such as `code --wait`.
+pub fn edit_task_with(
+    conn: &mut SqliteConnection,
+    id: i32,
+    editor: &str,
+) -> Result<Option<Task>, String> {
+    let task = Task::find(conn, id).map_err(|e| match e {
+        diesel::result::Error::NotFound => format!("Task {} not found", id),
+        e => e.to_string(),
+    })?;
+    let original = render_document(&task);
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("roll-task-{}-", id))
+        .suffix(".md")
+        .tempfile()
+        .map_err(|e| e.to_string())?;
+    file.write_all(original.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    loop {
+        run_editor(editor, file.path())?;
+        let text = fs::read_to_string(file.path()).map_err(|e| e.to_string())?;
+        if text == original {
+            return Ok(None);
+        }
+        let error = match parse_document(&text) {
+            Ok(Some(document)) => match save_document(conn, id, &document) {
+                Ok(task) => return Ok(Some(task)),
+                Err(TaskUpdateError::Workflow(error @ WorkflowError::ReasonRequired(_))) => {
+                    format!("{}, give one in the reason line", error)
+                }
+                Err(TaskUpdateError::Workflow(error)) => error.to_string(),
+                Err(TaskUpdateError::Database(error)) => return Err(error.to_string()),
+            },
+            Ok(None) => return Ok(None),
+            Err(error) => error,
+        };
+        // Reopen the editor with the error on top, so nothing that was typed gets lost.
+        fs::write(file.path(), with_error_comment(&text, &error)).map_err(|e| e.to_string())?;
+    }
+}
+
+/// Saves an edited document to the task, checking a status change against the workflow.
+fn save_document(
+    conn: &mut SqliteConnection,
+    id: i32,
+    document: &TaskDocument,
+) -> Result<Task, TaskUpdateError> {
+    events::transaction(conn, |conn| {
+        let task = Task::update_with_reason(
+            conn,
+            id,
+            Some(&document.title),
+            Some(&document.description),
+            Some(document.status),
+            document.project_id.flatten(),
+            document.reason.as_deref(),
+        )?;
+        match document.project_id {
+            Some(None) if task.project_id.is_some() => Ok(Task::set_project(conn, id, None)?),
+            _ => Ok(task),
+        }
+    })
+}
+
+/// Replaces any leading comment lines of `text` with a comment describing `error`.
+fn with_error_comment(text: &str, error: &str) -> String {
+    let body = text
+        .lines()
+        .skip_while(|line| line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut commented = String::new();
+    for line in error.lines() {
+        commented.push_str(&format!("# Error: {}\n", line));
+    }
+    commented.push_str("# Fix the error and save again, or empty the file to abort.\n");
+    commented + &body + "\n"
+}
+
+fn editor_command() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+fn run_editor(editor: &str, path: &Path) -> Result<(), String> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("No editor configured")?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("Failed to launch editor '{}': {}", editor, e))?;
+    if !status.success() {
+        return Err(format!("Editor '{}' exited with {}", editor, status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            id: 1,
+            title: "Fix: login bug".to_string(),
+            description: Some("# Notes\n\nLogged out after *five* minutes.".to_string()),
+            status: TaskStatus::InProgress,
+            project_id: Some(2),
+            priority: None,
+            due_date: None,
+        }
+    }
+
+    #[test]
+    fn test_render_and_parse_round_trip() {
+        let document = parse_document(&render_document(&task())).unwrap().unwrap();
+
+        assert_eq!(
+            document,
+            TaskDocument {
+                title: "Fix: login bug".to_string(),
+                status: TaskStatus::InProgress,
+                project_id: Some(Some(2)),
+                reason: None,
+                description: "# Notes\n\nLogged out after *five* minutes.".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_quoted_title_round_trip() {
+        let task = Task {
+            title: "Say \"hi\" \\ 'bye'\nagain".to_string(),
+            ..task()
+        };
+        let document = parse_document(&render_document(&task)).unwrap().unwrap();
+
+        assert_eq!(document.title, task.title);
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let parse = |front_matter: &str| {
+            parse_document(&format!("---\n{}\n---\n", front_matter))
+                .unwrap()
+                .unwrap()
+        };
+
+        let document = parse("title: 'It''s: done'\nstatus: Done\nreason: Shipped it");
+        assert_eq!(document.title, "It's: done");
+        assert_eq!(document.status, TaskStatus::Completed);
+        assert_eq!(document.project_id, None);
+        assert_eq!(document.reason.as_deref(), Some("Shipped it"));
+
+        for project_id in ["project_id: null", "project_id:", "project_id: ''"] {
+            let document = parse(&format!("title: x\nstatus: Todo\n{}", project_id));
+            assert_eq!(document.project_id, Some(None), "{}", project_id);
+        }
+    }
+
+    #[test]
+    fn test_parse_skips_error_comments() {
+        let text = with_error_comment(&render_document(&task()), "Invalid task status: Done");
+
+        assert!(text.starts_with("# Error: Invalid task status: Done\n"));
+        assert!(parse_document(&text).unwrap().is_some());
+        // Replacing the error comment does not pile up old errors.
+        let text = with_error_comment(&text, "another");
+        assert_eq!(text.matches("# Error:").count(), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_document("title: no delimiters").is_err());
+        assert!(parse_document("---\ntitle: unclosed\nstatus: Todo\n").is_err());
+        assert!(parse_document("---\ntitle: x\nstatus: Someday\n---\n").is_err());
+        assert!(parse_document("---\ntitle: x\nstatus: Todo\nowner: me\n---\n").is_err());
+        assert!(parse_document("---\ntitle: ' '\nstatus: Todo\n---\n").is_err());
+        assert!(parse_document("---\ntitle: x\ntitle: y\nstatus: Todo\n---\n").is_err());
+        assert!(parse_document("---\ntitle: x\n---\n").is_err());
+        assert!(parse_document("---\ntitle: \"x\nstatus: Todo\n---\n").is_err());
+        assert!(parse_document("---\ntitle: x\nstatus: Todo\nproject_id: two\n---\n").is_err());
+        assert!(parse_document("---\njust words\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_empty_document_aborts() {
+        assert_eq!(parse_document("").unwrap(), None);
+        assert_eq!(parse_document("# Error: oops\n\n").unwrap(), None);
+    }
+}
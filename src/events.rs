@@ -0,0 +1,221 @@
+//! Change events for tasks and projects.
+//!
+//! The task and project operations [`emit`] an event for every change they make. Events
+//! emitted inside a [`transaction`] are held back until it commits, and dropped if it rolls
+//! back, so consumers never hear about changes that didn't happen. They are then passed to the
+//! listeners added with [`subscribe`], and delivered to the configured webhooks and hook
+//! scripts, see [`crate::notify`].
+use crate::models::project_status::ProjectStatus;
+use crate::models::status_name::StatusName;
+use crate::models::task_status::TaskStatus;
+use crate::models::{Project, ProjectJson, Task, TaskJson};
+use crate::notify;
+use diesel::connection::{AnsiTransactionManager, TransactionManager};
+use diesel::SqliteConnection;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+type Listener = Arc<dyn Fn(&Event) + Send + Sync>;
+
+static LISTENERS: Mutex<Vec<Listener>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Events emitted inside a transaction that hasn't committed yet.
+    static PENDING: RefCell<Vec<Event>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A change to a task or project.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    TaskCreated(Task),
+    /// The title, description or project of a task changed.
+    TaskUpdated(Task),
+    TaskStatusChanged {
+        task: Task,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<String>,
+    },
+    TaskDeleted(Task),
+    ProjectCreated(Project),
+    /// The title or description of a project changed.
+    ProjectUpdated(Project),
+    ProjectStatusChanged {
+        project: Project,
+        from: ProjectStatus,
+        to: ProjectStatus,
+    },
+    ProjectDeleted(Project),
+}
+
+impl Event {
+    /// Name of the event, such as `task_status_changed`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::TaskCreated(_) => "task_created",
+            Event::TaskUpdated(_) => "task_updated",
+            Event::TaskStatusChanged { .. } => "task_status_changed",
+            Event::TaskDeleted(_) => "task_deleted",
+            Event::ProjectCreated(_) => "project_created",
+            Event::ProjectUpdated(_) => "project_updated",
+            Event::ProjectStatusChanged { .. } => "project_status_changed",
+            Event::ProjectDeleted(_) => "project_deleted",
+        }
+    }
+
+    /// The names webhooks and hook scripts can subscribe to the event by: its own, and for
+    /// a status change also one naming the new status, such as `task_completed`.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = vec![self.name().to_string()];
+        match self {
+            Event::TaskStatusChanged { to, .. } => names.push(format!("task_{}", to.key())),
+            Event::ProjectStatusChanged { to, .. } => names.push(format!("project_{}", to.key())),
+            _ => {}
+        }
+        names
+    }
+
+    /// The event as posted to webhooks and given to hook scripts, such as
+    /// `{"event": "task_status_changed", "task": {...}, "from": "Todo", "to": "Completed"}`.
+    pub fn to_json(&self) -> Value {
+        let mut value = match self.clone() {
+            Event::TaskCreated(task) | Event::TaskUpdated(task) | Event::TaskDeleted(task) => {
+                json!({ "task": TaskJson::from(task) })
+            }
+            Event::TaskStatusChanged {
+                task,
+                from,
+                to,
+                reason,
+            } => json!({
+                "task": TaskJson::from(task),
+                "from": from.to_string(),
+                "to": to.to_string(),
+                "reason": reason,
+            }),
+            Event::ProjectCreated(project)
+            | Event::ProjectUpdated(project)
+            | Event::ProjectDeleted(project) => json!({ "project": ProjectJson::from(project) }),
+            Event::ProjectStatusChanged { project, from, to } => json!({
+                "project": ProjectJson::from(project),
+                "from": from.to_string(),
+                "to": to.to_string(),
+            }),
+        };
+        value["event"] = json!(self.name());
+        value
+    }
+}
+
+/// Calls `listener` with every published event, on the thread that made the change.
+pub fn subscribe(listener: impl Fn(&Event) + Send + Sync + 'static) {
+    LISTENERS
+        .lock()
+        .expect("listeners are never poisoned")
+        .push(Arc::new(listener));
+}
+
+/// Publishes an event, or holds it back until the current transaction commits.
+pub fn emit(conn: &mut SqliteConnection, event: Event) {
+    PENDING.with_borrow_mut(|pending| pending.push(event));
+    if !in_transaction(conn) {
+        publish_pending();
+    }
+}
+
+/// Runs `f` in a transaction like [`diesel::Connection::transaction`], publishing the events
+/// emitted inside once the outermost transaction commits. When a nested transaction, a
+/// savepoint, rolls back, only the events emitted inside it are dropped.
+pub fn transaction<T, E, F>(conn: &mut SqliteConnection, f: F) -> Result<T, E>
+where
+    F: FnOnce(&mut SqliteConnection) -> Result<T, E>,
+    E: From<diesel::result::Error>,
+{
+    use diesel::Connection;
+    let outermost = !in_transaction(conn);
+    let before = PENDING.with_borrow(Vec::len);
+    let result = conn.transaction(f);
+    match result {
+        Ok(_) if outermost => publish_pending(),
+        Ok(_) => {}
+        Err(_) => PENDING.with_borrow_mut(|pending| pending.truncate(before)),
+    }
+    result
+}
+
+fn in_transaction(conn: &mut SqliteConnection) -> bool {
+    !matches!(
+        AnsiTransactionManager::transaction_manager_status_mut(conn).transaction_depth(),
+        Ok(None)
+    )
+}
+
+fn publish_pending() {
+    let events = PENDING.with_borrow_mut(std::mem::take);
+    if events.is_empty() {
+        return;
+    }
+    // Listeners may emit or subscribe themselves, so they are called without the lock.
+    let listeners = LISTENERS
+        .lock()
+        .expect("listeners are never poisoned")
+        .clone();
+    for event in &events {
+        for listener in &listeners {
+            listener(event);
+        }
+    }
+    notify::deliver(events);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(status: TaskStatus) -> Task {
+        Task {
+            id: 7,
+            title: "Water plants".to_string(),
+            description: None,
+            status,
+            project_id: None,
+            priority: None,
+            due_date: None,
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(
+            Event::TaskCreated(task(TaskStatus::Todo)).names(),
+            vec!["task_created"]
+        );
+        let changed = Event::TaskStatusChanged {
+            task: task(TaskStatus::InProgress),
+            from: TaskStatus::Todo,
+            to: TaskStatus::InProgress,
+            reason: None,
+        };
+        assert_eq!(
+            changed.names(),
+            vec!["task_status_changed", "task_in_progress"]
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let changed = Event::TaskStatusChanged {
+            task: task(TaskStatus::Completed),
+            from: TaskStatus::InReview,
+            to: TaskStatus::Completed,
+            reason: Some("Shipped".to_string()),
+        };
+        let value = changed.to_json();
+        assert_eq!(value["event"], "task_status_changed");
+        assert_eq!(value["task"]["id"], 7);
+        assert_eq!(value["from"], "In Review");
+        assert_eq!(value["to"], "Completed");
+        assert_eq!(value["reason"], "Shipped");
+    }
+}
@@ -0,0 +1,131 @@
+//! Working with a local git repository through the `git` command line.
+//!
+//! `roll git hook install` puts two hooks into a repository:
+//!
+//! - `commit-msg` warns about tasks a commit message mentions that don't exist, while the
+//!   message can still be fixed.
+//! - `post-commit` records the new commit against the tasks it mentions, and moves the ones
+//!   mentioned with a keyword such as `fixes #12`.
+//!
+//! Neither hook ever stops a commit: they do nothing where roll isn't installed, and a failing
+//! `roll git hook` is ignored.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Marks hooks written by [`install_hooks`], so they can be replaced but others are kept.
+const HOOK_MARKER: &str = "# Installed by `roll git hook install`.";
+
+/// The hooks [`install_hooks`] writes, and the `roll git hook` subcommand each one runs.
+const HOOKS: [(&str, &str); 2] = [
+    ("commit-msg", "roll git hook commit-msg \"$1\""),
+    ("post-commit", "roll git hook post-commit"),
+];
+
+/// A commit as recorded against tasks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+}
+
+impl Commit {
+    /// The first line of the message.
+    pub fn summary(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+}
+
+/// Runs git in `repo`, returning its output without the trailing newline.
+fn git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => format!("git {} exited with {}", args.join(" "), output.status),
+            stderr => stderr.to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end_matches('\n')
+        .to_string())
+}
+
+/// Checks out a branch, creating it from the current `HEAD` if it doesn't exist yet. Returns
+/// whether it was created.
+pub fn start_branch(repo: &Path, branch: &str) -> Result<bool, String> {
+    let exists = git(
+        repo,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch),
+        ],
+    )
+    .is_ok();
+    if exists {
+        git(repo, &["checkout", "--quiet", branch])?;
+    } else {
+        git(repo, &["checkout", "--quiet", "-b", branch])?;
+    }
+    Ok(!exists)
+}
+
+/// The commit `HEAD` points at.
+pub fn head_commit(repo: &Path) -> Result<Commit, String> {
+    let output = git(repo, &["log", "-1", "--format=%H%n%B"])?;
+    let (sha, message) = output.split_once('\n').unwrap_or((&output, ""));
+    Ok(Commit {
+        sha: sha.to_string(),
+        message: message.trim().to_string(),
+    })
+}
+
+/// Writes the `commit-msg` and `post-commit` hooks to the hooks directory of a repository.
+/// Hooks not written by roll are only replaced if `force` is set.
+pub fn install_hooks(repo: &Path, force: bool) -> Result<Vec<PathBuf>, String> {
+    let hooks_dir = repo.join(git(repo, &["rev-parse", "--git-path", "hooks"])?);
+    for (name, _) in HOOKS {
+        let path = hooks_dir.join(name);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if !force && !existing.is_empty() && !existing.contains(HOOK_MARKER) {
+            return Err(format!(
+                "{} already exists, use --force to replace it",
+                path.display()
+            ));
+        }
+    }
+    fs::create_dir_all(&hooks_dir).map_err(|e| e.to_string())?;
+    let mut paths = Vec::new();
+    for (name, command) in HOOKS {
+        let path = hooks_dir.join(name);
+        fs::write(
+            &path,
+            format!(
+                "#!/bin/sh\n{}\ncommand -v roll >/dev/null 2>&1 || exit 0\n{} || true\n",
+                HOOK_MARKER, command
+            ),
+        )
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        make_executable(&path)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
@@ -1,5 +1,13 @@
 pub mod cli;
+pub mod config;
 pub mod db;
+pub mod editor;
+pub mod events;
+pub mod git;
 pub mod models;
+pub mod notify;
+pub mod opener;
 pub mod schema;
+pub mod server;
 pub mod tui;
+pub mod workspaces;
@@ -0,0 +1,52 @@
+use super::projects::Project;
+use super::tasks::Task;
+use serde::Serialize;
+
+/// A task as `roll serve` and the change events show it, with statuses and priorities named
+/// as on the command line.
+#[derive(Debug, Serialize)]
+pub struct TaskJson {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub project_id: Option<i32>,
+    pub priority: Option<String>,
+    pub due_date: Option<String>,
+}
+
+impl From<Task> for TaskJson {
+    fn from(task: Task) -> Self {
+        Self {
+            id: task.id,
+            title: task.title,
+            description: task.description,
+            status: task.status.to_string(),
+            project_id: task.project_id,
+            priority: task.priority.map(|priority| priority.to_string()),
+            due_date: task.due_date.map(|date| date.to_string()),
+        }
+    }
+}
+
+/// A project as `roll serve` and the change events show it.
+#[derive(Debug, Serialize)]
+pub struct ProjectJson {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub slug: String,
+}
+
+impl From<Project> for ProjectJson {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id,
+            title: project.title,
+            description: project.description,
+            status: project.status.to_string(),
+            slug: project.slug,
+        }
+    }
+}
@@ -1,7 +1,32 @@
+mod json;
+mod project_rollup;
 pub mod project_status;
 mod projects;
+mod quick_add;
+pub mod status_name;
+mod task_activity;
+mod task_comments;
+mod task_commits;
+mod task_dependencies;
+mod task_filter;
+mod task_links;
+pub mod task_priority;
 pub mod task_status;
+mod task_status_changes;
+mod task_tags;
 mod tasks;
+pub mod workflow;
 
+pub use json::*;
+pub use project_rollup::*;
 pub use projects::*;
+pub use quick_add::*;
+pub use task_activity::*;
+pub use task_comments::*;
+pub use task_commits::*;
+pub use task_dependencies::*;
+pub use task_filter::*;
+pub use task_links::*;
+pub use task_status_changes::*;
+pub use task_tags::*;
 pub use tasks::*;
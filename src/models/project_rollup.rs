@@ -0,0 +1,207 @@
+use super::project_status::ProjectStatus;
+use super::task_status::TaskStatus;
+use strum::VariantArray;
+
+/// Summary of the tasks in a project.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectRollup {
+    /// Number of tasks per status, for the statuses that occur.
+    pub counts: Vec<(TaskStatus, i64)>,
+    /// Id of the oldest task that is neither completed nor canceled.
+    pub oldest_open_task: Option<i32>,
+}
+
+/// A project status the rollup points to, with why.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatusSuggestion {
+    pub status: ProjectStatus,
+    pub reason: &'static str,
+}
+
+/// Whether a task with this status still needs work.
+pub fn is_open(status: TaskStatus) -> bool {
+    !matches!(status, TaskStatus::Completed | TaskStatus::Canceled)
+}
+
+impl ProjectRollup {
+    /// Adds `count` tasks with `status`, `first_id` being the lowest id among them.
+    pub fn add(&mut self, status: TaskStatus, count: i64, first_id: Option<i32>) {
+        match self.counts.iter_mut().find(|(s, _)| *s == status) {
+            Some((_, existing)) => *existing += count,
+            None => {
+                self.counts.push((status, count));
+                self.counts.sort_by_key(|(s, _)| {
+                    TaskStatus::VARIANTS.iter().position(|variant| variant == s)
+                });
+            }
+        }
+        if is_open(status) {
+            self.oldest_open_task = match (self.oldest_open_task, first_id) {
+                (Some(oldest), Some(id)) => Some(oldest.min(id)),
+                (oldest, id) => oldest.or(id),
+            };
+        }
+    }
+
+    pub fn count(&self, status: TaskStatus) -> i64 {
+        self.counts
+            .iter()
+            .find(|(s, _)| *s == status)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn total(&self) -> i64 {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn open(&self) -> i64 {
+        self.counts
+            .iter()
+            .filter(|(status, _)| is_open(*status))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Share of completed tasks, leaving out canceled ones. `None` if there is nothing to do.
+    pub fn percent_complete(&self) -> Option<i64> {
+        let relevant = self.total() - self.count(TaskStatus::Canceled);
+        (relevant > 0).then(|| self.count(TaskStatus::Completed) * 100 / relevant)
+    }
+
+    /// One line such as `5 tasks, 2 open, 50% complete`.
+    pub fn summary(&self) -> String {
+        let percent = self
+            .percent_complete()
+            .map_or("-".to_string(), |percent| format!("{}%", percent));
+        format!(
+            "{} tasks, {} open, {} complete",
+            self.total(),
+            self.open(),
+            percent
+        )
+    }
+
+    /// Suggests a status for a project with this rollup, if it differs from `current`:
+    ///
+    /// - Completed once all tasks are completed or canceled, or Canceled if they all are.
+    /// - Blocked while any task is blocked.
+    /// - Active once a blocked project has no blocked tasks, a planned project has started
+    ///   tasks, or a completed project has open tasks again.
+    ///
+    /// Projects on hold or canceled are left alone.
+    pub fn suggested_status(&self, current: ProjectStatus) -> Option<StatusSuggestion> {
+        let suggestion = |status, reason| Some(StatusSuggestion { status, reason });
+        let suggested = if matches!(current, ProjectStatus::OnHold | ProjectStatus::Canceled)
+            || self.total() == 0
+        {
+            None
+        } else if self.open() == 0 && self.count(TaskStatus::Completed) > 0 {
+            suggestion(
+                ProjectStatus::Completed,
+                "all tasks are completed or canceled",
+            )
+        } else if self.open() == 0 {
+            suggestion(ProjectStatus::Canceled, "all tasks are canceled")
+        } else if self.count(TaskStatus::Blocked) > 0 {
+            suggestion(ProjectStatus::Blocked, "a task is blocked")
+        } else if current == ProjectStatus::Blocked {
+            suggestion(ProjectStatus::Active, "no task is blocked anymore")
+        } else if current == ProjectStatus::Completed {
+            suggestion(ProjectStatus::Active, "there are open tasks")
+        } else if current == ProjectStatus::Planning && self.total() > self.count(TaskStatus::Todo)
+        {
+            suggestion(ProjectStatus::Active, "work on its tasks has started")
+        } else {
+            None
+        };
+        suggested.filter(|suggestion| suggestion.status != current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rollup(counts: &[(TaskStatus, i64)]) -> ProjectRollup {
+        let mut rollup = ProjectRollup::default();
+        for (i, (status, count)) in counts.iter().enumerate() {
+            rollup.add(*status, *count, Some(10 - i as i32));
+        }
+        rollup
+    }
+
+    #[test]
+    fn test_rollup_counts() {
+        let rollup = rollup(&[
+            (TaskStatus::Completed, 2),
+            (TaskStatus::Todo, 1),
+            (TaskStatus::Canceled, 1),
+            (TaskStatus::InProgress, 1),
+        ]);
+
+        assert_eq!(
+            rollup.counts,
+            vec![
+                (TaskStatus::Todo, 1),
+                (TaskStatus::InProgress, 1),
+                (TaskStatus::Completed, 2),
+                (TaskStatus::Canceled, 1),
+            ]
+        );
+        assert_eq!(rollup.total(), 5);
+        assert_eq!(rollup.open(), 2);
+        assert_eq!(rollup.percent_complete(), Some(50));
+        assert_eq!(rollup.oldest_open_task, Some(7));
+        assert_eq!(rollup.summary(), "5 tasks, 2 open, 50% complete");
+        assert_eq!(ProjectRollup::default().percent_complete(), None);
+    }
+
+    #[test]
+    fn test_suggested_status() {
+        let suggest = |counts: &[(TaskStatus, i64)], current| {
+            rollup(counts)
+                .suggested_status(current)
+                .map(|suggestion| suggestion.status)
+        };
+
+        assert_eq!(
+            suggest(
+                &[(TaskStatus::Completed, 2), (TaskStatus::Canceled, 1)],
+                ProjectStatus::Active
+            ),
+            Some(ProjectStatus::Completed)
+        );
+        assert_eq!(
+            suggest(&[(TaskStatus::Canceled, 1)], ProjectStatus::Active),
+            Some(ProjectStatus::Canceled)
+        );
+        assert_eq!(
+            suggest(
+                &[(TaskStatus::Blocked, 1), (TaskStatus::Todo, 1)],
+                ProjectStatus::Active
+            ),
+            Some(ProjectStatus::Blocked)
+        );
+        assert_eq!(
+            suggest(&[(TaskStatus::Todo, 1)], ProjectStatus::Blocked),
+            Some(ProjectStatus::Active)
+        );
+        assert_eq!(
+            suggest(&[(TaskStatus::InProgress, 1)], ProjectStatus::Planning),
+            Some(ProjectStatus::Active)
+        );
+        assert_eq!(
+            suggest(&[(TaskStatus::Todo, 1)], ProjectStatus::Planning),
+            None
+        );
+        assert_eq!(
+            suggest(&[(TaskStatus::Blocked, 1)], ProjectStatus::Blocked),
+            None
+        );
+        assert_eq!(
+            suggest(&[(TaskStatus::Completed, 1)], ProjectStatus::OnHold),
+            None
+        );
+        assert_eq!(suggest(&[], ProjectStatus::Active), None);
+    }
+}
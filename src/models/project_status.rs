@@ -1,8 +1,12 @@
+use super::status_name::{parse_status, possible_value, StatusName};
+use crate::config;
+use clap::builder::PossibleValue;
 use ratatui::widgets::ListItem;
+use std::collections::HashMap;
 use std::str::FromStr;
-use strum::EnumIter;
+use strum::{EnumIter, VariantArray};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum, EnumIter, VariantArray)]
 pub enum ProjectStatus {
     Planning,
     Active,
@@ -13,37 +17,58 @@ pub enum ProjectStatus {
     Canceled,
 }
 
+impl StatusName for ProjectStatus {
+    const KIND: &'static str = "project status";
+
+    fn name(self) -> &'static str {
+        match self {
+            ProjectStatus::Planning => "Planning",
+            ProjectStatus::Active => "Active",
+            ProjectStatus::OnHold => "On Hold",
+            ProjectStatus::Blocked => "Blocked",
+            ProjectStatus::InReview => "In Review",
+            ProjectStatus::Completed => "Completed",
+            ProjectStatus::Canceled => "Canceled",
+        }
+    }
+
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            ProjectStatus::Completed => &["done"],
+            ProjectStatus::Canceled => &["cancelled"],
+            _ => &[],
+        }
+    }
+
+    fn configured_aliases() -> &'static HashMap<String, String> {
+        &config::get().aliases.project_status
+    }
+}
+
 impl FromStr for ProjectStatus {
     type Err = String;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
-            "Planning" => Ok(ProjectStatus::Planning),
-            "Active" => Ok(ProjectStatus::Active),
-            "On Hold" => Ok(ProjectStatus::OnHold),
-            "Blocked" => Ok(ProjectStatus::Blocked),
-            "In Review" => Ok(ProjectStatus::InReview),
-            "Completed" => Ok(ProjectStatus::Completed),
-            "Canceled" => Ok(ProjectStatus::Canceled),
-            _ => Err(format!("Invalid project status: {}", input)),
-        }
+        parse_status(input)
     }
 }
 
 impl std::fmt::Display for ProjectStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let status_str = match self {
-            ProjectStatus::Planning => "Planning",
-            ProjectStatus::Active => "Active",
-            ProjectStatus::OnHold => "On Hold",
-            ProjectStatus::Blocked => "Blocked",
-            ProjectStatus::InReview => "In Review",
-            ProjectStatus::Completed => "Completed",
-            ProjectStatus::Canceled => "Canceled",
-        };
-        write!(f, "{}", status_str)
+        write!(f, "{}", self.name())
     }
 }
+
+impl clap::ValueEnum for ProjectStatus {
+    fn value_variants<'a>() -> &'a [Self] {
+        Self::VARIANTS
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(possible_value(*self))
+    }
+}
+
 impl<'a> From<ProjectStatus> for ListItem<'a> {
     fn from(status: ProjectStatus) -> Self {
         ListItem::new(format!("{}", status))
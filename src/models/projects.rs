@@ -12,6 +12,8 @@ pub struct Project {
     pub title: String,
     pub description: Option<String>,
     pub status: ProjectStatus,
+    /// Unique, URL-safe name derived from the title when the project is created.
+    pub slug: String,
 }
 
 impl<'a> From<Project> for ListItem<'a> {
@@ -26,6 +28,7 @@ pub struct NewProject<'a> {
     pub title: Option<&'a str>,
     pub description: Option<&'a str>,
     pub status: Option<ProjectStatus>,
+    pub slug: &'a str,
 }
 #[derive(Debug, AsChangeset, Identifiable)]
 #[diesel(table_name = projects)]
@@ -35,3 +38,73 @@ pub struct UpdateProject<'a> {
     pub description: Option<&'a str>,
     pub status: Option<ProjectStatus>,
 }
+
+/// Turns a title into a slug such as `garden-shed`: lowercase letters and digits, with every
+/// other run of characters replaced by a single `-`.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "project".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Why a project reference given by id, slug, title or slug prefix could not be resolved.
+#[derive(Debug)]
+pub enum ProjectLookupError {
+    NotFound(String),
+    /// The reference matches several projects, which are listed.
+    Ambiguous(String, Vec<Project>),
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for ProjectLookupError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl std::fmt::Display for ProjectLookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectLookupError::NotFound(reference) => {
+                write!(f, "Project not found: {}", reference)
+            }
+            ProjectLookupError::Ambiguous(reference, candidates) => {
+                write!(f, "Project {} is ambiguous, it matches:", reference)?;
+                for project in candidates {
+                    write!(
+                        f,
+                        "\n  {} ({}: {})",
+                        project.slug, project.id, project.title
+                    )?;
+                }
+                Ok(())
+            }
+            ProjectLookupError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Garden Shed"), "garden-shed");
+        assert_eq!(slugify("  Q4: Plan & Budget!  "), "q4-plan-budget");
+        assert_eq!(slugify("snake_case--title"), "snake-case-title");
+        assert_eq!(slugify("Über Café"), "über-café");
+        assert_eq!(slugify("!!!"), "project");
+    }
+}
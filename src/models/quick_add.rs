@@ -0,0 +1,239 @@
+use super::task_priority::TaskPriority;
+use chrono::{Datelike, Days, Duration, NaiveDate, Weekday};
+
+/// A task written in quick-add syntax, such as
+/// `Fix login bug +backend #urgent !high due:fri @project-name`.
+///
+/// Words starting with `+` or `#` are tags, `!` sets the priority, `due:` the due date and `@`
+/// names the project. All other words make up the title.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub tags: Vec<String>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<NaiveDate>,
+    /// Reference to the project: its id, slug, title ignoring case, `-` and `_`, or a slug
+    /// prefix.
+    pub project: Option<String>,
+}
+
+impl QuickAdd {
+    /// Parses quick-add syntax. Relative due dates such as `due:fri` are resolved from `today`.
+    pub fn parse(input: &str, today: NaiveDate) -> Result<Self, String> {
+        let mut quick_add = QuickAdd::default();
+        let mut title_words = Vec::new();
+        for word in input.split_whitespace() {
+            if let Some(tag) = word.strip_prefix(['+', '#']).filter(|tag| !tag.is_empty()) {
+                if !quick_add.tags.iter().any(|t| t == tag) {
+                    quick_add.tags.push(tag.to_string());
+                }
+            } else if let Some(priority) = word.strip_prefix('!').filter(|p| !p.is_empty()) {
+                if quick_add.priority.is_some() {
+                    return Err(format!("Priority given twice: {}", word));
+                }
+                quick_add.priority = Some(priority.parse()?);
+            } else if let Some(due) = word.strip_prefix("due:") {
+                if quick_add.due_date.is_some() {
+                    return Err(format!("Due date given twice: {}", word));
+                }
+                quick_add.due_date = Some(parse_due_date(due, today)?);
+            } else if let Some(project) = word.strip_prefix('@').filter(|p| !p.is_empty()) {
+                if quick_add.project.is_some() {
+                    return Err(format!("Project given twice: {}", word));
+                }
+                quick_add.project = Some(project.to_string());
+            } else {
+                title_words.push(word);
+            }
+        }
+        if title_words.is_empty() {
+            return Err("A task needs a title".to_string());
+        }
+        quick_add.title = title_words.join(" ");
+        Ok(quick_add)
+    }
+}
+
+/// Why a quick-add task could not be created.
+#[derive(Debug)]
+pub enum QuickAddError {
+    UnknownProject(String),
+    /// The name matches several projects, whose titles are listed.
+    AmbiguousProject(String, Vec<String>),
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for QuickAddError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl std::fmt::Display for QuickAddError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuickAddError::UnknownProject(name) => write!(f, "Unknown project: {}", name),
+            QuickAddError::AmbiguousProject(name, titles) => write!(
+                f,
+                "Project name {} is ambiguous, it matches: {}",
+                name,
+                titles.join(", ")
+            ),
+            QuickAddError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Parses a due date: `today`, `tomorrow`, a weekday such as `fri` or `friday` (the next one,
+/// today included), a number of days or weeks from today such as `3d` or `2w`, or an ISO date
+/// such as `2024-12-24`.
+pub fn parse_due_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let invalid = || format!("Invalid due date: {}", input);
+    let input_lower = input.to_lowercase();
+    match input_lower.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" | "tmr" => return today.checked_add_days(Days::new(1)).ok_or_else(invalid),
+        _ => {}
+    }
+    if let Ok(weekday) = input_lower.parse::<Weekday>() {
+        let days_ahead = (7 + weekday.num_days_from_monday() as u64
+            - today.weekday().num_days_from_monday() as u64)
+            % 7;
+        return today
+            .checked_add_days(Days::new(days_ahead))
+            .ok_or_else(invalid);
+    }
+    // `None` if the input isn't relative, `Some(None)` if its number of days overflows.
+    let relative = |suffix: char, factor: i64| {
+        let amount = input_lower
+            .strip_suffix(suffix)
+            .filter(|amount| !amount.is_empty() && amount.bytes().all(|b| b.is_ascii_digit()))?;
+        Some(
+            amount
+                .parse::<i64>()
+                .ok()
+                .and_then(|amount| amount.checked_mul(factor)),
+        )
+    };
+    if let Some(days) = relative('d', 1).or_else(|| relative('w', 7)) {
+        return days
+            .and_then(Duration::try_days)
+            .and_then(|duration| today.checked_add_signed(duration))
+            .ok_or_else(|| format!("Due date out of range: {}", input));
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| invalid())
+}
+
+/// Normalizes a project name for matching, so `@garden-shed` finds "Garden Shed".
+pub fn normalize_project_name(name: &str) -> String {
+    name.to_lowercase()
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 10, 16).unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_full_syntax() {
+        let quick_add = QuickAdd::parse(
+            "Fix login bug +backend #urgent !high due:fri @project-name",
+            today(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            quick_add,
+            QuickAdd {
+                title: "Fix login bug".to_string(),
+                tags: vec!["backend".to_string(), "urgent".to_string()],
+                priority: Some(TaskPriority::High),
+                due_date: Some(date(10, 18)),
+                project: Some("project-name".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_title_only() {
+        let quick_add = QuickAdd::parse("  Water   the plants ", today()).unwrap();
+
+        assert_eq!(quick_add.title, "Water the plants");
+        assert_eq!(
+            quick_add,
+            QuickAdd {
+                title: quick_add.title.clone(),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_markers_anywhere_and_bare_symbols() {
+        let quick_add = QuickAdd::parse("+home Call mom # now ! @", today()).unwrap();
+
+        assert_eq!(quick_add.title, "Call mom # now ! @");
+        assert_eq!(quick_add.tags, vec!["home".to_string()]);
+    }
+
+    #[test]
+    fn test_duplicate_tags_are_merged() {
+        let quick_add = QuickAdd::parse("Task +a #a +b", today()).unwrap();
+
+        assert_eq!(quick_add.tags, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(QuickAdd::parse("+tag !high", today()).is_err());
+        assert!(QuickAdd::parse("Task !highest", today()).is_err());
+        assert!(QuickAdd::parse("Task !high !low", today()).is_err());
+        assert!(QuickAdd::parse("Task due:someday", today()).is_err());
+        assert!(QuickAdd::parse("Task @one @two", today()).is_err());
+    }
+
+    #[test]
+    fn test_parse_due_date() {
+        assert_eq!(parse_due_date("today", today()), Ok(today()));
+        assert_eq!(parse_due_date("Tomorrow", today()), Ok(date(10, 17)));
+        assert_eq!(parse_due_date("wed", today()), Ok(today()));
+        assert_eq!(parse_due_date("monday", today()), Ok(date(10, 21)));
+        assert_eq!(parse_due_date("3d", today()), Ok(date(10, 19)));
+        assert_eq!(parse_due_date("2w", today()), Ok(date(10, 30)));
+        assert_eq!(parse_due_date("2024-12-24", today()), Ok(date(12, 24)));
+        assert!(parse_due_date("2024-13-01", today()).is_err());
+        assert!(parse_due_date("", today()).is_err());
+        assert!(parse_due_date("-3d", today()).is_err());
+    }
+
+    #[test]
+    fn test_parse_due_date_out_of_range() {
+        let out_of_range = |input: &str| Err(format!("Due date out of range: {}", input));
+        for input in [
+            "9223372036854775807w",
+            "99999999999999999999d",
+            "1000000000d",
+        ] {
+            assert_eq!(parse_due_date(input, today()), out_of_range(input));
+        }
+    }
+
+    #[test]
+    fn test_normalize_project_name() {
+        assert_eq!(normalize_project_name("Garden  Shed"), "garden shed");
+        assert_eq!(normalize_project_name("garden-shed"), "garden shed");
+        assert_eq!(normalize_project_name("GARDEN_shed"), "garden shed");
+    }
+}
@@ -0,0 +1,165 @@
+use clap::builder::{PossibleValue, TypedValueParser};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use strum::VariantArray;
+
+/// The names of a status enum. `Display`, `FromStr` and clap's possible values are all derived
+/// from [`StatusName::name`], so they can't drift apart. The database form comes from the
+/// `DbEnum` derive, which stores variants in snake_case; a test checks it matches
+/// [`StatusName::key`].
+pub trait StatusName: Copy + PartialEq + VariantArray + 'static {
+    /// What the status belongs to, for error messages, such as "task status".
+    const KIND: &'static str;
+
+    /// Name shown to users, such as "In Progress".
+    fn name(self) -> &'static str;
+
+    /// Built-in extra names, such as "wip" for In Progress.
+    fn aliases(self) -> &'static [&'static str];
+
+    /// Extra names from the config file, mapping an alias to a status name.
+    fn configured_aliases() -> &'static HashMap<String, String>;
+
+    /// Normalized name, such as `in_progress`, which is also how the database stores it.
+    fn key(self) -> String {
+        normalize_status_name(self.name())
+    }
+}
+
+/// Normalizes a status name, so `In Progress`, `in-progress` and `IN_PROGRESS` all read
+/// `in_progress`.
+pub fn normalize_status_name(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn find_status<T: StatusName>(normalized: &str) -> Option<T> {
+    T::VARIANTS.iter().copied().find(|status| {
+        status.key() == normalized
+            || status
+                .aliases()
+                .iter()
+                .any(|alias| normalize_status_name(alias) == normalized)
+    })
+}
+
+/// Parses a status in any case, with spaces, `-` or `_` between words, or by an alias.
+pub fn parse_status<T: StatusName>(input: &str) -> Result<T, String> {
+    let normalized = normalize_status_name(input);
+    find_status(&normalized)
+        .or_else(|| {
+            T::configured_aliases()
+                .iter()
+                .find(|(alias, _)| normalize_status_name(alias) == normalized)
+                .and_then(|(_, target)| find_status(&normalize_status_name(target)))
+        })
+        .ok_or_else(|| format!("Invalid {}: {}", T::KIND, input))
+}
+
+/// Clap's possible value for a status: its key in kebab-case, such as `in-progress`.
+pub fn possible_value<T: StatusName>(status: T) -> PossibleValue {
+    PossibleValue::new(status.key().replace('_', "-"))
+        .help(status.name())
+        .aliases(status.aliases().iter().copied())
+}
+
+/// Parses status arguments with [`parse_status`], so configured aliases and any spelling are
+/// accepted, while clap still lists the possible values in help and completions. Clap would
+/// otherwise pick its `EnumValueParser` for `ValueEnum` types, which only knows the built-in
+/// names, so status arguments set it explicitly:
+///
+/// ```ignore
+/// #[arg(short, long, value_parser = StatusValueParser::<TaskStatus>::new())]
+/// status: Option<TaskStatus>,
+/// ```
+#[derive(Clone)]
+pub struct StatusValueParser<T>(PhantomData<T>);
+
+impl<T> StatusValueParser<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for StatusValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TypedValueParser for StatusValueParser<T>
+where
+    T: StatusName + clap::ValueEnum + Send + Sync,
+{
+    type Value = T;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<T, clap::Error> {
+        let value = value.to_string_lossy();
+        parse_status(&value).map_err(|message| {
+            let arg = arg.map(|arg| arg.to_string()).unwrap_or_default();
+            let valid = T::VARIANTS
+                .iter()
+                .map(|status| possible_value(*status).get_name().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            clap::Error::raw(
+                clap::error::ErrorKind::InvalidValue,
+                format!("{} for '{}' [possible values: {}]\n", message, arg, valid),
+            )
+            .with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            T::value_variants()
+                .iter()
+                .filter_map(clap::ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::project_status::{ProjectStatus, ProjectStatusMapping};
+    use crate::models::task_status::{TaskStatus, TaskStatusMapping};
+    use diesel::dsl::sql;
+    use diesel::sql_types::Text;
+    use diesel::{Connection, RunQueryDsl, SqliteConnection};
+
+    #[test]
+    fn test_key_matches_database_form() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        for &status in TaskStatus::VARIANTS {
+            let stored: String =
+                diesel::select(sql::<Text>("").bind::<TaskStatusMapping, _>(status))
+                    .get_result(&mut conn)
+                    .unwrap();
+            assert_eq!(stored, status.key());
+        }
+        for &status in ProjectStatus::VARIANTS {
+            let stored: String =
+                diesel::select(sql::<Text>("").bind::<ProjectStatusMapping, _>(status))
+                    .get_result(&mut conn)
+                    .unwrap();
+            assert_eq!(stored, status.key());
+        }
+    }
+
+    #[test]
+    fn test_normalize_status_name() {
+        assert_eq!(normalize_status_name("In Progress"), "in_progress");
+        assert_eq!(normalize_status_name(" in-progress "), "in_progress");
+        assert_eq!(normalize_status_name("IN__PROGRESS"), "in_progress");
+        assert_eq!(normalize_status_name("todo"), "todo");
+    }
+}
@@ -0,0 +1,129 @@
+use super::task_comments::TaskComment;
+use super::task_commits::TaskCommit;
+use super::task_status_changes::TaskStatusChange;
+use chrono::NaiveDateTime;
+
+/// Something that happened to a task: a comment, a status change or a commit mentioning it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskActivity {
+    Comment(TaskComment),
+    StatusChange(TaskStatusChange),
+    Commit(TaskCommit),
+}
+
+impl TaskActivity {
+    /// Interleaves comments, status changes and commits, oldest first.
+    pub fn merge(
+        comments: Vec<TaskComment>,
+        changes: Vec<TaskStatusChange>,
+        commits: Vec<TaskCommit>,
+    ) -> Vec<Self> {
+        let mut activity: Vec<Self> = changes
+            .into_iter()
+            .map(Self::StatusChange)
+            .chain(comments.into_iter().map(Self::Comment))
+            .chain(commits.into_iter().map(Self::Commit))
+            .collect();
+        activity.sort_by_key(Self::at);
+        activity
+    }
+
+    pub fn at(&self) -> NaiveDateTime {
+        match self {
+            TaskActivity::Comment(comment) => comment.created_at,
+            TaskActivity::StatusChange(change) => change.changed_at,
+            TaskActivity::Commit(commit) => commit.created_at,
+        }
+    }
+
+    /// Describes the activity, starting with its time. Comments spanning several lines continue
+    /// on indented lines.
+    pub fn lines(&self) -> Vec<String> {
+        let at = self.at().format("%Y-%m-%d %H:%M");
+        match self {
+            TaskActivity::StatusChange(change) => {
+                let reason = change
+                    .reason
+                    .as_ref()
+                    .map(|reason| format!(": {}", reason))
+                    .unwrap_or_default();
+                vec![format!(
+                    "{} {} -> {}{}",
+                    at, change.from_status, change.to_status, reason
+                )]
+            }
+            TaskActivity::Commit(commit) => vec![format!(
+                "{} Commit {}: {}",
+                at,
+                commit.short_sha(),
+                commit.summary
+            )],
+            TaskActivity::Comment(comment) => match comment.body.lines().collect::<Vec<_>>()[..] {
+                [line] => vec![format!("{} Comment: {}", at, line)],
+                ref lines => std::iter::once(format!("{} Comment:", at))
+                    .chain(lines.iter().map(|line| format!("  {}", line)))
+                    .collect(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task_status::TaskStatus;
+    use chrono::NaiveDate;
+
+    fn at(minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(12, minute, 0)
+            .unwrap()
+    }
+
+    fn comment(body: &str, minute: u32) -> TaskComment {
+        TaskComment {
+            id: minute as i32,
+            task_id: 1,
+            body: body.to_string(),
+            created_at: at(minute),
+        }
+    }
+
+    #[test]
+    fn test_merge_and_describe() {
+        let change = TaskStatusChange {
+            id: 1,
+            task_id: 1,
+            from_status: TaskStatus::Todo,
+            to_status: TaskStatus::Blocked,
+            reason: Some("Waiting on the API".to_string()),
+            changed_at: at(5),
+        };
+        let commit = TaskCommit {
+            id: 1,
+            task_id: 1,
+            sha: "0123456789abcdef".to_string(),
+            summary: "Fix login, refs #1".to_string(),
+            created_at: at(7),
+        };
+        let activity = TaskActivity::merge(
+            vec![comment("First", 1), comment("Second\nline", 9)],
+            vec![change],
+            vec![commit],
+        );
+
+        let lines: Vec<String> = activity.iter().flat_map(TaskActivity::lines).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "2026-10-19 12:01 Comment: First",
+                "2026-10-19 12:05 Todo -> Blocked: Waiting on the API",
+                "2026-10-19 12:07 Commit 0123456: Fix login, refs #1",
+                "2026-10-19 12:09 Comment:",
+                "  Second",
+                "  line",
+            ]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use super::tasks::Task;
+use crate::schema::task_comments;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// A comment on a task. Comments are kept next to the description, so nothing said about a
+/// task gets overwritten.
+#[derive(Debug, Clone, Queryable, Selectable, Associations, PartialEq)]
+#[diesel(belongs_to(Task))]
+#[diesel(table_name = task_comments)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskComment {
+    pub id: i32,
+    pub task_id: i32,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = task_comments)]
+pub struct NewTaskComment<'a> {
+    pub task_id: i32,
+    pub body: &'a str,
+}
@@ -0,0 +1,163 @@
+use super::projects::slugify;
+use super::task_status::TaskStatus;
+use super::tasks::Task;
+use crate::schema::task_commits;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use std::collections::HashMap;
+
+/// Longest slug of a task title in a branch name, so branch names stay readable.
+const MAX_BRANCH_SLUG_LENGTH: usize = 40;
+
+/// A git commit whose message mentions a task.
+#[derive(Debug, Clone, Queryable, Selectable, Associations, PartialEq)]
+#[diesel(belongs_to(Task))]
+#[diesel(table_name = task_commits)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskCommit {
+    pub id: i32,
+    pub task_id: i32,
+    pub sha: String,
+    /// First line of the commit message.
+    pub summary: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl TaskCommit {
+    /// The abbreviated hash, as git shows it.
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(7)]
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = task_commits)]
+pub struct NewTaskCommit<'a> {
+    pub task_id: i32,
+    pub sha: &'a str,
+    pub summary: &'a str,
+}
+
+/// A task mentioned in a commit message as `#123`, and the status a keyword such as
+/// `fixes #123` moves it to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommitReference {
+    pub task_id: i32,
+    pub status: Option<TaskStatus>,
+}
+
+impl CommitReference {
+    /// Finds the tasks a commit message mentions. A keyword applies to the mention right after
+    /// it and to a list continuing it, as in `fixes #1, #2 and #3`. Lines starting with `#`
+    /// are skipped, as git drops them from the message.
+    pub fn parse(message: &str, keywords: &HashMap<String, TaskStatus>) -> Vec<Self> {
+        let mut references: Vec<Self> = Vec::new();
+        for line in message.lines().filter(|line| !line.starts_with('#')) {
+            let mut status = None;
+            for word in line.split_whitespace() {
+                let word = word.trim_matches(|c: char| ",.;:!?()[]".contains(c));
+                if let Some(task_id) = parse_mention(word) {
+                    match references.iter_mut().find(|r| r.task_id == task_id) {
+                        Some(reference) => reference.status = reference.status.or(status),
+                        None => references.push(Self { task_id, status }),
+                    }
+                } else if !word.eq_ignore_ascii_case("and") {
+                    status = keywords
+                        .iter()
+                        .find(|(keyword, _)| keyword.eq_ignore_ascii_case(word))
+                        .map(|(_, status)| *status);
+                }
+            }
+        }
+        references
+    }
+}
+
+fn parse_mention(word: &str) -> Option<i32> {
+    let id = word.strip_prefix('#')?;
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    id.parse().ok()
+}
+
+/// Name of the branch for working on a task, such as `123-fix-login-bug`.
+pub fn branch_name(task: &Task) -> String {
+    if !task.title.chars().any(char::is_alphanumeric) {
+        return task.id.to_string();
+    }
+    let mut slug = String::new();
+    for word in slugify(&task.title).split('-') {
+        if !slug.is_empty() && slug.len() + word.len() >= MAX_BRANCH_SLUG_LENGTH {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    format!("{}-{}", task.id, slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keywords() -> HashMap<String, TaskStatus> {
+        HashMap::from([
+            ("fixes".to_string(), TaskStatus::Completed),
+            ("implements".to_string(), TaskStatus::InReview),
+        ])
+    }
+
+    fn reference(task_id: i32, status: Option<TaskStatus>) -> CommitReference {
+        CommitReference { task_id, status }
+    }
+
+    #[test]
+    fn test_parse_references() {
+        let message = "Fix login (#12)\n\nFixes: #3, #4 and #5. Implements #6, see #7 and #3.\n\
+            # Please enter the commit message, #8\nNot issue#9 or #x";
+
+        assert_eq!(
+            CommitReference::parse(message, &keywords()),
+            vec![
+                reference(12, None),
+                reference(3, Some(TaskStatus::Completed)),
+                reference(4, Some(TaskStatus::Completed)),
+                reference(5, Some(TaskStatus::Completed)),
+                reference(6, Some(TaskStatus::InReview)),
+                reference(7, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mention_before_keyword_gets_its_status() {
+        assert_eq!(
+            CommitReference::parse("Start #3\n\nfixes #3", &keywords()),
+            vec![reference(3, Some(TaskStatus::Completed))]
+        );
+    }
+
+    #[test]
+    fn test_branch_name() {
+        let mut task = Task {
+            id: 123,
+            title: "Fix login bug!".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            project_id: None,
+            priority: None,
+            due_date: None,
+        };
+        assert_eq!(branch_name(&task), "123-fix-login-bug");
+
+        task.title =
+            "Make the login page remember users across restarts of the browser".to_string();
+        assert_eq!(branch_name(&task), "123-make-the-login-page-remember-users");
+
+        task.title = "???".to_string();
+        assert_eq!(branch_name(&task), "123");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::schema::task_dependencies;
+use diesel::prelude::*;
+
+/// Says that a task can't go on until its blocker is completed.
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, PartialEq)]
+#[diesel(table_name = task_dependencies)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskDependency {
+    pub task_id: i32,
+    pub blocker_id: i32,
+}
+
+/// Why a dependency could not be added.
+#[derive(Debug)]
+pub enum DependencyError {
+    TaskNotFound(i32),
+    /// A task can't block itself.
+    SelfDependency(i32),
+    /// The blocker already depends on the task. Lists the chain of task ids, each blocked by
+    /// the next.
+    Cycle(Vec<i32>),
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for DependencyError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl std::fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DependencyError::TaskNotFound(id) => write!(f, "Task {} not found", id),
+            DependencyError::SelfDependency(id) => write!(f, "Task {} can't block itself", id),
+            DependencyError::Cycle(chain) => write!(
+                f,
+                "That would create a cycle, each task blocked by the next: {}",
+                chain
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+            DependencyError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::task_status::TaskStatus;
+use std::str::FromStr;
+
+/// Criteria for selecting tasks, written as comma separated `key=value` pairs such as
+/// `status=Blocked,project=garden,tag=urgent,ready=true`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    /// Reference to the project: its id, slug, title or a slug prefix.
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    /// Whether all tasks the task depends on are completed or canceled.
+    pub ready: Option<bool>,
+}
+
+impl TaskFilter {
+    /// Sets the condition for a key, as in `status=Blocked`. Also used for the query parameters
+    /// of `roll serve`, so both take the same conditions.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key.trim() {
+            "status" => self.status = Some(TaskStatus::from_str(value.trim())?),
+            "project" if !value.trim().is_empty() => self.project = Some(value.trim().to_string()),
+            "tag" if !value.trim().is_empty() => self.tag = Some(value.trim().to_string()),
+            "ready" => {
+                self.ready = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid ready value: {}", value))?,
+                )
+            }
+            _ => return Err(format!("Unknown filter key: {}", key)),
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TaskFilter {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut filter = TaskFilter::default();
+        for condition in input.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            let (key, value) = condition
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid filter condition: {}", condition))?;
+            filter.set(key, value)?;
+        }
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        let filter =
+            TaskFilter::from_str("status=Blocked, project=garden, tag=urgent, ready=false")
+                .unwrap();
+        assert_eq!(
+            filter,
+            TaskFilter {
+                status: Some(TaskStatus::Blocked),
+                project: Some("garden".to_string()),
+                tag: Some("urgent".to_string()),
+                ready: Some(false),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_filter() {
+        assert_eq!(TaskFilter::from_str("").unwrap(), TaskFilter::default());
+    }
+
+    #[test]
+    fn test_parse_invalid_filter() {
+        assert!(TaskFilter::from_str("status").is_err());
+        assert!(TaskFilter::from_str("color=red").is_err());
+        assert!(TaskFilter::from_str("status=Unknown").is_err());
+        assert!(TaskFilter::from_str("project=").is_err());
+        assert!(TaskFilter::from_str("tag= ").is_err());
+        assert!(TaskFilter::from_str("ready=maybe").is_err());
+    }
+}
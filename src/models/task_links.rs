@@ -0,0 +1,212 @@
+use super::tasks::Task;
+use crate::schema::task_links;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use ratatui::widgets::ListItem;
+use std::path::Path;
+
+/// Schemes of the URLs found in descriptions. Other URLs can still be linked explicitly.
+const DETECTED_SCHEMES: [&str; 3] = ["https://", "http://", "file://"];
+
+/// A URL or file path linked to a task, such as a pull request or a design doc.
+#[derive(Debug, Clone, Queryable, Selectable, Associations, PartialEq)]
+#[diesel(belongs_to(Task))]
+#[diesel(table_name = task_links)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskLink {
+    pub id: i32,
+    pub task_id: i32,
+    pub target: String,
+    pub label: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = task_links)]
+pub struct NewTaskLink<'a> {
+    pub task_id: i32,
+    pub target: &'a str,
+    pub label: Option<&'a str>,
+}
+
+/// A link shown for a task: one added with `roll task link`, or one found in its description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    pub target: String,
+    pub label: Option<String>,
+}
+
+impl Link {
+    pub fn new(target: &str, label: Option<&str>) -> Self {
+        Self {
+            target: target.to_string(),
+            label: label.map(str::to_string),
+        }
+    }
+}
+
+impl From<TaskLink> for Link {
+    fn from(link: TaskLink) -> Self {
+        Self {
+            target: link.target,
+            label: link.label,
+        }
+    }
+}
+
+impl std::fmt::Display for Link {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "{}: {}", label, self.target),
+            None => write!(f, "{}", self.target),
+        }
+    }
+}
+
+impl<'a> From<Link> for ListItem<'a> {
+    fn from(link: Link) -> Self {
+        ListItem::new(link.to_string())
+    }
+}
+
+/// Whether `target` starts with a URL scheme such as `https://` or `mailto:`.
+pub fn is_url(target: &str) -> bool {
+    let Some((scheme, rest)) = target.split_once(':') else {
+        return false;
+    };
+    // A single letter is a Windows drive, as in `C:\notes.txt`.
+    scheme.len() > 1
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && (rest.starts_with("//") || scheme.eq_ignore_ascii_case("mailto"))
+}
+
+/// Returns a target to store for a link: URLs as they are, file paths made absolute against
+/// `base`, so they can be opened from any directory.
+pub fn normalize_target(target: &str, base: &Path) -> String {
+    let target = target.trim();
+    if is_url(target) {
+        return target.to_string();
+    }
+    let path = match target.strip_prefix("~/") {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => home.join(rest),
+            None => Path::new(target).to_path_buf(),
+        },
+        None => base.join(target),
+    };
+    path.display().to_string()
+}
+
+/// Finds the `http`, `https` and `file` URLs in a description, in order and without repeats.
+/// A Markdown link such as `[PR](https://…)` keeps its text as the label.
+pub fn detect_links(text: &str) -> Vec<Link> {
+    let mut links: Vec<Link> = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = next_url_start(text, offset) {
+        let rest = &text[start..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`'))
+            .unwrap_or(rest.len());
+        let target = trim_url(&rest[..end]);
+        offset = start + end.max(1);
+        if DETECTED_SCHEMES.contains(&target) {
+            continue;
+        }
+        let label = markdown_label(&text[..start]);
+        if !links.iter().any(|link| link.target == target) {
+            links.push(Link::new(target, label));
+        }
+    }
+    links
+}
+
+/// Index of the next URL at or after `offset` that doesn't start in the middle of a word.
+fn next_url_start(text: &str, mut offset: usize) -> Option<usize> {
+    loop {
+        let start = DETECTED_SCHEMES
+            .iter()
+            .filter_map(|scheme| text[offset..].find(scheme).map(|index| offset + index))
+            .min()?;
+        if !text[..start].ends_with(|c: char| c.is_alphanumeric()) {
+            return Some(start);
+        }
+        offset = start + 1;
+    }
+}
+
+/// Drops punctuation ending the sentence around a URL, and closing parentheses that don't
+/// belong to it, as around `(see https://example.com/a_(b))`.
+fn trim_url(mut url: &str) -> &str {
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', ']']);
+        let trimmed = if trimmed.ends_with(')')
+            && trimmed.matches(')').count() > trimmed.matches('(').count()
+        {
+            &trimmed[..trimmed.len() - 1]
+        } else {
+            trimmed
+        };
+        if trimmed == url {
+            return url;
+        }
+        url = trimmed;
+    }
+}
+
+/// The text of a Markdown link whose target starts right after `before`.
+fn markdown_label(before: &str) -> Option<&str> {
+    let before = before.strip_suffix("](")?;
+    let open = before.rfind('[')?;
+    let label = before[open + 1..].trim();
+    (!label.is_empty() && !label.contains(['\n', ']'])).then_some(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_links() {
+        let text = "See [the PR](https://github.com/org/repo/pull/12), the spec at \
+            https://example.com/spec.\n(Also http://wiki/a_(b)) and https://github.com/org/repo/pull/12 \
+            again, but not xhttps://nope or a bare https://";
+
+        assert_eq!(
+            detect_links(text),
+            vec![
+                Link::new("https://github.com/org/repo/pull/12", Some("the PR")),
+                Link::new("https://example.com/spec", None),
+                Link::new("http://wiki/a_(b)", None),
+            ]
+        );
+        assert!(detect_links("No links here").is_empty());
+    }
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://example.com"));
+        assert!(is_url("file:///tmp/notes.md"));
+        assert!(is_url("mailto:me@example.com"));
+        assert!(!is_url("docs/spec.md"));
+        assert!(!is_url("C:\\notes.txt"));
+        assert!(!is_url("note: remember"));
+    }
+
+    #[test]
+    fn test_normalize_target() {
+        let base = Path::new("/work/repo");
+
+        assert_eq!(
+            normalize_target(" https://example.com ", base),
+            "https://example.com"
+        );
+        assert_eq!(
+            normalize_target("docs/spec.md", base),
+            "/work/repo/docs/spec.md"
+        );
+        assert_eq!(normalize_target("/etc/hosts", base), "/etc/hosts");
+    }
+}
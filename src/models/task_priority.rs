@@ -0,0 +1,41 @@
+use std::str::FromStr;
+use strum::EnumIter;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, diesel_derive_enum::DbEnum, EnumIter,
+)]
+pub enum TaskPriority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl FromStr for TaskPriority {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "low" => Ok(TaskPriority::Low),
+            "medium" | "med" => Ok(TaskPriority::Medium),
+            "high" => Ok(TaskPriority::High),
+            "urgent" => Ok(TaskPriority::Urgent),
+            _ => Err(format!(
+                "Invalid task priority: {} (expected low, medium, high or urgent)",
+                input
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for TaskPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let priority_str = match self {
+            TaskPriority::Low => "Low",
+            TaskPriority::Medium => "Medium",
+            TaskPriority::High => "High",
+            TaskPriority::Urgent => "Urgent",
+        };
+        write!(f, "{}", priority_str)
+    }
+}
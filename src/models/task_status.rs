@@ -1,9 +1,16 @@
+use super::status_name::{parse_status, possible_value, StatusName};
+use crate::config;
+use clap::builder::PossibleValue;
 use ratatui::widgets::ListItem;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::str::FromStr;
 
-use strum::EnumIter;
+use strum::{EnumIter, VariantArray};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum, EnumIter)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, diesel_derive_enum::DbEnum, EnumIter, VariantArray,
+)]
 pub enum TaskStatus {
     Todo,
     InProgress,
@@ -14,37 +21,67 @@ pub enum TaskStatus {
     Canceled,
 }
 
+impl StatusName for TaskStatus {
+    const KIND: &'static str = "task status";
+
+    fn name(self) -> &'static str {
+        match self {
+            TaskStatus::Todo => "Todo",
+            TaskStatus::InProgress => "In Progress",
+            TaskStatus::Blocked => "Blocked",
+            TaskStatus::InReview => "In Review",
+            TaskStatus::Completed => "Completed",
+            TaskStatus::OnHold => "On Hold",
+            TaskStatus::Canceled => "Canceled",
+        }
+    }
+
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            TaskStatus::InProgress => &["wip", "doing"],
+            TaskStatus::Completed => &["done"],
+            TaskStatus::Canceled => &["cancelled"],
+            _ => &[],
+        }
+    }
+
+    fn configured_aliases() -> &'static HashMap<String, String> {
+        &config::get().aliases.task_status
+    }
+}
+
 impl FromStr for TaskStatus {
     type Err = String;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
-            "Todo" => Ok(TaskStatus::Todo),
-            "In Progress" => Ok(TaskStatus::InProgress),
-            "Blocked" => Ok(TaskStatus::Blocked),
-            "In Review" => Ok(TaskStatus::InReview),
-            "Completed" => Ok(TaskStatus::Completed),
-            "On Hold" => Ok(TaskStatus::OnHold),
-            "Canceled" => Ok(TaskStatus::Canceled),
-            _ => Err(format!("Invalid task status: {}", input)),
-        }
+        parse_status(input)
     }
 }
 
 impl std::fmt::Display for TaskStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let status_str = match self {
-            TaskStatus::Todo => "Todo",
-            TaskStatus::InProgress => "In Progress",
-            TaskStatus::Blocked => "Blocked",
-            TaskStatus::InReview => "In Review",
-            TaskStatus::Completed => "Completed",
-            TaskStatus::OnHold => "On Hold",
-            TaskStatus::Canceled => "Canceled",
-        };
-        write!(f, "{}", status_str)
+        write!(f, "{}", self.name())
     }
 }
+
+/// Statuses in the config file are parsed like arguments, so `in_progress` and `done` work.
+impl<'de> Deserialize<'de> for TaskStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        parse_status(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+impl clap::ValueEnum for TaskStatus {
+    fn value_variants<'a>() -> &'a [Self] {
+        Self::VARIANTS
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(possible_value(*self))
+    }
+}
+
 impl<'a> From<TaskStatus> for ListItem<'a> {
     fn from(status: TaskStatus) -> Self {
         ListItem::new(format!("{}", status))
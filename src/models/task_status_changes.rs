@@ -0,0 +1,28 @@
+use super::task_status::TaskStatus;
+use super::tasks::Task;
+use crate::schema::task_status_changes;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// A change of a task's status, with the reason given for it.
+#[derive(Debug, Clone, Queryable, Selectable, Associations, PartialEq)]
+#[diesel(belongs_to(Task))]
+#[diesel(table_name = task_status_changes)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskStatusChange {
+    pub id: i32,
+    pub task_id: i32,
+    pub from_status: TaskStatus,
+    pub to_status: TaskStatus,
+    pub reason: Option<String>,
+    pub changed_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = task_status_changes)]
+pub struct NewTaskStatusChange<'a> {
+    pub task_id: i32,
+    pub from_status: TaskStatus,
+    pub to_status: TaskStatus,
+    pub reason: Option<&'a str>,
+}
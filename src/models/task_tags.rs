@@ -0,0 +1,12 @@
+use super::tasks::Task;
+use crate::schema::task_tags;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, Associations, PartialEq)]
+#[diesel(belongs_to(Task))]
+#[diesel(table_name = task_tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskTag {
+    pub task_id: i32,
+    pub tag: String,
+}
@@ -1,6 +1,9 @@
 use super::projects::Project;
+use super::task_priority::TaskPriority;
 use super::task_status::TaskStatus;
+use super::workflow::WorkflowError;
 use crate::schema::tasks;
+use chrono::NaiveDate;
 use diesel::prelude::*;
 use ratatui::widgets::ListItem;
 
@@ -16,6 +19,8 @@ pub struct Task {
     pub description: Option<String>,
     pub status: TaskStatus,
     pub project_id: Option<i32>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<NaiveDate>,
 }
 
 impl<'a> From<Task> for ListItem<'a> {
@@ -31,6 +36,8 @@ pub struct NewTask<'a> {
     pub description: Option<&'a str>,
     pub status: Option<TaskStatus>,
     pub project_id: Option<i32>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<NaiveDate>,
 }
 
 #[derive(AsChangeset, Identifiable)]
@@ -42,3 +49,32 @@ pub struct UpdateTask<'a> {
     pub status: Option<TaskStatus>,
     pub project_id: Option<i32>,
 }
+
+/// Why a task could not be updated.
+#[derive(Debug)]
+pub enum TaskUpdateError {
+    /// The status change is not allowed by the configured workflow.
+    Workflow(WorkflowError),
+    Database(diesel::result::Error),
+}
+
+impl From<WorkflowError> for TaskUpdateError {
+    fn from(error: WorkflowError) -> Self {
+        Self::Workflow(error)
+    }
+}
+
+impl From<diesel::result::Error> for TaskUpdateError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl std::fmt::Display for TaskUpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskUpdateError::Workflow(error) => write!(f, "{}", error),
+            TaskUpdateError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use super::task_status::TaskStatus;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Rules for moving tasks between statuses, read from the `[workflow]` section of the config.
+/// The default workflow allows every move.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Workflow {
+    /// The statuses a task may move to from a status. Statuses without an entry may move to
+    /// any status.
+    pub transitions: HashMap<TaskStatus, Vec<TaskStatus>>,
+    /// Statuses that can only be entered with a reason.
+    pub reason_required: Vec<TaskStatus>,
+    /// Statuses a task can't leave.
+    pub terminal: Vec<TaskStatus>,
+}
+
+/// A status change the workflow doesn't allow.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkflowError {
+    Terminal(TaskStatus),
+    NotAllowed {
+        from: TaskStatus,
+        to: TaskStatus,
+        allowed: Vec<TaskStatus>,
+    },
+    ReasonRequired(TaskStatus),
+}
+
+impl std::fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkflowError::Terminal(status) => {
+                write!(f, "{} is a terminal status and can't be changed", status)
+            }
+            WorkflowError::NotAllowed { from, to, allowed } if allowed.is_empty() => {
+                write!(f, "Can't move from {} to {}", from, to)
+            }
+            WorkflowError::NotAllowed { from, to, allowed } => write!(
+                f,
+                "Can't move from {} to {}, allowed: {}",
+                from,
+                to,
+                allowed
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            WorkflowError::ReasonRequired(status) => {
+                write!(f, "Moving to {} requires a reason", status)
+            }
+        }
+    }
+}
+
+impl Workflow {
+    /// Checks a move from one status to another. Keeping the status is always allowed.
+    pub fn check(
+        &self,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<(), WorkflowError> {
+        if from == to {
+            return Ok(());
+        }
+        if self.terminal.contains(&from) {
+            return Err(WorkflowError::Terminal(from));
+        }
+        if !self.allows(from, to) {
+            return Err(WorkflowError::NotAllowed {
+                from,
+                to,
+                allowed: self.transitions[&from].clone(),
+            });
+        }
+        if self.requires_reason(to) && reason.is_none_or(|reason| reason.trim().is_empty()) {
+            return Err(WorkflowError::ReasonRequired(to));
+        }
+        Ok(())
+    }
+
+    /// Whether a task may move from one status to another, ignoring reasons.
+    pub fn allows(&self, from: TaskStatus, to: TaskStatus) -> bool {
+        from == to
+            || (!self.terminal.contains(&from)
+                && self
+                    .transitions
+                    .get(&from)
+                    .is_none_or(|allowed| allowed.contains(&to)))
+    }
+
+    pub fn requires_reason(&self, status: TaskStatus) -> bool {
+        self.reason_required.contains(&status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TaskStatus::*;
+
+    fn workflow() -> Workflow {
+        Workflow {
+            transitions: HashMap::from([
+                (Todo, vec![InProgress, Canceled]),
+                (InProgress, vec![InReview, Blocked]),
+                (InReview, vec![Completed, InProgress]),
+            ]),
+            reason_required: vec![Blocked, Canceled],
+            terminal: vec![Completed, Canceled],
+        }
+    }
+
+    #[test]
+    fn test_default_workflow_allows_everything() {
+        assert_eq!(Workflow::default().check(Completed, Todo, None), Ok(()));
+        assert_eq!(Workflow::default().check(Todo, Blocked, None), Ok(()));
+    }
+
+    #[test]
+    fn test_transitions() {
+        let workflow = workflow();
+
+        assert_eq!(workflow.check(Todo, InProgress, None), Ok(()));
+        assert_eq!(
+            workflow.check(Todo, Completed, None),
+            Err(WorkflowError::NotAllowed {
+                from: Todo,
+                to: Completed,
+                allowed: vec![InProgress, Canceled]
+            })
+        );
+        // Statuses without transitions may move anywhere.
+        assert_eq!(workflow.check(OnHold, Completed, None), Ok(()));
+        assert_eq!(workflow.check(Todo, Todo, None), Ok(()));
+    }
+
+    #[test]
+    fn test_reason_required() {
+        let workflow = workflow();
+
+        assert_eq!(
+            workflow.check(InProgress, Blocked, None),
+            Err(WorkflowError::ReasonRequired(Blocked))
+        );
+        assert_eq!(
+            workflow.check(InProgress, Blocked, Some("  ")),
+            Err(WorkflowError::ReasonRequired(Blocked))
+        );
+        assert_eq!(
+            workflow.check(InProgress, Blocked, Some("Waiting on API")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_terminal_statuses() {
+        let workflow = workflow();
+
+        assert_eq!(
+            workflow.check(Completed, InProgress, None),
+            Err(WorkflowError::Terminal(Completed))
+        );
+        assert!(!workflow.allows(Canceled, Todo));
+        assert!(workflow.allows(Canceled, Canceled));
+    }
+
+    #[test]
+    fn test_parse_workflow() {
+        let workflow: Workflow = toml::from_str(
+            "reason_required = [\"blocked\"]\nterminal = [\"done\"]\n\n[transitions]\ntodo = [\"in-progress\", \"Canceled\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(workflow.transitions[&Todo], vec![InProgress, Canceled]);
+        assert_eq!(workflow.reason_required, vec![Blocked]);
+        assert_eq!(workflow.terminal, vec![Completed]);
+        assert!(toml::from_str::<Workflow>("terminal = [\"finished\"]").is_err());
+    }
+}
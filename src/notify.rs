@@ -0,0 +1,181 @@
+//! Delivering change events to the webhooks and hook scripts configured in `[events]`.
+//!
+//! Each webhook gets the events it asks for posted as JSON, see [`Event::to_json`], and a
+//! failed post is retried a few times with a growing delay. A hook script is an executable in
+//! the hooks directory named `on-` and an event name with `-` for `_`, such as
+//! `on-task-completed`. It gets the event as JSON on stdin and its name in `$ROLL_EVENT`.
+//!
+//! Deliveries run in the background, so a slow webhook doesn't hold up the change. A single
+//! worker thread delivers all events, so they arrive in the order they happened. The CLI
+//! waits for them before it exits with [`wait`].
+use crate::config::{self, Webhook};
+use crate::events::Event;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const HOOK_PREFIX: &str = "on-";
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+/// Longest delay between retries, however many there are.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+static WORKER: Mutex<Option<Worker>> = Mutex::new(None);
+static ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// The background thread delivering events one at a time, fed by [`deliver`].
+struct Worker {
+    events: Sender<Vec<Event>>,
+    thread: JoinHandle<()>,
+}
+
+impl Worker {
+    fn spawn() -> Self {
+        let (events, received) = mpsc::channel::<Vec<Event>>();
+        let thread = thread::spawn(move || {
+            for event in received.iter().flatten() {
+                deliver_event(&event);
+            }
+        });
+        Self { events, thread }
+    }
+}
+
+/// Delivers events in the background, in the order they were passed in, across calls too.
+pub fn deliver(events: Vec<Event>) {
+    let settings = &config::get().events;
+    let has_hooks = settings.hooks_dir.as_deref().is_some_and(Path::is_dir);
+    if settings.webhooks.is_empty() && !has_hooks {
+        return;
+    }
+    let mut worker = WORKER.lock().expect("worker is never poisoned");
+    let worker = worker.get_or_insert_with(Worker::spawn);
+    // Only a panicking delivery stops the worker early; start over with a new one then.
+    if let Err(mpsc::SendError(events)) = worker.events.send(events) {
+        *worker = Worker::spawn();
+        worker.events.send(events).ok();
+    }
+}
+
+/// Waits for the deliveries still queued or running.
+pub fn wait() {
+    let worker = WORKER.lock().expect("worker is never poisoned").take();
+    if let Some(Worker { events, thread }) = worker {
+        // The worker stops once the queue is empty and nothing can be sent anymore.
+        drop(events);
+        thread.join().ok();
+    }
+}
+
+/// Takes the errors of failed deliveries since the last call.
+pub fn take_errors() -> Vec<String> {
+    std::mem::take(&mut *ERRORS.lock().expect("errors are never poisoned"))
+}
+
+fn report(error: String) {
+    ERRORS
+        .lock()
+        .expect("errors are never poisoned")
+        .push(error);
+}
+
+fn deliver_event(event: &Event) {
+    let settings = &config::get().events;
+    let body = event.to_json().to_string();
+    let names = event.names();
+    for webhook in settings
+        .webhooks
+        .iter()
+        .filter(|webhook| webhook.wants(&names))
+    {
+        if let Err(e) = post(webhook, &body, settings.retries) {
+            report(format!(
+                "Error posting {} to {}: {}",
+                event.name(),
+                webhook.url,
+                e
+            ));
+        }
+    }
+    let Some(hooks_dir) = &settings.hooks_dir else {
+        return;
+    };
+    for name in &names {
+        let script = hooks_dir.join(format!("{}{}", HOOK_PREFIX, name.replace('_', "-")));
+        if !script.is_file() {
+            continue;
+        }
+        if let Err(e) = run_hook(&script, name, &body) {
+            report(format!("Error running {}: {}", script.display(), e));
+        }
+    }
+}
+
+/// Posts an event to a webhook, retrying server errors and failed connections.
+fn post(webhook: &Webhook, body: &str, retries: u32) -> Result<(), String> {
+    let mut attempt = 0;
+    loop {
+        let result = ureq::post(&webhook.url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .set("Content-Type", "application/json")
+            .send_string(body);
+        match result {
+            Ok(_) => return Ok(()),
+            // The request itself is wrong, so sending it again won't help.
+            Err(ureq::Error::Status(status, _)) if status < 500 && status != 429 => {
+                return Err(format!("answered {}", status))
+            }
+            Err(e) if attempt >= retries => return Err(e.to_string()),
+            Err(_) => {}
+        }
+        thread::sleep(retry_delay(attempt));
+        attempt += 1;
+    }
+}
+
+/// The delay before retry `attempt + 1`, doubling each time up to [`MAX_RETRY_DELAY`].
+fn retry_delay(attempt: u32) -> Duration {
+    2u32.checked_pow(attempt)
+        .and_then(|factor| RETRY_DELAY.checked_mul(factor))
+        .map_or(MAX_RETRY_DELAY, |delay| delay.min(MAX_RETRY_DELAY))
+}
+
+fn run_hook(script: &Path, name: &str, body: &str) -> Result<(), String> {
+    let mut child = Command::new(script)
+        .env("ROLL_EVENT", name)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A script ignoring its input may exit before reading it, which is fine.
+        stdin.write_all(body.as_bytes()).ok();
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => format!("exited with {}", output.status),
+            stderr => stderr.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(0), RETRY_DELAY);
+        assert_eq!(retry_delay(2), RETRY_DELAY * 4);
+        assert_eq!(retry_delay(20), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+}
@@ -0,0 +1,48 @@
+//! Opening links with the system opener, such as `xdg-open`, or the one set in the config:
+//!
+//! ```toml
+//! [links]
+//! opener = "firefox --new-tab"
+//! ```
+use crate::config;
+use std::process::{Command, Stdio};
+use std::thread;
+
+#[cfg(target_os = "macos")]
+const DEFAULT_OPENER: &str = "open";
+#[cfg(target_os = "windows")]
+const DEFAULT_OPENER: &str = "explorer";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const DEFAULT_OPENER: &str = "xdg-open";
+
+/// Opens a URL or file path with the configured opener, without waiting for it to finish.
+pub fn open(target: &str) -> Result<(), String> {
+    open_with(target, &opener_command())
+}
+
+/// Like [`open`], but with an explicit opener command such as `firefox --new-tab`.
+pub fn open_with(target: &str, opener: &str) -> Result<(), String> {
+    let mut parts = opener.split_whitespace();
+    let program = parts.next().ok_or("No opener configured")?;
+    // The opener's output would garble the TUI, so it is discarded.
+    let mut child = Command::new(program)
+        .args(parts)
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to launch opener '{}': {}", opener, e))?;
+    // Reap the opener once it exits, so it doesn't linger as a zombie.
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn opener_command() -> String {
+    config::get()
+        .links
+        .opener
+        .clone()
+        .filter(|opener| !opener.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_OPENER.to_string())
+}
@@ -8,21 +8,98 @@ diesel::table! {
         title -> Text,
         description -> Nullable<Text>,
         status -> ProjectStatusMapping,
+        slug -> Text,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::{Integer, Text, Timestamp};
+    task_comments (id) {
+        id -> Integer,
+        task_id -> Integer,
+        body -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::{Integer, Text, Timestamp};
+    task_commits (id) {
+        id -> Integer,
+        task_id -> Integer,
+        sha -> Text,
+        summary -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    task_dependencies (task_id, blocker_id) {
+        task_id -> Integer,
+        blocker_id -> Integer,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::{Integer, Nullable, Text, Timestamp};
+    task_links (id) {
+        id -> Integer,
+        task_id -> Integer,
+        target -> Text,
+        label -> Nullable<Text>,
+        created_at -> Timestamp,
     }
 }
 
 diesel::table! {
     use crate::models::task_status::TaskStatusMapping;
-    use diesel::sql_types::{Integer, Text, Nullable};
+    use diesel::sql_types::{Integer, Text, Nullable, Timestamp};
+    task_status_changes (id) {
+        id -> Integer,
+        task_id -> Integer,
+        from_status -> TaskStatusMapping,
+        to_status -> TaskStatusMapping,
+        reason -> Nullable<Text>,
+        changed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    task_tags (task_id, tag) {
+        task_id -> Integer,
+        tag -> Text,
+    }
+}
+
+diesel::table! {
+    use crate::models::task_priority::TaskPriorityMapping;
+    use crate::models::task_status::TaskStatusMapping;
+    use diesel::sql_types::{Date, Integer, Text, Nullable};
     tasks (id) {
         id -> Integer,
         title -> Text,
         description -> Nullable<Text>,
         status -> TaskStatusMapping,
         project_id -> Nullable<Integer>,
+        priority -> Nullable<TaskPriorityMapping>,
+        due_date -> Nullable<Date>,
     }
 }
 
+diesel::joinable!(task_comments -> tasks (task_id));
+diesel::joinable!(task_commits -> tasks (task_id));
+diesel::joinable!(task_links -> tasks (task_id));
+diesel::joinable!(task_status_changes -> tasks (task_id));
+diesel::joinable!(task_tags -> tasks (task_id));
 diesel::joinable!(tasks -> projects (project_id));
 
-diesel::allow_tables_to_appear_in_same_query!(projects, tasks,);
+diesel::allow_tables_to_appear_in_same_query!(
+    projects,
+    task_comments,
+    task_commits,
+    task_dependencies,
+    task_links,
+    task_status_changes,
+    task_tags,
+    tasks,
+);
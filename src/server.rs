@@ -0,0 +1,529 @@
+//! A local HTTP API for scripts and editor plugins, run by `roll serve`.
+//!
+//! Requests and responses are JSON. Statuses are written as in the CLI, so `"in_progress"`
+//! and `"In Progress"` both work, and responses use the latter:
+//!
+//! ```text
+//! GET    /tasks?status=blocked&project=garden&ready=true   the same filter as `roll task list -w`
+//! POST   /tasks          {"title": "...", "description": "...", "status": "...", "project": "garden"}
+//! GET    /tasks/12
+//! PATCH  /tasks/12       the fields of POST, plus "reason" for a status change
+//! DELETE /tasks/12
+//! GET    /projects
+//! POST   /projects       {"title": "...", "description": "...", "status": "..."}
+//! GET    /projects/garden
+//! PATCH  /projects/garden
+//! DELETE /projects/garden
+//! ```
+//!
+//! Projects are given by id, slug or unambiguous slug prefix, like on the command line. Errors
+//! come as `{"error": "..."}` with a matching status code. If `[server] token` is set in the
+//! config file, every request needs an `Authorization: Bearer <token>` header.
+//!
+//! So that web pages can't use the API behind the user's back, requests from a browser must
+//! come from a loopback origin and name a loopback host, or the address the server listens on,
+//! and bodies must be sent as `application/json`, which a page can't do without asking first.
+use crate::models::project_status::ProjectStatus;
+use crate::models::task_status::TaskStatus;
+use crate::models::{
+    Project, ProjectJson, ProjectLookupError, Task, TaskFilter, TaskJson, TaskUpdateError,
+};
+use crate::notify;
+use diesel::SqliteConnection;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use tiny_http::{Header, Method, Request, Response};
+
+pub const DEFAULT_BIND: &str = "127.0.0.1:7878";
+/// Largest request body taken, so a single client can't exhaust memory.
+const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// A project given by id or by slug, title or slug prefix.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ProjectReference {
+    Id(i32),
+    Name(String),
+}
+
+impl ProjectReference {
+    fn resolve(&self, conn: &mut SqliteConnection) -> Result<i32, ApiError> {
+        let reference = match self {
+            ProjectReference::Id(id) => id.to_string(),
+            ProjectReference::Name(name) => name.clone(),
+        };
+        match Project::resolve(conn, &reference) {
+            Ok(project) => Ok(project.id),
+            // The request names a project, rather than being about one.
+            Err(ProjectLookupError::NotFound(_)) => Err(ApiError::bad_request(format!(
+                "Project not found: {}",
+                reference
+            ))),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// The body of `POST /tasks` and `PATCH /tasks/<id>`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TaskInput {
+    title: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+    project: Option<ProjectReference>,
+    /// Why the status changes, only taken by `PATCH`.
+    reason: Option<String>,
+}
+
+/// The body of `POST /projects` and `PATCH /projects/<project>`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectInput {
+    title: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+}
+
+/// A failed request, answered with `{"error": message}`.
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, message)
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self::new(404, message)
+    }
+}
+
+impl From<diesel::result::Error> for ApiError {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => Self::not_found("Not found"),
+            error => Self::new(500, error.to_string()),
+        }
+    }
+}
+
+impl From<ProjectLookupError> for ApiError {
+    fn from(error: ProjectLookupError) -> Self {
+        match error {
+            ProjectLookupError::NotFound(_) => Self::not_found(error.to_string()),
+            ProjectLookupError::Ambiguous(..) => Self::bad_request(error.to_string()),
+            ProjectLookupError::Database(error) => error.into(),
+        }
+    }
+}
+
+impl From<TaskUpdateError> for ApiError {
+    fn from(error: TaskUpdateError) -> Self {
+        match error {
+            TaskUpdateError::Workflow(error) => Self::new(422, error.to_string()),
+            TaskUpdateError::Database(error) => error.into(),
+        }
+    }
+}
+
+/// What a request is answered with: a status code and a JSON body, if any.
+type Reply = Result<(u16, Option<Value>), ApiError>;
+
+fn ok(value: impl Serialize) -> Reply {
+    Ok((200, Some(json!(value))))
+}
+
+fn created(value: impl Serialize) -> Reply {
+    Ok((201, Some(json!(value))))
+}
+
+fn deleted(amount: usize) -> Reply {
+    match amount {
+        0 => Err(ApiError::not_found("Not found")),
+        _ => Ok((204, None)),
+    }
+}
+
+/// The API server, listening but not yet answering requests.
+pub struct Server {
+    http: tiny_http::Server,
+    token: Option<String>,
+}
+
+impl Server {
+    /// Listens on `address`, such as `127.0.0.1:7878`. With a `token`, requests must carry it
+    /// as a bearer token.
+    pub fn bind(address: &str, token: Option<String>) -> Result<Self, String> {
+        let http = tiny_http::Server::http(address)
+            .map_err(|e| format!("Failed to listen on {}: {}", address, e))?;
+        Ok(Self { http, token })
+    }
+
+    /// The address the server listens on, telling the port when bound to port 0.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Answers requests one at a time, forever.
+    pub fn run(&self, conn: &mut SqliteConnection) {
+        for mut request in self.http.incoming_requests() {
+            let reply = self
+                .check_origin(&request)
+                .and_then(|()| self.authorize(&request))
+                .and_then(|()| handle(conn, &mut request));
+            let response = match reply {
+                Ok((status, Some(body))) => json_response(status, &body),
+                Ok((status, None)) => Response::from_data(Vec::new()).with_status_code(status),
+                Err(e) => {
+                    let response = json_response(e.status, &json!({ "error": e.message }));
+                    match e.status {
+                        401 => response.with_header(header("WWW-Authenticate", "Bearer")),
+                        _ => response,
+                    }
+                }
+            };
+            if let Err(e) = request.respond(response) {
+                eprintln!("Error answering request: {}", e);
+            }
+            for error in notify::take_errors() {
+                eprintln!("{}", error);
+            }
+        }
+    }
+
+    /// Rejects requests made by web pages on other sites, telling by their `Origin`, and ones
+    /// sent to a host name that only resolves to this machine for an attacker's page.
+    fn check_origin(&self, request: &Request) -> Result<(), ApiError> {
+        let bound = self.local_addr().map(|address| address.ip());
+        let host_allowed = |authority: &str| {
+            let host = host_of(authority);
+            is_loopback(host)
+                || host
+                    .parse::<IpAddr>()
+                    .ok()
+                    .is_some_and(|ip| Some(ip) == bound)
+        };
+        if let Some(origin) = header_value(request, "Origin") {
+            let authority = origin
+                .strip_prefix("http://")
+                .or_else(|| origin.strip_prefix("https://"));
+            if !authority.is_some_and(|authority| is_loopback(host_of(authority))) {
+                return Err(ApiError::new(
+                    403,
+                    format!("Origin not allowed: {}", origin),
+                ));
+            }
+        }
+        match header_value(request, "Host") {
+            Some(host) if !host_allowed(host) => {
+                Err(ApiError::new(403, format!("Host not allowed: {}", host)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn authorize(&self, request: &Request) -> Result<(), ApiError> {
+        let Some(token) = &self.token else {
+            return Ok(());
+        };
+        let given = header_value(request, "Authorization")
+            .and_then(|value| value.trim().split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+            .map(|(_, given)| given.trim());
+        match given {
+            Some(given) if constant_time_eq(given.as_bytes(), token.as_bytes()) => Ok(()),
+            _ => Err(ApiError::new(401, "Missing or wrong bearer token")),
+        }
+    }
+}
+
+/// Compares two byte strings in a time that only depends on their lengths, so the time taken
+/// to reject a token tells nothing about how much of it was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// The host of an authority such as `localhost:7878` or `[::1]:7878`, without brackets.
+fn host_of(authority: &str) -> &str {
+    match authority.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(rest),
+        None => authority.split(':').next().unwrap_or(authority),
+    }
+}
+
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+fn header_value<'a>(request: &'a Request, field: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(field))
+        .map(|header| header.value.as_str())
+}
+
+fn header(field: &str, value: &str) -> Header {
+    Header::from_bytes(field.as_bytes(), value.as_bytes()).expect("header is valid ASCII")
+}
+
+fn json_response(status: u16, body: &Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn handle(conn: &mut SqliteConnection, request: &mut Request) -> Reply {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let method = request.method().clone();
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["tasks"]) => list_tasks(conn, query),
+        (Method::Post, ["tasks"]) => create_task(conn, read_body(request)?),
+        (Method::Get, ["tasks", id]) => ok(TaskJson::from(Task::find(conn, task_id(id)?)?)),
+        (Method::Patch, ["tasks", id]) => update_task(conn, task_id(id)?, read_body(request)?),
+        (Method::Delete, ["tasks", id]) => deleted(Task::delete(conn, task_id(id)?)?),
+        (Method::Get, ["projects"]) => {
+            let projects = Project::list(conn)?;
+            ok(projects
+                .into_iter()
+                .map(ProjectJson::from)
+                .collect::<Vec<_>>())
+        }
+        (Method::Post, ["projects"]) => create_project(conn, read_body(request)?),
+        (Method::Get, ["projects", reference]) => ok(ProjectJson::from(Project::resolve(
+            conn,
+            &decode(reference)?,
+        )?)),
+        (Method::Patch, ["projects", reference]) => {
+            let project = Project::resolve(conn, &decode(reference)?)?;
+            update_project(conn, project.id, read_body(request)?)
+        }
+        (Method::Delete, ["projects", reference]) => {
+            let project = Project::resolve(conn, &decode(reference)?)?;
+            deleted(Project::delete(conn, project.id)?)
+        }
+        (_, ["tasks"] | ["tasks", _] | ["projects"] | ["projects", _]) => Err(ApiError::new(
+            405,
+            format!("Method {} not allowed on {}", method, path),
+        )),
+        _ => Err(ApiError::not_found(format!("No such endpoint: {}", path))),
+    }
+}
+
+fn task_id(segment: &str) -> Result<i32, ApiError> {
+    segment
+        .parse()
+        .map_err(|_| ApiError::bad_request(format!("Invalid task id: {}", segment)))
+}
+
+/// Reads a JSON body of at most [`MAX_BODY_SIZE`] bytes, taking an empty one as `{}`. The body
+/// must be sent as `application/json`, which browsers only allow other sites after asking.
+fn read_body<T: DeserializeOwned + Default>(request: &mut Request) -> Result<T, ApiError> {
+    let content_type = header_value(request, "Content-Type").unwrap_or("");
+    let media_type = content_type.split(';').next().unwrap_or("").trim();
+    if !media_type.eq_ignore_ascii_case("application/json") {
+        return Err(ApiError::new(
+            415,
+            "The body must be sent as Content-Type: application/json",
+        ));
+    }
+    let too_large = || {
+        ApiError::new(
+            413,
+            format!("The body is larger than {} bytes", MAX_BODY_SIZE),
+        )
+    };
+    if request
+        .body_length()
+        .is_some_and(|length| length as u64 > MAX_BODY_SIZE)
+    {
+        return Err(too_large());
+    }
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_SIZE + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::bad_request(format!("Failed to read body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY_SIZE {
+        return Err(too_large());
+    }
+    if body.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(&body).map_err(|e| ApiError::bad_request(format!("Invalid body: {}", e)))
+}
+
+fn parse_status<T: FromStr<Err = String>>(status: Option<&str>) -> Result<Option<T>, ApiError> {
+    status
+        .map(|status| T::from_str(status).map_err(ApiError::bad_request))
+        .transpose()
+}
+
+fn list_tasks(conn: &mut SqliteConnection, query: &str) -> Reply {
+    let mut filter = TaskFilter::default();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        filter
+            .set(&decode(key)?, &decode(value)?)
+            .map_err(ApiError::bad_request)?;
+    }
+    let tasks = Task::list_filtered(conn, &filter)?;
+    ok(tasks.into_iter().map(TaskJson::from).collect::<Vec<_>>())
+}
+
+fn create_task(conn: &mut SqliteConnection, input: TaskInput) -> Reply {
+    if input.reason.is_some() {
+        return Err(ApiError::bad_request("A reason is only taken by PATCH"));
+    }
+    let status = parse_status::<TaskStatus>(input.status.as_deref())?;
+    let project_id = match &input.project {
+        Some(project) => Some(project.resolve(conn)?),
+        None => None,
+    };
+    let task = Task::create(
+        conn,
+        input.title.as_deref(),
+        input.description.as_deref(),
+        status,
+        project_id,
+    )?;
+    created(TaskJson::from(task))
+}
+
+fn update_task(conn: &mut SqliteConnection, id: i32, input: TaskInput) -> Reply {
+    if input.title.is_none()
+        && input.description.is_none()
+        && input.status.is_none()
+        && input.project.is_none()
+    {
+        return Err(nothing_to_change());
+    }
+    let status = parse_status::<TaskStatus>(input.status.as_deref())?;
+    let project_id = match &input.project {
+        Some(project) => Some(project.resolve(conn)?),
+        None => None,
+    };
+    let task = Task::update_with_reason(
+        conn,
+        id,
+        input.title.as_deref(),
+        input.description.as_deref(),
+        status,
+        project_id,
+        input.reason.as_deref(),
+    )?;
+    ok(TaskJson::from(task))
+}
+
+fn nothing_to_change() -> ApiError {
+    ApiError::bad_request("Nothing to change, give at least one field")
+}
+
+fn create_project(conn: &mut SqliteConnection, input: ProjectInput) -> Reply {
+    let status = parse_status::<ProjectStatus>(input.status.as_deref())?;
+    let project = Project::create(
+        conn,
+        input.title.as_deref(),
+        input.description.as_deref(),
+        status,
+    )?;
+    created(ProjectJson::from(project))
+}
+
+fn update_project(conn: &mut SqliteConnection, id: i32, input: ProjectInput) -> Reply {
+    if input.title.is_none() && input.description.is_none() && input.status.is_none() {
+        return Err(nothing_to_change());
+    }
+    let status = parse_status::<ProjectStatus>(input.status.as_deref())?;
+    let project = Project::update(
+        conn,
+        id,
+        input.title.as_deref(),
+        input.description.as_deref(),
+        status,
+    )?;
+    ok(ProjectJson::from(project))
+}
+
+/// Decodes a percent-encoded part of a URL, taking `+` as a space as forms do.
+fn decode(text: &str) -> Result<String, ApiError> {
+    let invalid = || ApiError::bad_request(format!("Invalid URL encoding: {}", text));
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.bytes();
+    while let Some(byte) = rest.next() {
+        bytes.push(match byte {
+            b'+' => b' ',
+            b'%' => {
+                let hex = [
+                    rest.next().ok_or_else(invalid)?,
+                    rest.next().ok_or_else(invalid)?,
+                ];
+                let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+                u8::from_str_radix(hex, 16).map_err(|_| invalid())?
+            }
+            byte => byte,
+        });
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loopback_hosts() {
+        for authority in [
+            "localhost:7878",
+            "LOCALHOST",
+            "127.0.0.1:7878",
+            "127.1.2.3",
+            "[::1]:80",
+        ] {
+            assert!(is_loopback(host_of(authority)), "{}", authority);
+        }
+        for authority in [
+            "evil.example",
+            "localhost.evil.example:7878",
+            "10.0.0.1",
+            "[::2]",
+        ] {
+            assert!(!is_loopback(host_of(authority)), "{}", authority);
+        }
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"s3cret", b"s3cret"));
+        assert!(!constant_time_eq(b"s3cret", b"s3cres"));
+        assert!(!constant_time_eq(b"s3cret", b"s3cret!"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("in+progress").unwrap(), "in progress");
+        assert_eq!(decode("garden%2Dshed%20%C3%A9").unwrap(), "garden-shed é");
+        assert!(decode("100%").is_err());
+        assert!(decode("%zz").is_err());
+    }
+}
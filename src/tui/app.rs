@@ -1,62 +1,158 @@
+use super::components::command_palette::{Command, CommandPalette};
 use super::components::popup::Popup;
 use super::components::project_list::ProjectList;
+use super::components::tag_filter_picker::TagFilterPicker;
+use super::components::task_link_picker::TaskLinkPicker;
 use super::components::task_list::TaskList;
+use super::components::workspace_picker::WorkspacePicker;
 use super::components::Component;
+use super::markdown::render_markdown;
 use super::utils::centered_rect;
-use crate::db::connection::establish_connection;
+use crate::db::connection::{
+    database_location, establish_connection_at, try_establish_connection_at,
+};
+use crate::db::discovery::{DatabaseLocation, DatabaseSource};
+use crate::editor;
+use crate::models::{workflow::WorkflowError, Link, TaskTag, TaskUpdateError};
+use crate::notify;
+use crate::opener;
+use crate::workspaces::Workspaces;
+use diesel::SqliteConnection;
 use ratatui::Frame;
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, KeyCode, KeyEvent, KeyEventKind},
-    layout::{Constraint, Layout, Rect},
+    crossterm::event::{
+        self, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Style},
     text::Line,
-    widgets::{Block, Paragraph, Widget},
+    widgets::{Block, Paragraph, Widget, Wrap},
     DefaultTerminal,
 };
 use std::cell::RefCell;
 use std::io;
 use std::rc::Rc;
 
+/// A message shown in the status bar at the bottom of the screen.
+enum StatusMessage {
+    Info(String),
+    Error(String),
+}
+
+impl StatusMessage {
+    fn to_line(&self) -> Line<'_> {
+        match self {
+            StatusMessage::Info(message) => Line::raw(message.as_str()),
+            StatusMessage::Error(message) => {
+                Line::styled(message.as_str(), Style::default().fg(Color::Red))
+            }
+        }
+    }
+}
+
+#[derive(PartialEq)]
 enum ActiveScreen {
     Projects,
     Tasks,
 }
 
 pub struct App {
+    conn: Rc<RefCell<SqliteConnection>>,
+    /// The database in use, shown in the title bar.
+    database: DatabaseLocation,
     tasks: TaskList,
     projects: ProjectList,
     active_screen: ActiveScreen,
     popup: Option<Popup>,
+    /// The task to open in the external editor once the current event is handled.
+    edit_request: Option<i32>,
+    /// Feedback about the last action, shown in the status bar until the next key press.
+    status_message: Option<StatusMessage>,
+    /// Lines scrolled down in the activity feed of the selected task.
+    activity_scroll: u16,
+    /// The task the activity feed was scrolled for, so selecting another one starts at the top.
+    activity_task_id: Option<i32>,
+    /// Area the activity feed was last rendered into, for scrolling it with the mouse.
+    activity_area: Rect,
     exit: bool,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let conn = Rc::new(RefCell::new(establish_connection()));
+    /// Creates the app on an open database. Fails if the tasks or projects can't be loaded.
+    pub fn new(database: DatabaseLocation, conn: SqliteConnection) -> Result<Self, String> {
+        let conn = Rc::new(RefCell::new(conn));
+        let tasks = TaskList::new(Rc::clone(&conn)).map_err(|e| e.to_string())?;
+        let projects = ProjectList::new(Rc::clone(&conn)).map_err(|e| e.to_string())?;
         let mut app = Self {
-            tasks: TaskList::new(Rc::clone(&conn)),
-            projects: ProjectList::new(Rc::clone(&conn)),
+            conn: Rc::clone(&conn),
+            database,
+            tasks,
+            projects,
             active_screen: ActiveScreen::Tasks,
             popup: None,
+            edit_request: None,
+            status_message: None,
+            activity_scroll: 0,
+            activity_task_id: None,
+            activity_area: Rect::ZERO,
             exit: false,
         };
         app.tasks.switch_active();
-        app
+        Ok(app)
     }
     pub fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         while !self.exit {
             terminal.draw(|frame| self.draw(frame))?;
             self.handle_events()?;
+            if let Some(error) = notify::take_errors().pop() {
+                self.status_message = Some(StatusMessage::Error(error));
+            }
+            if let Some(task_id) = self.edit_request.take() {
+                self.edit_task(terminal, task_id)?;
+            }
         }
         Ok(())
     }
 
+    /// Suspends the terminal, lets the user edit the task in `$EDITOR` and restores it again.
+    fn edit_task(&mut self, terminal: &mut DefaultTerminal, task_id: i32) -> io::Result<()> {
+        super::restore();
+        let result = editor::edit_task(&mut self.conn.borrow_mut(), task_id);
+        *terminal = super::init()?;
+        terminal.clear()?;
+        self.status_message = Some(match result {
+            Ok(Some(_)) => StatusMessage::Info(format!("Task {} updated", task_id)),
+            Ok(None) => StatusMessage::Info(format!("Task {} unchanged", task_id)),
+            Err(e) => StatusMessage::Error(format!("Error editing task: {}", e)),
+        });
+        self.refresh();
+        Ok(())
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
+        let [title_area, body_area, status_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        frame.render_widget(
+            Line::styled(
+                format!("On a Roll: {}", self.database),
+                Style::default().fg(Color::DarkGray),
+            ),
+            title_area,
+        );
         let [main_area, detail_area] =
             Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)])
                 .margin(1)
                 .spacing(1)
-                .areas(frame.area());
+                .areas(body_area);
+        if let Some(status_message) = &self.status_message {
+            frame.render_widget(status_message.to_line(), status_area);
+        }
         let [task_area, project_area] =
             Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
                 .areas(main_area);
@@ -64,7 +160,14 @@ impl App {
         self.tasks.render(frame, task_area);
         self.projects.render(frame, project_area);
         if let Some(popup) = self.get_popup() {
-            let popup_area = centered_rect(60, 20, frame.area());
+            let popup_area = match popup {
+                Popup::CommandPalette(_)
+                | Popup::TaskInput(_)
+                | Popup::TaskUpdate(_)
+                | Popup::ProjectInput(_)
+                | Popup::ProjectUpdate(_) => centered_rect(60, 50, frame.area()),
+                _ => centered_rect(60, 20, frame.area()),
+            };
             popup.render(frame, popup_area);
         }
     }
@@ -90,13 +193,24 @@ impl App {
             event::Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
                 self.handle_key_event(key_event)
             }
+            event::Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+            event::Event::Paste(text) => {
+                if let Some(popup) = self.popup.as_mut() {
+                    popup.handle_paste(&text);
+                }
+            }
             _ => {}
         };
         Ok(())
     }
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        self.status_message = None;
+        self.tasks
+            .set_default_project(self.projects.get_selected().map(|project| project.id));
         if let Some(popup) = self.popup.take() {
             self.handle_popup_key_event(key_event, popup);
+        } else if is_command_palette_key(key_event) {
+            self.open_command_palette();
         } else {
             match self.active_screen {
                 ActiveScreen::Tasks => self.handle_tasks_key_event(key_event),
@@ -105,38 +219,173 @@ impl App {
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        if let Some(popup) = self.popup.as_mut() {
+            popup.handle_mouse_events(mouse_event);
+            return;
+        }
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        if self.activity_area.contains(Position::new(column, row)) {
+            match mouse_event.kind {
+                MouseEventKind::ScrollDown => self.scroll_activity(1),
+                MouseEventKind::ScrollUp => self.scroll_activity(-1),
+                _ => {}
+            }
+            return;
+        }
+        let screen = if self.tasks.contains(column, row) {
+            ActiveScreen::Tasks
+        } else if self.projects.contains(column, row) {
+            ActiveScreen::Projects
+        } else {
+            return;
+        };
+        // Clicking into the other pane focuses it, scrolling over it does not.
+        if mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
+            && screen != self.active_screen
+        {
+            self.switch_screen();
+        }
+        match screen {
+            ActiveScreen::Tasks => self.tasks.handle_mouse_events(mouse_event),
+            ActiveScreen::Projects => self.projects.handle_mouse_events(mouse_event),
+        }
+    }
+
     fn handle_tasks_key_event(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('q') => self.exit(),
-            KeyCode::Tab => {
-                self.active_screen = ActiveScreen::Projects;
-                self.tasks.switch_active();
-                self.projects.switch_active()
+            KeyCode::Char('W') => self.open_workspace_picker(),
+            KeyCode::Char('e') => self.edit_request = self.tasks.get_selected().map(|t| t.id),
+            KeyCode::Char('o') => self.open_task_link(),
+            KeyCode::Char('J') => self.scroll_activity(1),
+            KeyCode::Char('K') => self.scroll_activity(-1),
+            KeyCode::Tab => self.switch_screen(),
+            _ => self.tasks.handle_key_events(key_event),
+        }
+    }
+
+    /// Opens the link of the selected task, or lets the user pick one if it has several.
+    fn open_task_link(&mut self) {
+        let Some(task_id) = self.tasks.get_selected().map(|task| task.id) else {
+            return;
+        };
+        let mut links = self.tasks.get_selected_links();
+        match links.len() {
+            0 => {
+                self.status_message = Some(StatusMessage::Info(format!(
+                    "Task {} has no links, add one with `roll task link`",
+                    task_id
+                )))
             }
+            1 => self.open_link(&links.remove(0)),
+            _ => self.popup = Some(Popup::TaskLinkPicker(TaskLinkPicker::new(links))),
+        }
+    }
 
-            _ => self.tasks.handle_key_events(key_event),
+    fn open_link(&mut self, link: &Link) {
+        self.status_message = Some(match opener::open(&link.target) {
+            Ok(()) => StatusMessage::Info(format!("Opened {}", link.target)),
+            Err(e) => StatusMessage::Error(format!("Error opening link: {}", e)),
+        });
+    }
+
+    fn open_tag_filter(&mut self) {
+        let tags = TaskTag::list_all(&mut self.conn.borrow_mut());
+        match tags {
+            Ok(tags) if tags.is_empty() && self.tasks.tag_filter().is_none() => {
+                self.status_message = Some(StatusMessage::Info(
+                    "No tags, tag a task with t first".to_string(),
+                ))
+            }
+            Ok(tags) => {
+                self.popup = Some(Popup::TagFilterPicker(TagFilterPicker::new(
+                    tags,
+                    self.tasks.tag_filter(),
+                )))
+            }
+            Err(error) => self.report_error(Err(error)),
+        }
+    }
+
+    fn filter_by_tag(&mut self, tag: Option<String>) {
+        self.status_message = Some(StatusMessage::Info(match &tag {
+            Some(tag) => format!("Showing tasks tagged {}", tag),
+            None => "Showing all tasks".to_string(),
+        }));
+        if let Err(error) = self.tasks.set_tag_filter(tag) {
+            self.report_load_error("tasks", error);
         }
     }
 
+    fn open_workspace_picker(&mut self) {
+        match Workspaces::load() {
+            Ok(workspaces) if workspaces.workspaces.is_empty() => {
+                self.status_message = Some(StatusMessage::Info(
+                    "No workspaces, add one with `roll workspace add`".to_string(),
+                ))
+            }
+            Ok(workspaces) => {
+                let current = match &self.database.source {
+                    DatabaseSource::Workspace(name) => Some(name.as_str()),
+                    _ => None,
+                };
+                self.popup = Some(Popup::WorkspacePicker(WorkspacePicker::new(
+                    &workspaces,
+                    current,
+                )));
+            }
+            Err(e) => self.status_message = Some(StatusMessage::Error(e)),
+        }
+    }
+
+    /// Reopens the connection shared by the lists on the database of a workspace, and makes
+    /// it the active workspace.
+    fn switch_workspace(&mut self, name: &str) {
+        let result = Workspaces::load().and_then(|mut workspaces| {
+            let database = DatabaseLocation::workspace(name, workspaces.get(name)?);
+            let conn = try_establish_connection_at(&database)?;
+            workspaces.switch(name)?;
+            workspaces.save()?;
+            Ok((database, conn))
+        });
+        match result {
+            Ok((database, conn)) => {
+                *self.conn.borrow_mut() = conn;
+                self.database = database;
+                self.status_message = Some(StatusMessage::Info(format!(
+                    "Switched to workspace {}",
+                    name
+                )));
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::Error(format!(
+                    "Error switching workspace: {}",
+                    e
+                )))
+            }
+        }
+    }
+
+    /// Scrolls the activity feed, which is clamped to its length when rendered.
+    fn scroll_activity(&mut self, lines: i32) {
+        self.activity_scroll = self.activity_scroll.saturating_add_signed(lines as i16);
+    }
+
     fn hannle_projects_key_event(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('q') => self.exit(),
-            KeyCode::Tab => {
-                self.active_screen = ActiveScreen::Tasks;
-                self.tasks.switch_active();
-                self.projects.switch_active()
-            }
+            KeyCode::Char('W') => self.open_workspace_picker(),
+            KeyCode::Tab => self.switch_screen(),
             _ => self.projects.handle_key_events(key_event),
         }
     }
 
     fn handle_popup_key_event(&mut self, key_event: KeyEvent, mut popup: Popup) {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
         match key_event.code {
-            KeyCode::Enter => {
-                popup.submit();
-                self.refresh();
-                self.popup = None
-            }
+            KeyCode::Enter if !popup.is_editing_text() => self.submit_popup(popup),
+            KeyCode::Char('s') if ctrl => self.submit_popup(popup),
             KeyCode::Esc => self.popup = None,
             _ => {
                 popup.handle_key_events(key_event);
@@ -145,46 +394,264 @@ impl App {
         }
     }
 
+    fn submit_popup(&mut self, mut popup: Popup) {
+        if let Popup::CommandPalette(command_palette) = &popup {
+            if let Some(command) = command_palette.selected() {
+                self.execute_command(command.clone());
+            }
+        } else if let Popup::TaskLinkPicker(task_link_picker) = &popup {
+            if let Some(link) = task_link_picker.selected() {
+                self.open_link(link);
+            }
+        } else if let Popup::TagFilterPicker(tag_filter_picker) = &popup {
+            if let Some(choice) = tag_filter_picker.selected() {
+                self.filter_by_tag(choice.0.clone());
+            }
+        } else if let Popup::WorkspacePicker(workspace_picker) = &popup {
+            if let Some(choice) = workspace_picker.selected() {
+                self.switch_workspace(&choice.name);
+            }
+        } else if let Err(error) = popup.submit() {
+            // Keep the popup open, so nothing that was entered gets lost.
+            self.status_message = Some(StatusMessage::Error(error.to_string()));
+            self.popup = Some(popup);
+            return;
+        }
+        self.refresh();
+        self.popup = None
+    }
+
+    fn report_error(&mut self, result: Result<(), diesel::result::Error>) {
+        if let Err(error) = result {
+            self.status_message = Some(StatusMessage::Error(format!("Database error: {}", error)));
+        }
+    }
+
+    fn report_update_error(&mut self, result: Result<(), TaskUpdateError>) {
+        match result {
+            Ok(()) => {}
+            Err(TaskUpdateError::Workflow(error @ WorkflowError::ReasonRequired(_))) => {
+                self.status_message = Some(StatusMessage::Error(format!(
+                    "{}, give one in the update form (u)",
+                    error
+                )));
+            }
+            Err(TaskUpdateError::Workflow(error)) => {
+                self.status_message = Some(StatusMessage::Error(error.to_string()));
+            }
+            Err(TaskUpdateError::Database(error)) => self.report_error(Err(error)),
+        }
+    }
+
+    fn open_command_palette(&mut self) {
+        let commands = Command::all(self.projects.get_all());
+        self.popup = Some(Popup::CommandPalette(CommandPalette::new(commands)));
+    }
+
+    fn execute_command(&mut self, command: Command) {
+        match command {
+            Command::NewTask => self.tasks.open_input(),
+            Command::QuickAddTask => self.tasks.open_quick_add(),
+            Command::UpdateTask => self.tasks.open_update(),
+            Command::DeleteTask => self.tasks.open_delete(),
+            Command::TagTask => self.tasks.open_tag(),
+            Command::CommentTask => self.tasks.open_comment(),
+            Command::OpenTaskLink => self.open_task_link(),
+            Command::FilterByTag => self.open_tag_filter(),
+            Command::SetTaskStatus(status) => {
+                let result = self.tasks.set_status(status);
+                self.report_update_error(result)
+            }
+            Command::MoveTaskToProject { project_id, .. } => {
+                let result = self.tasks.move_to_project(project_id);
+                self.report_update_error(result)
+            }
+            Command::NewProject => self.projects.open_input(),
+            Command::UpdateProject => self.projects.open_update(),
+            Command::DeleteProject => self.projects.open_delete(),
+            Command::SetProjectStatus(status) => {
+                let result = self.projects.set_status(status);
+                self.report_error(result)
+            }
+            Command::SwitchPane => self.switch_screen(),
+            Command::SwitchWorkspace => self.open_workspace_picker(),
+            Command::Quit => self.exit(),
+        }
+    }
+
+    fn switch_screen(&mut self) {
+        self.active_screen = match self.active_screen {
+            ActiveScreen::Tasks => ActiveScreen::Projects,
+            ActiveScreen::Projects => ActiveScreen::Tasks,
+        };
+        self.tasks.switch_active();
+        self.projects.switch_active()
+    }
+
     fn exit(&mut self) {
         self.exit = true;
     }
     fn refresh(&mut self) {
-        self.tasks.refresh();
-        self.projects.refresh()
+        if let Err(error) = self.tasks.refresh() {
+            self.report_load_error("tasks", error);
+        }
+        if let Err(error) = self.projects.refresh() {
+            self.report_load_error("projects", error);
+        }
+    }
+
+    /// Reports that the lists couldn't be reloaded, leaving them as they were.
+    fn report_load_error(&mut self, what: &str, error: impl std::fmt::Display) {
+        self.status_message = Some(StatusMessage::Error(format!(
+            "Error loading {}: {}",
+            what, error
+        )));
+    }
+
+    fn project_title(&self, project_id: Option<i32>) -> &str {
+        project_id
+            .and_then(|id| self.projects.get_all().iter().find(|p| p.id == id))
+            .map_or("-", |project| project.title.as_str())
     }
 
-    fn render_task_detail(&self, area: Rect, buf: &mut Buffer) {
+    fn render_task_detail(&mut self, area: Rect, buf: &mut Buffer) {
+        let [detail_area, activity_area] =
+            Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(area);
+        self.render_activity(activity_area, buf);
         if let Some(selected_task) = self.tasks.get_selected() {
-            let text = vec![
+            let mut text = vec![
                 Line::from(format!("Title: {}", selected_task.title)),
+                Line::from(format!("Status: {}", selected_task.status)),
                 Line::from(format!(
-                    "Description: {}",
-                    selected_task.description.as_deref().unwrap_or("")
+                    "Project: {}",
+                    self.project_title(selected_task.project_id)
+                )),
+                Line::from(format!(
+                    "Priority: {}",
+                    selected_task
+                        .priority
+                        .map_or("-".to_string(), |priority| priority.to_string())
+                )),
+                Line::from(format!(
+                    "Due: {}",
+                    selected_task
+                        .due_date
+                        .map_or("-".to_string(), |due_date| due_date.to_string())
+                )),
+                Line::from(format!(
+                    "Tags: {}",
+                    self.tasks.get_selected_tags().join(", ")
                 )),
-                Line::from(format!("Status: {}", selected_task.status)),
             ];
+            let blockers = self.tasks.get_selected_blockers();
+            if !blockers.is_empty() {
+                text.push(Line::from("Blocked by:"));
+                text.extend(blockers.into_iter().map(|blocker| {
+                    Line::from(format!(
+                        "  {}: {} ({})",
+                        blocker.id, blocker.title, blocker.status
+                    ))
+                }));
+            }
+            let links = self.tasks.get_selected_links();
+            if !links.is_empty() {
+                text.push(Line::from("Links (o to open):"));
+                text.extend(
+                    links
+                        .into_iter()
+                        .map(|link| Line::from(format!("  {}", link))),
+                );
+            }
+            text.extend(description_lines(selected_task.description.as_deref()));
             Paragraph::new(text)
+                .wrap(Wrap { trim: false })
                 .block(Block::bordered().title("Task details"))
-                .render(area, buf);
+                .render(detail_area, buf);
         }
     }
 
+    /// Renders the comments, status changes and commits of the selected task, newest first.
+    fn render_activity(&mut self, area: Rect, buf: &mut Buffer) {
+        self.activity_area = area;
+        let Some(task_id) = self.tasks.get_selected().map(|task| task.id) else {
+            return;
+        };
+        if self.activity_task_id != Some(task_id) {
+            self.activity_task_id = Some(task_id);
+            self.activity_scroll = 0;
+        }
+        let activity = self.tasks.get_selected_activity();
+        let lines: Vec<Line> = if activity.is_empty() {
+            vec![Line::from("No activity yet, press c to comment")]
+        } else {
+            activity
+                .iter()
+                .rev()
+                .flat_map(|entry| entry.lines())
+                .map(Line::from)
+                .collect()
+        };
+        self.activity_scroll = self
+            .activity_scroll
+            .min(lines.len().saturating_sub(1) as u16);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.activity_scroll, 0))
+            .block(Block::bordered().title("Activity (J/K to scroll)"))
+            .render(area, buf);
+    }
+
     fn render_project_detail(&self, area: Rect, buf: &mut Buffer) {
         if let Some(selected_project) = self.projects.get_selected() {
-            let text = vec![
+            let mut text = vec![
                 Line::from(format!("Title: {}", selected_project.title)),
-                Line::from(format!(
-                    "Description: {}",
-                    selected_project.description.as_deref().unwrap_or("")
-                )),
+                Line::from(format!("Slug: {}", selected_project.slug)),
                 Line::from(format!("Status: {}", selected_project.status)),
             ];
+            if let Some(rollup) = self.projects.get_selected_rollup() {
+                text.push(Line::from(format!("Tasks: {}", rollup.summary())));
+                text.extend(
+                    rollup
+                        .counts
+                        .iter()
+                        .map(|(status, count)| Line::from(format!("  {}: {}", status, count))),
+                );
+                if let Some(task) = rollup
+                    .oldest_open_task
+                    .and_then(|id| self.tasks.get_all().iter().find(|task| task.id == id))
+                {
+                    text.push(Line::from(format!(
+                        "Oldest open task: {}: {}",
+                        task.id, task.title
+                    )));
+                }
+                if let Some(suggestion) = rollup.suggested_status(selected_project.status) {
+                    text.push(Line::from(format!(
+                        "Suggested status: {}, as {}",
+                        suggestion.status, suggestion.reason
+                    )));
+                }
+            }
+            text.extend(description_lines(selected_project.description.as_deref()));
             Paragraph::new(text)
+                .wrap(Wrap { trim: false })
                 .block(Block::bordered().title("Project details"))
                 .render(area, buf);
         }
     }
 }
+
+/// Renders a description as Markdown below a blank line and a heading.
+fn description_lines(description: Option<&str>) -> Vec<Line<'static>> {
+    match description {
+        Some(description) if !description.trim().is_empty() => {
+            let mut lines = vec![Line::default(), Line::from("Description:")];
+            lines.extend(render_markdown(description));
+            lines
+        }
+        _ => Vec::new(),
+    }
+}
 impl Widget for &mut App {
     fn render(self, area: Rect, buf: &mut Buffer) {
         match self.active_screen {
@@ -192,14 +659,119 @@ impl Widget for &mut App {
                 self.render_task_detail(area, buf);
             }
             ActiveScreen::Projects => {
+                self.activity_area = Rect::ZERO;
                 self.render_project_detail(area, buf);
             }
         }
     }
 }
 
+fn is_command_palette_key(key_event: KeyEvent) -> bool {
+    match key_event.code {
+        KeyCode::Char(':') => true,
+        KeyCode::Char('p') => key_event.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false,
+    }
+}
+
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        let database =
+            database_location(None).unwrap_or_else(|e| panic!("Error finding the database: {}", e));
+        let conn = establish_connection_at(&database);
+        Self::new(database, conn).unwrap_or_else(|e| panic!("Error loading the database: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::run_migrations;
+    use crate::models::Task;
+    use diesel::Connection;
+
+    fn app() -> App {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        run_migrations(&mut conn);
+        let first = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+        let second = Task::create(&mut conn, Some("Fix roof"), None, None, None).unwrap();
+        TaskTag::add_many(&mut conn, &[first.id, second.id], "house").unwrap();
+        TaskTag::add(&mut conn, second.id, "urgent").unwrap();
+        let database = DatabaseLocation {
+            url: ":memory:".to_string(),
+            source: DatabaseSource::Environment,
+        };
+        App::new(database, conn).unwrap()
+    }
+
+    fn titles(app: &App) -> Vec<&str> {
+        app.tasks
+            .get_all()
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect()
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key_event(KeyEvent::from(code));
+    }
+
+    #[test]
+    fn test_filter_by_tag_narrows_task_list() {
+        let mut app = app();
+        assert_eq!(titles(&app), vec!["Paint", "Fix roof"]);
+
+        app.execute_command(Command::FilterByTag);
+        // The choices are all tasks, house and urgent.
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(titles(&app), vec!["Fix roof"]);
+
+        // The filter holds when the list is refreshed after a change.
+        app.refresh();
+        assert_eq!(titles(&app), vec!["Fix roof"]);
+
+        app.execute_command(Command::FilterByTag);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(titles(&app), vec!["Paint", "Fix roof"]);
+    }
+
+    #[test]
+    fn test_action_key_clears_count() {
+        let mut app = app();
+        Task::create(&mut app.conn.borrow_mut(), Some("Mow"), None, None, None).unwrap();
+        app.refresh();
+
+        // Deleting nothing after `5d` must leave `j` a single step, not five.
+        press(&mut app, KeyCode::Char('5'));
+        press(&mut app, KeyCode::Char('d'));
+        // Drawing a frame moves the popup to the app, which then gets the keys.
+        assert!(app.get_popup().is_some());
+        press(&mut app, KeyCode::Esc);
+        assert!(app.popup.is_none());
+        press(&mut app, KeyCode::Char('j'));
+
+        let selected = app.tasks.get_selected().map(|task| task.title.as_str());
+        assert_eq!(selected, Some("Fix roof"));
+    }
+
+    #[test]
+    fn test_load_errors_go_to_status_bar() {
+        use diesel::RunQueryDsl;
+        let mut app = app();
+        diesel::sql_query("ALTER TABLE projects RENAME TO old_projects")
+            .execute(&mut *app.conn.borrow_mut())
+            .unwrap();
+
+        app.refresh();
+
+        assert!(matches!(
+            &app.status_message,
+            Some(StatusMessage::Error(message)) if message.starts_with("Error loading projects")
+        ));
+        assert_eq!(titles(&app), vec!["Paint", "Fix roof"]);
     }
 }
@@ -0,0 +1,223 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent},
+    layout::{Constraint, Layout, Rect},
+    widgets::{Block, ListItem},
+    Frame,
+};
+use strum::IntoEnumIterator;
+
+use crate::models::{project_status::ProjectStatus, task_status::TaskStatus, Project};
+use crate::tui::{utils::fuzzy_score, widgets::popup::Popup as PopupWidget};
+
+use super::{list_selection::ListSelection, user_input::UserInput, Component};
+
+/// An action that can be run from the command palette.
+///
+/// Task commands act on the tasks marked in the task list, or the selected task if none are
+/// marked. Project commands act on the projects of the project list the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    NewTask,
+    QuickAddTask,
+    UpdateTask,
+    DeleteTask,
+    TagTask,
+    CommentTask,
+    OpenTaskLink,
+    FilterByTag,
+    SetTaskStatus(TaskStatus),
+    MoveTaskToProject { project_id: i32, title: String },
+    NewProject,
+    UpdateProject,
+    DeleteProject,
+    SetProjectStatus(ProjectStatus),
+    SwitchPane,
+    SwitchWorkspace,
+    Quit,
+}
+
+impl Command {
+    /// Returns every available command, including one "move to project" entry per project.
+    pub fn all(projects: &[Project]) -> Vec<Self> {
+        let mut commands = vec![
+            Self::NewTask,
+            Self::QuickAddTask,
+            Self::UpdateTask,
+            Self::DeleteTask,
+            Self::TagTask,
+            Self::CommentTask,
+            Self::OpenTaskLink,
+            Self::FilterByTag,
+        ];
+        commands.extend(TaskStatus::iter().map(Self::SetTaskStatus));
+        commands.extend(projects.iter().map(|project| Self::MoveTaskToProject {
+            project_id: project.id,
+            title: project.title.clone(),
+        }));
+        commands.extend([Self::NewProject, Self::UpdateProject, Self::DeleteProject]);
+        commands.extend(ProjectStatus::iter().map(Self::SetProjectStatus));
+        commands.extend([Self::SwitchPane, Self::SwitchWorkspace, Self::Quit]);
+        commands
+    }
+}
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::NewTask => write!(f, "New task"),
+            Command::QuickAddTask => write!(f, "Quick add task…"),
+            Command::UpdateTask => write!(f, "Update task"),
+            Command::DeleteTask => write!(f, "Delete task"),
+            Command::TagTask => write!(f, "Tag task…"),
+            Command::CommentTask => write!(f, "Comment on task…"),
+            Command::OpenTaskLink => write!(f, "Open task link…"),
+            Command::FilterByTag => write!(f, "Filter tasks by tag…"),
+            Command::SetTaskStatus(status) => write!(f, "Set task status: {}", status),
+            Command::MoveTaskToProject { title, .. } => {
+                write!(f, "Move task to project: {}", title)
+            }
+            Command::NewProject => write!(f, "New project"),
+            Command::UpdateProject => write!(f, "Update project"),
+            Command::DeleteProject => write!(f, "Delete project"),
+            Command::SetProjectStatus(status) => write!(f, "Set project status: {}", status),
+            Command::SwitchPane => write!(f, "Switch pane"),
+            Command::SwitchWorkspace => write!(f, "Switch workspace…"),
+            Command::Quit => write!(f, "Quit"),
+        }
+    }
+}
+
+impl<'a> From<Command> for ListItem<'a> {
+    fn from(command: Command) -> Self {
+        ListItem::new(command.to_string())
+    }
+}
+
+pub struct CommandPalette {
+    query: UserInput,
+    commands: Vec<Command>,
+    matches: ListSelection<Command>,
+    area: Rect,
+}
+
+impl CommandPalette {
+    pub fn new(commands: Vec<Command>) -> Self {
+        let mut matches = ListSelection::new(commands.clone(), "Commands");
+        matches.switch_active();
+        Self {
+            query: UserInput::new("Command".to_string(), true),
+            commands,
+            matches,
+            area: Rect::ZERO,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Command> {
+        self.matches.selected()
+    }
+
+    /// Re-filters the commands against the current query, best matches first.
+    fn filter(&mut self) {
+        let query = self.query.get_input();
+        let mut scored: Vec<(i64, &Command)> = self
+            .commands
+            .iter()
+            .filter_map(|command| {
+                fuzzy_score(query, &command.to_string()).map(|score| (score, command))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches
+            .set_items(scored.into_iter().map(|(_, c)| c.clone()).collect());
+    }
+}
+
+impl Component for CommandPalette {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.area = area;
+        f.render_widget(PopupWidget::default().title("Command Palette"), area);
+        let inner_area = Block::bordered().inner(area);
+        let [query_area, matches_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(inner_area);
+        self.query.render(f, query_area);
+        self.matches.render(f, matches_area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Down | KeyCode::Tab => self.matches.select_next(),
+            KeyCode::Char('n') if ctrl => self.matches.select_next(),
+            KeyCode::Up | KeyCode::BackTab => self.matches.select_previous(),
+            KeyCode::Char('p') if ctrl => self.matches.select_previous(),
+            _ => {
+                self.query.handle_key_events(key);
+                self.filter();
+            }
+        }
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.matches.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.query.handle_paste(text);
+        self.filter();
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_query(palette: &mut CommandPalette, query: &str) {
+        for c in query.chars() {
+            palette.handle_key_events(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_filter_selects_best_match() {
+        let mut palette = CommandPalette::new(Command::all(&[]));
+        type_query(&mut palette, "blocked");
+
+        assert_eq!(
+            palette.selected(),
+            Some(&Command::SetTaskStatus(TaskStatus::Blocked))
+        );
+    }
+
+    #[test]
+    fn test_filter_includes_projects() {
+        let project = Project {
+            id: 7,
+            title: "Garden".to_string(),
+            description: None,
+            status: ProjectStatus::Active,
+            slug: "garden".to_string(),
+        };
+        let mut palette = CommandPalette::new(Command::all(&[project]));
+        type_query(&mut palette, "move garden");
+
+        assert_eq!(
+            palette.selected(),
+            Some(&Command::MoveTaskToProject {
+                project_id: 7,
+                title: "Garden".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_no_match_selects_nothing() {
+        let mut palette = CommandPalette::new(Command::all(&[]));
+        type_query(&mut palette, "zzz");
+
+        assert_eq!(palette.selected(), None);
+    }
+}
@@ -1,14 +1,21 @@
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
 use derive_setters::Setters;
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::Line,
     widgets::{Block, Clear, List, ListItem, ListState},
     Frame,
 };
 
 use super::Component;
 
+/// Two clicks on the same row within this interval count as a double-click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Setters)]
 pub struct ListSelection<T>
 where
@@ -18,6 +25,29 @@ where
     item_cursor: ListState,
     active: bool,
     title: &'static str,
+    /// Whether moving past either end of the list continues at the other end.
+    wrap_around: bool,
+    /// Whether items can be marked with space and visual range selection.
+    multi_select: bool,
+    /// Indices of the marked items.
+    #[setters(skip)]
+    marked: BTreeSet<usize>,
+    /// Index where the current visual range selection started.
+    #[setters(skip)]
+    visual_anchor: Option<usize>,
+    /// A key that starts a multi-key command, such as the first `g` of `gg`.
+    #[setters(skip)]
+    pending_key: Option<char>,
+    /// Count typed before a motion, such as the `5` of `5j`.
+    #[setters(skip)]
+    count: Option<usize>,
+    #[setters(skip)]
+    area: Rect,
+    #[setters(skip)]
+    last_click: Option<(Instant, usize)>,
+    /// Shown below the list, such as a status change the workflow doesn't allow.
+    #[setters(skip)]
+    error: Option<String>,
 }
 
 impl<T> ListSelection<T>
@@ -30,57 +60,201 @@ where
             item_cursor: ListState::default().with_selected(Some(0)),
             active: false,
             title,
+            wrap_around: true,
+            multi_select: false,
+            marked: BTreeSet::new(),
+            visual_anchor: None,
+            pending_key: None,
+            count: None,
+            area: Rect::ZERO,
+            last_click: None,
+            error: None,
         }
     }
     pub fn set_items(&mut self, items: Vec<T>) {
         self.items = items;
         self.item_cursor = ListState::default().with_selected(Some(0));
+        self.clear_marks();
     }
     pub fn switch_active(&mut self) {
         self.active = !self.active
     }
 
+    pub fn get_items(&self) -> &[T] {
+        &self.items
+    }
+
     pub fn selected(&self) -> Option<&T> {
         self.item_cursor
             .selected()
-            .map(|selected| &self.items[selected])
+            .and_then(|selected| self.items.get(selected))
     }
     pub fn reset(&mut self) {
         self.item_cursor.select(Some(0));
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
+    /// Returns the indices of all marked items, including the active visual range.
+    fn marked_indices(&self) -> BTreeSet<usize> {
+        let mut marked = self.marked.clone();
+        if let (Some(anchor), Some(selected)) = (self.visual_anchor, self.item_cursor.selected()) {
+            marked.extend(anchor.min(selected)..=anchor.max(selected));
+        }
+        marked
+    }
+
+    pub fn marked(&self) -> Vec<&T> {
+        self.marked_indices()
+            .into_iter()
+            .filter_map(|index| self.items.get(index))
+            .collect()
+    }
+
+    /// Returns the marked items, or the selected item if nothing is marked.
+    pub fn targets(&self) -> Vec<&T> {
+        let marked = self.marked();
+        if marked.is_empty() {
+            self.selected().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    /// Forgets a count or `g` typed so far, for keys the list's owner handles itself.
+    pub fn clear_pending(&mut self) {
+        self.count = None;
+        self.pending_key = None;
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.visual_anchor = None;
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(selected) = self.item_cursor.selected() {
+            if !self.marked.remove(&selected) {
+                self.marked.insert(selected);
+            }
+        }
+    }
+
+    /// Starts a visual range selection, or marks the current range if one is active.
+    fn toggle_visual(&mut self) {
+        match self.visual_anchor {
+            Some(_) => {
+                self.marked = self.marked_indices();
+                self.visual_anchor = None;
+            }
+            None => self.visual_anchor = self.item_cursor.selected(),
+        }
+    }
+
+    /// Handles the keys for marking items. Returns `true` if the key was consumed.
+    fn handle_mark_key_events(&mut self, key: KeyEvent) -> bool {
+        if !self.multi_select {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('v') => self.toggle_visual(),
+            KeyCode::Esc if self.visual_anchor.is_some() => self.visual_anchor = None,
+            KeyCode::Esc => self.marked.clear(),
+            _ => return false,
+        }
+        true
     }
     pub fn select_next(&mut self) {
+        self.move_selection(1);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.move_selection(-1);
+    }
+
+    /// Moves the selection by `delta` items, wrapping around the ends of the list if
+    /// `wrap_around` is set and stopping at them otherwise.
+    fn move_selection(&mut self, delta: isize) {
         if self.items.is_empty() {
             self.item_cursor.select(None);
             return;
         }
 
-        let next_index = self
-            .item_cursor
-            .selected()
-            .map_or(0, |selected| (selected + 1) % self.items.len());
+        let len = self.items.len() as isize;
+        let index = match self.item_cursor.selected() {
+            Some(selected) => selected as isize + delta,
+            // Without a selection, moving forward starts at the top and backward at the bottom.
+            None if delta > 0 => delta - 1,
+            None => len + delta,
+        };
+        let index = if self.wrap_around {
+            index.rem_euclid(len)
+        } else {
+            index.clamp(0, len - 1)
+        };
 
-        self.item_cursor.select(Some(next_index));
+        self.item_cursor.select(Some(index as usize));
     }
 
-    pub fn select_previous(&mut self) {
+    /// Selects the item at `index`, clamped to the list bounds.
+    fn select_index(&mut self, index: usize) {
         if self.items.is_empty() {
             self.item_cursor.select(None);
             return;
         }
+        self.item_cursor
+            .select(Some(index.min(self.items.len() - 1)));
+    }
 
-        let previous_index = self
-            .item_cursor
-            .selected()
-            .map_or(self.items.len() - 1, |selected| {
-                if selected == 0 {
-                    self.items.len() - 1
-                } else {
-                    selected - 1
-                }
-            });
+    /// Moves the selection by `delta` items without wrapping around, for page-wise movement.
+    fn scroll_selection(&mut self, delta: isize) {
+        let selected = self.item_cursor.selected().unwrap_or(0) as isize;
+        self.select_index((selected + delta).max(0) as usize);
+    }
+
+    /// Number of items visible at once, based on the last rendered area.
+    fn page_size(&self) -> usize {
+        // Two rows are taken up by the border.
+        self.area.height.saturating_sub(2).max(1) as usize
+    }
 
-        self.item_cursor.select(Some(previous_index));
+    /// Maps a terminal row to the index of the item rendered there, if any.
+    fn index_at(&self, row: u16) -> Option<usize> {
+        // The first and last rows of the area are taken up by the border.
+        if row <= self.area.y || row >= self.area.bottom().saturating_sub(1) {
+            return None;
+        }
+        let index = (row - self.area.y - 1) as usize + self.item_cursor.offset();
+        (index < self.items.len()).then_some(index)
+    }
+
+    /// Selects the item under the mouse. Returns `true` if the click completes a double-click
+    /// on the same item.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        if !self.contains(column, row) {
+            return false;
+        }
+        let Some(index) = self.index_at(row) else {
+            return false;
+        };
+        self.item_cursor.select(Some(index));
+
+        let now = Instant::now();
+        let double_click = self.last_click.is_some_and(|(at, last_index)| {
+            last_index == index && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        });
+        self.last_click = if double_click {
+            None
+        } else {
+            Some((now, index))
+        };
+        double_click
     }
+
     pub fn set_selected(&mut self, object: T) {
         if let Some(index) = self.items.iter().position(|item| *item == object) {
             self.item_cursor.select(Some(index));
@@ -95,6 +269,7 @@ where
     T: Into<ListItem<'static>> + Clone + PartialEq,
 {
     fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.area = area;
         f.render_widget(Clear, area);
         let highlight_style = if self.active {
             Style::default()
@@ -103,20 +278,95 @@ where
         } else {
             Style::default().add_modifier(Modifier::ITALIC) // Keep the italic style when not active
         };
-        let task_list = List::new(self.items.clone())
-            .block(Block::bordered().title(self.title))
+        let marked = self.marked_indices();
+        let items = self.items.iter().enumerate().map(|(index, item)| {
+            let list_item: ListItem = item.clone().into();
+            if marked.contains(&index) {
+                list_item.style(Style::default().fg(Color::Yellow))
+            } else {
+                list_item
+            }
+        });
+        let title = if marked.is_empty() {
+            self.title.to_string()
+        } else {
+            format!("{} ({} marked)", self.title, marked.len())
+        };
+        let mut block = Block::bordered().title(title);
+        if let Some(error) = &self.error {
+            let error_style = Style::default().fg(Color::Red);
+            block = block
+                .border_style(error_style)
+                .title_bottom(Line::styled(error.as_str(), error_style));
+        }
+        let task_list = List::new(items)
+            .block(block)
             .highlight_style(highlight_style)
             .highlight_symbol(">>")
             .repeat_highlight_symbol(true);
         f.render_stateful_widget(task_list, area, &mut self.item_cursor);
     }
     fn handle_key_events(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('j') => self.select_next(),
-            KeyCode::Char('k') => self.select_previous(),
+        if self.handle_mark_key_events(key) {
+            self.clear_pending();
+            return;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if let KeyCode::Char(digit @ '0'..='9') = key.code {
+            // A leading zero is not a count.
+            if !ctrl && (digit != '0' || self.count.is_some()) {
+                let digit = digit.to_digit(10).unwrap() as usize;
+                self.count = Some(
+                    self.count
+                        .unwrap_or(0)
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+                return;
+            }
+        }
+
+        let count = self.count.take();
+        let repeat = count.unwrap_or(1) as isize;
+        let half_page = (self.page_size() / 2).max(1) as isize;
+        let page = self.page_size() as isize;
+        match (self.pending_key.take(), key.code) {
+            (Some('g'), KeyCode::Char('g')) => self.select_index(count.unwrap_or(1) - 1),
+            (Some(_), _) => {}
+            (None, KeyCode::Char('g')) if !ctrl => {
+                self.pending_key = Some('g');
+                self.count = count;
+            }
+            (None, KeyCode::Char('G')) => match count {
+                Some(line) => self.select_index(line - 1),
+                None => self.select_index(usize::MAX),
+            },
+            (None, KeyCode::Char('d')) if ctrl => self.scroll_selection(half_page * repeat),
+            (None, KeyCode::Char('u')) if ctrl => self.scroll_selection(-half_page * repeat),
+            (None, KeyCode::Char('j') | KeyCode::Down) => self.move_selection(repeat),
+            (None, KeyCode::Char('k') | KeyCode::Up) => self.move_selection(-repeat),
+            (None, KeyCode::PageDown) => self.scroll_selection(page * repeat),
+            (None, KeyCode::PageUp) => self.scroll_selection(-page * repeat),
+            (None, KeyCode::Home) => self.select_index(0),
+            (None, KeyCode::End) => self.select_index(usize::MAX),
+            _ => {}
+        }
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.click(mouse.column, mouse.row);
+            }
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::ScrollUp => self.select_previous(),
             _ => {}
         }
     }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
 
 #[cfg(test)]
@@ -130,9 +380,9 @@ mod tests {
         name: &'static str,
     }
 
-    impl Into<ListItem<'static>> for TestItem {
-        fn into(self) -> ListItem<'static> {
-            ListItem::new(self.name)
+    impl From<TestItem> for ListItem<'static> {
+        fn from(item: TestItem) -> Self {
+            ListItem::new(item.name)
         }
     }
 
@@ -247,4 +497,237 @@ mod tests {
         list_selection.handle_key_events(KeyEvent::from(KeyCode::Char('k')));
         assert_eq!(list_selection.item_cursor.selected(), Some(0));
     }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: ratatui::crossterm::event::KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_click_selects_row() {
+        let items = vec![
+            TestItem { name: "Item 1" },
+            TestItem { name: "Item 2" },
+            TestItem { name: "Item 3" },
+        ];
+        let mut list_selection = ListSelection::new(items, "Test List");
+        list_selection.area = Rect::new(0, 0, 20, 10);
+
+        let down = MouseEventKind::Down(MouseButton::Left);
+        list_selection.handle_mouse_events(mouse(down, 5, 3));
+        assert_eq!(list_selection.item_cursor.selected(), Some(2));
+
+        // Clicks on the border or below the last item keep the selection.
+        list_selection.handle_mouse_events(mouse(down, 5, 0));
+        list_selection.handle_mouse_events(mouse(down, 5, 6));
+        list_selection.handle_mouse_events(mouse(down, 30, 1));
+        assert_eq!(list_selection.item_cursor.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_double_click() {
+        let items = vec![TestItem { name: "Item 1" }, TestItem { name: "Item 2" }];
+        let mut list_selection = ListSelection::new(items, "Test List");
+        list_selection.area = Rect::new(0, 0, 20, 10);
+
+        assert!(!list_selection.click(5, 1));
+        assert!(list_selection.click(5, 1));
+        assert!(!list_selection.click(5, 1));
+        assert!(!list_selection.click(5, 2));
+    }
+
+    #[test]
+    fn test_scroll() {
+        let items = vec![TestItem { name: "Item 1" }, TestItem { name: "Item 2" }];
+        let mut list_selection = ListSelection::new(items, "Test List");
+
+        list_selection.handle_mouse_events(mouse(MouseEventKind::ScrollDown, 0, 0));
+        assert_eq!(list_selection.item_cursor.selected(), Some(1));
+        list_selection.handle_mouse_events(mouse(MouseEventKind::ScrollUp, 0, 0));
+        assert_eq!(list_selection.item_cursor.selected(), Some(0));
+    }
+
+    fn press(list_selection: &mut ListSelection<TestItem>, keys: &str) {
+        for c in keys.chars() {
+            list_selection.handle_key_events(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    fn ten_items() -> Vec<TestItem> {
+        const NAMES: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        NAMES.iter().map(|name| TestItem { name }).collect()
+    }
+
+    #[test]
+    fn test_count_prefix() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List");
+
+        press(&mut list_selection, "5j");
+        assert_eq!(list_selection.item_cursor.selected(), Some(5));
+
+        press(&mut list_selection, "2k");
+        assert_eq!(list_selection.item_cursor.selected(), Some(3));
+
+        // Counts wrap around like single steps do.
+        press(&mut list_selection, "12j");
+        assert_eq!(list_selection.item_cursor.selected(), Some(5));
+
+        // A count only applies to the next motion.
+        press(&mut list_selection, "3jj");
+        assert_eq!(list_selection.item_cursor.selected(), Some(9));
+    }
+
+    #[test]
+    fn test_leading_zero_is_not_a_count() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List");
+
+        press(&mut list_selection, "0j");
+        assert_eq!(list_selection.item_cursor.selected(), Some(1));
+
+        press(&mut list_selection, "10k");
+        assert_eq!(list_selection.item_cursor.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_jump_to_top_and_bottom() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List");
+
+        press(&mut list_selection, "G");
+        assert_eq!(list_selection.item_cursor.selected(), Some(9));
+
+        press(&mut list_selection, "gg");
+        assert_eq!(list_selection.item_cursor.selected(), Some(0));
+
+        press(&mut list_selection, "4G");
+        assert_eq!(list_selection.item_cursor.selected(), Some(3));
+
+        press(&mut list_selection, "7gg");
+        assert_eq!(list_selection.item_cursor.selected(), Some(6));
+
+        // A `g` followed by anything else is dropped.
+        press(&mut list_selection, "gj");
+        assert_eq!(list_selection.item_cursor.selected(), Some(6));
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::Home));
+        assert_eq!(list_selection.item_cursor.selected(), Some(0));
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::End));
+        assert_eq!(list_selection.item_cursor.selected(), Some(9));
+    }
+
+    #[test]
+    fn test_page_movement() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List");
+        // Six rows leave room for four items between the borders.
+        list_selection.area = Rect::new(0, 0, 20, 6);
+
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        list_selection.handle_key_events(ctrl('d'));
+        assert_eq!(list_selection.item_cursor.selected(), Some(2));
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::PageDown));
+        assert_eq!(list_selection.item_cursor.selected(), Some(6));
+
+        // Page movement stops at the ends instead of wrapping around.
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::PageDown));
+        assert_eq!(list_selection.item_cursor.selected(), Some(9));
+
+        list_selection.handle_key_events(ctrl('u'));
+        assert_eq!(list_selection.item_cursor.selected(), Some(7));
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::PageUp));
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::PageUp));
+        assert_eq!(list_selection.item_cursor.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_arrow_keys() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List");
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::Down));
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::Down));
+        assert_eq!(list_selection.item_cursor.selected(), Some(2));
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::Up));
+        assert_eq!(list_selection.item_cursor.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_without_wrap_around() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List").wrap_around(false);
+
+        press(&mut list_selection, "k");
+        assert_eq!(list_selection.item_cursor.selected(), Some(0));
+
+        press(&mut list_selection, "20j");
+        assert_eq!(list_selection.item_cursor.selected(), Some(9));
+
+        press(&mut list_selection, "j");
+        assert_eq!(list_selection.item_cursor.selected(), Some(9));
+    }
+
+    #[test]
+    fn test_marking_requires_multi_select() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List");
+
+        press(&mut list_selection, " ");
+        assert!(list_selection.marked().is_empty());
+    }
+
+    #[test]
+    fn test_toggle_mark() {
+        let items = ten_items();
+        let mut list_selection = ListSelection::new(items.clone(), "Test List").multi_select(true);
+        assert_eq!(list_selection.targets(), vec![&items[0]]);
+
+        press(&mut list_selection, " 2j ");
+        assert_eq!(list_selection.marked(), vec![&items[0], &items[2]]);
+        assert_eq!(list_selection.targets(), vec![&items[0], &items[2]]);
+
+        press(&mut list_selection, "2k ");
+        assert_eq!(list_selection.marked(), vec![&items[2]]);
+
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::Esc));
+        assert!(list_selection.marked().is_empty());
+    }
+
+    #[test]
+    fn test_visual_range() {
+        let items = ten_items();
+        let mut list_selection = ListSelection::new(items.clone(), "Test List").multi_select(true);
+
+        press(&mut list_selection, "jv2j");
+        assert_eq!(
+            list_selection.marked(),
+            vec![&items[1], &items[2], &items[3]]
+        );
+
+        // Finishing the range keeps it marked while moving on.
+        press(&mut list_selection, "vj");
+        assert_eq!(
+            list_selection.marked(),
+            vec![&items[1], &items[2], &items[3]]
+        );
+
+        // Escape cancels an active range without touching earlier marks.
+        press(&mut list_selection, "v2j");
+        list_selection.handle_key_events(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(
+            list_selection.marked(),
+            vec![&items[1], &items[2], &items[3]]
+        );
+    }
+
+    #[test]
+    fn test_set_items_clears_marks() {
+        let mut list_selection = ListSelection::new(ten_items(), "Test List").multi_select(true);
+
+        press(&mut list_selection, " jv");
+        list_selection.set_items(ten_items());
+        assert!(list_selection.marked().is_empty());
+    }
 }
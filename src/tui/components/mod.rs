@@ -1,28 +1,75 @@
+pub mod command_palette;
 mod list_selection;
 mod multi_input;
 pub mod popup;
 mod project_delete;
 mod project_input;
 pub mod project_list;
+mod project_picker;
 mod project_update;
+pub mod tag_filter_picker;
+mod task_comment_input;
 mod task_delete;
 pub mod task_input;
+pub mod task_link_picker;
 pub mod task_list;
+mod task_quick_add;
+mod task_reason_input;
+mod task_tag_input;
 pub mod task_update;
+mod text_area;
 mod user_input;
+pub mod workspace_picker;
 
-use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::{Position, Rect},
+    Frame,
+};
 
 pub trait Component {
     fn render(&mut self, f: &mut Frame, area: Rect);
     fn handle_key_events(&mut self, key: KeyEvent);
+    fn handle_mouse_events(&mut self, _mouse: MouseEvent) {}
+    /// Handles text pasted into the terminal while bracketed paste is enabled.
+    fn handle_paste(&mut self, _text: &str) {}
+    /// The area the component was last rendered into, used for hit-testing mouse events.
+    fn area(&self) -> Rect;
+    fn contains(&self, column: u16, row: u16) -> bool {
+        self.area().contains(Position::new(column, row))
+    }
+}
+
+/// Why submitting a form failed.
+#[derive(Debug)]
+pub enum SubmitError {
+    /// Some fields are invalid. Their errors are rendered next to the fields.
+    Invalid,
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for SubmitError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl std::fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Invalid => write!(f, "Please fix the highlighted fields"),
+            SubmitError::Database(error) => write!(f, "Database error: {}", error),
+        }
+    }
 }
 
 pub trait InputSubmit {
-    fn submit_and_reset(&mut self) {
-        self.submit();
+    /// Submits the form and resets it, unless submitting failed.
+    fn submit_and_reset(&mut self) -> Result<(), SubmitError> {
+        self.submit()?;
         self.reset();
+        Ok(())
     }
-    fn submit(&self);
+    fn submit(&mut self) -> Result<(), SubmitError>;
     fn reset(&mut self);
 }
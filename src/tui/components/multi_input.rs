@@ -1,12 +1,15 @@
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
+    crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
     layout::{Constraint, Layout, Rect},
     widgets::ListItem,
     Frame,
 };
 use strum::IntoEnumIterator;
 
-use super::{list_selection::ListSelection, user_input::UserInput, Component};
+use super::{
+    list_selection::ListSelection, project_picker::ProjectPicker, text_area::TextArea,
+    user_input::UserInput, Component, SubmitError,
+};
 use strum::EnumIter;
 
 #[derive(Debug, Clone, PartialEq, EnumIter)]
@@ -14,21 +17,33 @@ pub enum InputField {
     Title,
     Description,
     Status,
+    Project,
+    Reason,
 }
 pub struct MultiInput<T>
 where
     T: Into<ListItem<'static>> + Clone,
 {
     title: UserInput,
-    description: UserInput,
+    description: TextArea,
     status: ListSelection<T>,
+    /// Only tasks belong to a project, so only task forms have a project picker.
+    project: Option<ProjectPicker>,
+    /// Only task updates change a status under the workflow, so only they ask for a reason.
+    reason: Option<UserInput>,
     active_field: InputField,
+    /// Set once submitting was attempted, from then on errors update while typing.
+    show_errors: bool,
+    area: Rect,
 }
 
 pub struct Inputs<'a, T> {
     pub title: &'a str,
     pub description: &'a str,
     pub status: &'a T,
+    pub project_id: Option<i32>,
+    /// The reason for a status change, if the form has a reason field and it isn't blank.
+    pub reason: Option<&'a str>,
 }
 
 impl<T> MultiInput<T>
@@ -38,13 +53,47 @@ where
     pub fn new() -> Self {
         Self {
             title: UserInput::new("Task Title".to_string(), true),
-            description: UserInput::new("Task Description".to_string(), false),
+            description: TextArea::new("Task Description".to_string(), false),
             active_field: InputField::Title,
             status: ListSelection::new(T::iter().collect(), "Status"),
+            project: None,
+            reason: None,
+            show_errors: false,
+            area: Rect::ZERO,
+        }
+    }
+    pub fn with_project_picker(mut self, project_picker: ProjectPicker) -> Self {
+        self.project = Some(project_picker);
+        self
+    }
+
+    pub fn with_reason_input(mut self) -> Self {
+        self.reason = Some(UserInput::new(
+            "Reason for status change".to_string(),
+            false,
+        ));
+        self
+    }
+
+    /// Shows an error on the status list, such as a move the workflow doesn't allow.
+    pub fn set_status_error(&mut self, error: Option<String>) {
+        self.status.set_error(error);
+    }
+
+    pub fn set_reason_error(&mut self, error: Option<String>) {
+        if let Some(reason) = self.reason.as_mut() {
+            reason.set_error(error);
         }
     }
+
     fn switch_field(&mut self, reverse: bool) {
-        let fields: Vec<InputField> = InputField::iter().collect();
+        let fields: Vec<InputField> = InputField::iter()
+            .filter(|field| match field {
+                InputField::Project => self.project.is_some(),
+                InputField::Reason => self.reason.is_some(),
+                _ => true,
+            })
+            .collect();
         let mut index = fields.iter().position(|f| f == &self.active_field).unwrap();
 
         // Determine the next field based on the direction
@@ -58,36 +107,122 @@ where
             index = (index + 1) % fields.len();
         }
 
+        self.focus(fields[index].clone());
+    }
+
+    fn focus(&mut self, field: InputField) {
+        if field == self.active_field {
+            return;
+        }
         // Deactivate the currently active field and activate the new one
+        self.switch_active_field();
+        self.active_field = field;
+        self.switch_active_field();
+    }
+
+    fn switch_active_field(&mut self) {
         match self.active_field {
             InputField::Title => self.title.switch_active(),
             InputField::Description => self.description.switch_active(),
             InputField::Status => self.status.switch_active(),
+            InputField::Project => {
+                if let Some(project) = self.project.as_mut() {
+                    project.switch_active()
+                }
+            }
+            InputField::Reason => {
+                if let Some(reason) = self.reason.as_mut() {
+                    reason.switch_active()
+                }
+            }
         }
+    }
 
-        // Update the active field
-        self.active_field = fields[index].clone();
-
-        // Activate the new field
-        match self.active_field {
-            InputField::Title => self.title.switch_active(),
-            InputField::Description => self.description.switch_active(),
-            InputField::Status => self.status.switch_active(),
+    /// Returns the field rendered at the given terminal cell, if any.
+    fn field_at(&self, column: u16, row: u16) -> Option<InputField> {
+        if self.title.contains(column, row) {
+            Some(InputField::Title)
+        } else if self.description.contains(column, row) {
+            Some(InputField::Description)
+        } else if self.status.contains(column, row) {
+            Some(InputField::Status)
+        } else if self
+            .project
+            .as_ref()
+            .is_some_and(|project| project.contains(column, row))
+        {
+            Some(InputField::Project)
+        } else if self
+            .reason
+            .as_ref()
+            .is_some_and(|reason| reason.contains(column, row))
+        {
+            Some(InputField::Reason)
+        } else {
+            None
+        }
+    }
+    /// Checks the fields and shows an error below each invalid one. Returns whether the form
+    /// is valid.
+    pub fn validate(&mut self) -> bool {
+        self.show_errors = true;
+        let title_error = if self.title.get_input().trim().is_empty() {
+            Some("Title must not be empty".to_string())
+        } else {
+            None
         };
+        // Workflow errors come from the database on submit and are outdated by any change.
+        let status_error = if self.status.selected().is_none() {
+            Some("Select a status".to_string())
+        } else {
+            None
+        };
+        let valid = title_error.is_none() && status_error.is_none();
+        self.title.set_error(title_error);
+        self.set_status_error(status_error);
+        self.set_reason_error(None);
+        valid
     }
 
-    pub fn get_inputs(&self) -> Inputs<T> {
-        Inputs {
-            title: self.title.get_input(),
-            description: self.description.get_input(),
-            status: self.status.selected().unwrap(),
+    /// Returns the inputs if they are valid, showing errors for the invalid fields otherwise.
+    pub fn get_valid_inputs(&mut self) -> Result<Inputs<'_, T>, SubmitError> {
+        if !self.validate() {
+            return Err(SubmitError::Invalid);
         }
+        let status = self.status.selected().ok_or(SubmitError::Invalid)?;
+        Ok(Inputs {
+            title: self.title.get_input().trim(),
+            description: self.description.get_input(),
+            status,
+            project_id: self.project.as_ref().and_then(ProjectPicker::selected_id),
+            reason: self
+                .reason
+                .as_ref()
+                .map(|reason| reason.get_input().trim())
+                .filter(|reason| !reason.is_empty()),
+        })
+    }
+    /// Whether the multi-line description is being edited, where Enter inserts a newline.
+    pub fn is_editing_text(&self) -> bool {
+        self.active_field == InputField::Description
     }
+
     pub fn reset(&mut self) {
         self.title.reset();
         self.description.reset();
         self.status.reset();
-        self.active_field = InputField::Title;
+        if let Some(project) = self.project.as_mut() {
+            project.reset();
+        }
+        if let Some(reason) = self.reason.as_mut() {
+            reason.reset();
+            reason.set_error(None);
+        }
+        self.title.set_error(None);
+        self.show_errors = false;
+        if self.active_field != InputField::Title {
+            self.focus(InputField::Title);
+        }
     }
 
     pub fn set_inputs(&mut self, title: String, description: Option<String>, status: T) {
@@ -104,16 +239,32 @@ where
     T: Into<ListItem<'static>> + Clone + PartialEq + IntoEnumIterator,
 {
     fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.area = area;
         let [text_area, list_area] =
             Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
                 .areas(area);
-        let [title_area, description_area] =
-            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .areas(text_area);
+        let reason_height = if self.reason.is_some() { 3 } else { 0 };
+        let [title_area, description_area, reason_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(reason_height),
+        ])
+        .areas(text_area);
 
         self.title.render(f, title_area);
         self.description.render(f, description_area);
-        self.status.render(f, list_area);
+        if let Some(reason) = self.reason.as_mut() {
+            reason.render(f, reason_area);
+        }
+        match self.project.as_mut() {
+            Some(project) => {
+                let [status_area, project_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(list_area);
+                self.status.render(f, status_area);
+                project.render(f, project_area);
+            }
+            None => self.status.render(f, list_area),
+        }
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) {
@@ -124,7 +275,133 @@ where
                 InputField::Title => self.title.handle_key_events(key),
                 InputField::Description => self.description.handle_key_events(key),
                 InputField::Status => self.status.handle_key_events(key),
+                InputField::Project => {
+                    if let Some(project) = self.project.as_mut() {
+                        project.handle_key_events(key)
+                    }
+                }
+                InputField::Reason => {
+                    if let Some(reason) = self.reason.as_mut() {
+                        reason.handle_key_events(key)
+                    }
+                }
             },
         }
+        if self.show_errors {
+            self.validate();
+        }
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+            if let Some(field) = self.field_at(mouse.column, mouse.row) {
+                self.focus(field);
+            }
+        }
+        match self.active_field {
+            InputField::Title => self.title.handle_mouse_events(mouse),
+            InputField::Description => self.description.handle_mouse_events(mouse),
+            InputField::Status => self.status.handle_mouse_events(mouse),
+            InputField::Project => {
+                if let Some(project) = self.project.as_mut() {
+                    project.handle_mouse_events(mouse)
+                }
+            }
+            InputField::Reason => {
+                if let Some(reason) = self.reason.as_mut() {
+                    reason.handle_mouse_events(mouse)
+                }
+            }
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        match self.active_field {
+            InputField::Title => self.title.handle_paste(text),
+            InputField::Description => self.description.handle_paste(text),
+            InputField::Status => {}
+            InputField::Project => {
+                if let Some(project) = self.project.as_mut() {
+                    project.handle_paste(text)
+                }
+            }
+            InputField::Reason => {
+                if let Some(reason) = self.reason.as_mut() {
+                    reason.handle_paste(text)
+                }
+            }
+        }
+        if self.show_errors {
+            self.validate();
+        }
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task_status::TaskStatus;
+
+    #[test]
+    fn test_blank_title_is_invalid() {
+        let mut inputs = MultiInput::<TaskStatus>::new();
+        inputs.set_inputs("   ".to_string(), None, TaskStatus::Todo);
+
+        assert!(matches!(
+            inputs.get_valid_inputs(),
+            Err(SubmitError::Invalid)
+        ));
+    }
+
+    #[test]
+    fn test_missing_status_is_invalid() {
+        let mut inputs = MultiInput::<TaskStatus>::new();
+        inputs.set_inputs("Title".to_string(), None, TaskStatus::Todo);
+        inputs.status.set_items(Vec::new());
+
+        assert!(matches!(
+            inputs.get_valid_inputs(),
+            Err(SubmitError::Invalid)
+        ));
+    }
+
+    #[test]
+    fn test_typing_fixes_title() {
+        let mut inputs = MultiInput::<TaskStatus>::new();
+        assert!(!inputs.validate());
+
+        inputs.handle_key_events(KeyEvent::from(KeyCode::Char('x')));
+
+        assert_eq!(inputs.get_valid_inputs().unwrap().title, "x");
+    }
+
+    #[test]
+    fn test_title_is_trimmed() {
+        let mut inputs = MultiInput::<TaskStatus>::new();
+        inputs.set_inputs("  Title ".to_string(), None, TaskStatus::Todo);
+
+        assert_eq!(inputs.get_valid_inputs().unwrap().title, "Title");
+    }
+
+    #[test]
+    fn test_reason_field() {
+        let mut inputs = MultiInput::<TaskStatus>::new().with_reason_input();
+        inputs.set_inputs("Title".to_string(), None, TaskStatus::Todo);
+        assert_eq!(inputs.get_valid_inputs().unwrap().reason, None);
+
+        inputs.focus(InputField::Reason);
+        inputs.handle_paste(" Waiting on review ");
+
+        assert_eq!(
+            inputs.get_valid_inputs().unwrap().reason,
+            Some("Waiting on review")
+        );
+        // Tabbing from the reason wraps around to the title.
+        inputs.switch_field(false);
+        assert_eq!(inputs.active_field, InputField::Title);
     }
 }
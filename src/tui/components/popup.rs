@@ -1,29 +1,66 @@
-use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    Frame,
+};
 
 use super::{
-    project_delete::ProjectDelete, project_input::ProjectInput, project_update::ProjectUpdate,
-    task_delete::TaskDelete, task_input::TaskInput, task_update::TaskUpdate, Component,
-    InputSubmit,
+    command_palette::CommandPalette, project_delete::ProjectDelete, project_input::ProjectInput,
+    project_update::ProjectUpdate, tag_filter_picker::TagFilterPicker,
+    task_comment_input::TaskCommentInput, task_delete::TaskDelete, task_input::TaskInput,
+    task_link_picker::TaskLinkPicker, task_quick_add::TaskQuickAdd,
+    task_reason_input::TaskReasonInput, task_tag_input::TaskTagInput, task_update::TaskUpdate,
+    workspace_picker::WorkspacePicker, Component, InputSubmit, SubmitError,
 };
 
 pub enum Popup {
     TaskInput(TaskInput),
     TaskUpdate(TaskUpdate),
     TaskDelete(TaskDelete),
+    TaskTagInput(TaskTagInput),
+    TaskCommentInput(TaskCommentInput),
+    TaskReasonInput(TaskReasonInput),
+    TaskLinkPicker(TaskLinkPicker),
+    TagFilterPicker(TagFilterPicker),
+    TaskQuickAdd(TaskQuickAdd),
     ProjectInput(ProjectInput),
     ProjectUpdate(ProjectUpdate),
     ProjectDelete(ProjectDelete),
+    CommandPalette(CommandPalette),
+    WorkspacePicker(WorkspacePicker),
 }
 
 impl Popup {
-    pub fn submit(&mut self) {
+    /// Whether the popup is editing multi-line text, where Enter inserts a newline instead of
+    /// submitting.
+    pub fn is_editing_text(&self) -> bool {
+        match self {
+            Self::TaskInput(task_input) => task_input.is_editing_text(),
+            Self::TaskUpdate(task_update) => task_update.is_editing_text(),
+            Self::ProjectInput(project_input) => project_input.is_editing_text(),
+            Self::ProjectUpdate(project_update) => project_update.is_editing_text(),
+            _ => false,
+        }
+    }
+
+    pub fn submit(&mut self) -> Result<(), SubmitError> {
         match self {
             Self::TaskInput(task_input) => task_input.submit_and_reset(),
             Self::TaskUpdate(task_update) => task_update.submit_and_reset(),
             Self::TaskDelete(task_delete) => task_delete.submit_and_reset(),
+            Self::TaskTagInput(task_tag_input) => task_tag_input.submit_and_reset(),
+            Self::TaskCommentInput(task_comment_input) => task_comment_input.submit_and_reset(),
+            Self::TaskQuickAdd(task_quick_add) => task_quick_add.submit_and_reset(),
+            Self::TaskReasonInput(task_reason_input) => task_reason_input.submit_and_reset(),
             Self::ProjectInput(project_input) => project_input.submit_and_reset(),
             Self::ProjectUpdate(project_update) => project_update.submit_and_reset(),
             Self::ProjectDelete(project_delete) => project_delete.submit_and_reset(),
+            // The selected command, link or tag is handled by the app, which owns the lists it
+            // acts on and reports what happened.
+            Self::CommandPalette(_)
+            | Self::TaskLinkPicker(_)
+            | Self::TagFilterPicker(_)
+            | Self::WorkspacePicker(_) => Ok(()),
         }
     }
 }
@@ -34,9 +71,17 @@ impl Component for Popup {
             Self::TaskInput(task_input) => task_input.render(f, area),
             Self::TaskUpdate(task_update) => task_update.render(f, area),
             Self::TaskDelete(task_delete) => task_delete.render(f, area),
+            Self::TaskTagInput(task_tag_input) => task_tag_input.render(f, area),
+            Self::TaskCommentInput(task_comment_input) => task_comment_input.render(f, area),
+            Self::TaskLinkPicker(task_link_picker) => task_link_picker.render(f, area),
+            Self::TagFilterPicker(tag_filter_picker) => tag_filter_picker.render(f, area),
+            Self::TaskQuickAdd(task_quick_add) => task_quick_add.render(f, area),
+            Self::TaskReasonInput(task_reason_input) => task_reason_input.render(f, area),
             Self::ProjectInput(project_input) => project_input.render(f, area),
             Self::ProjectUpdate(project_update) => project_update.render(f, area),
             Self::ProjectDelete(project_delete) => project_delete.render(f, area),
+            Self::CommandPalette(command_palette) => command_palette.render(f, area),
+            Self::WorkspacePicker(workspace_picker) => workspace_picker.render(f, area),
         }
     }
 
@@ -45,9 +90,82 @@ impl Component for Popup {
             Popup::TaskInput(task_input) => task_input.handle_key_events(key),
             Popup::TaskUpdate(task_update) => task_update.handle_key_events(key),
             Popup::TaskDelete(task_delete) => task_delete.handle_key_events(key),
+            Popup::TaskTagInput(task_tag_input) => task_tag_input.handle_key_events(key),
+            Popup::TaskCommentInput(task_comment_input) => {
+                task_comment_input.handle_key_events(key)
+            }
+            Popup::TaskLinkPicker(task_link_picker) => task_link_picker.handle_key_events(key),
+            Popup::TagFilterPicker(tag_filter_picker) => tag_filter_picker.handle_key_events(key),
+            Popup::TaskQuickAdd(task_quick_add) => task_quick_add.handle_key_events(key),
+            Popup::TaskReasonInput(task_reason_input) => task_reason_input.handle_key_events(key),
             Popup::ProjectInput(project_input) => project_input.handle_key_events(key),
             Popup::ProjectUpdate(project_update) => project_update.handle_key_events(key),
             Popup::ProjectDelete(project_delete) => project_delete.handle_key_events(key),
+            Popup::CommandPalette(command_palette) => command_palette.handle_key_events(key),
+            Popup::WorkspacePicker(workspace_picker) => workspace_picker.handle_key_events(key),
+        }
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        match self {
+            Popup::TaskInput(task_input) => task_input.handle_mouse_events(mouse),
+            Popup::TaskUpdate(task_update) => task_update.handle_mouse_events(mouse),
+            Popup::TaskDelete(task_delete) => task_delete.handle_mouse_events(mouse),
+            Popup::TaskTagInput(task_tag_input) => task_tag_input.handle_mouse_events(mouse),
+            Popup::TaskCommentInput(task_comment_input) => {
+                task_comment_input.handle_mouse_events(mouse)
+            }
+            Popup::TaskLinkPicker(task_link_picker) => task_link_picker.handle_mouse_events(mouse),
+            Popup::TagFilterPicker(tag_filter_picker) => {
+                tag_filter_picker.handle_mouse_events(mouse)
+            }
+            Popup::TaskQuickAdd(task_quick_add) => task_quick_add.handle_mouse_events(mouse),
+            Popup::TaskReasonInput(task_reason_input) => {
+                task_reason_input.handle_mouse_events(mouse)
+            }
+            Popup::ProjectInput(project_input) => project_input.handle_mouse_events(mouse),
+            Popup::ProjectUpdate(project_update) => project_update.handle_mouse_events(mouse),
+            Popup::ProjectDelete(project_delete) => project_delete.handle_mouse_events(mouse),
+            Popup::CommandPalette(command_palette) => command_palette.handle_mouse_events(mouse),
+            Popup::WorkspacePicker(workspace_picker) => workspace_picker.handle_mouse_events(mouse),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        match self {
+            Popup::TaskInput(task_input) => task_input.handle_paste(text),
+            Popup::TaskUpdate(task_update) => task_update.handle_paste(text),
+            Popup::TaskDelete(task_delete) => task_delete.handle_paste(text),
+            Popup::TaskTagInput(task_tag_input) => task_tag_input.handle_paste(text),
+            Popup::TaskCommentInput(task_comment_input) => task_comment_input.handle_paste(text),
+            Popup::TaskLinkPicker(task_link_picker) => task_link_picker.handle_paste(text),
+            Popup::TagFilterPicker(tag_filter_picker) => tag_filter_picker.handle_paste(text),
+            Popup::TaskQuickAdd(task_quick_add) => task_quick_add.handle_paste(text),
+            Popup::TaskReasonInput(task_reason_input) => task_reason_input.handle_paste(text),
+            Popup::ProjectInput(project_input) => project_input.handle_paste(text),
+            Popup::ProjectUpdate(project_update) => project_update.handle_paste(text),
+            Popup::ProjectDelete(project_delete) => project_delete.handle_paste(text),
+            Popup::CommandPalette(command_palette) => command_palette.handle_paste(text),
+            Popup::WorkspacePicker(workspace_picker) => workspace_picker.handle_paste(text),
+        }
+    }
+
+    fn area(&self) -> Rect {
+        match self {
+            Popup::TaskInput(task_input) => task_input.area(),
+            Popup::TaskUpdate(task_update) => task_update.area(),
+            Popup::TaskDelete(task_delete) => task_delete.area(),
+            Popup::TaskTagInput(task_tag_input) => task_tag_input.area(),
+            Popup::TaskCommentInput(task_comment_input) => task_comment_input.area(),
+            Popup::TaskLinkPicker(task_link_picker) => task_link_picker.area(),
+            Popup::TagFilterPicker(tag_filter_picker) => tag_filter_picker.area(),
+            Popup::TaskQuickAdd(task_quick_add) => task_quick_add.area(),
+            Popup::TaskReasonInput(task_reason_input) => task_reason_input.area(),
+            Popup::ProjectInput(project_input) => project_input.area(),
+            Popup::ProjectUpdate(project_update) => project_update.area(),
+            Popup::ProjectDelete(project_delete) => project_delete.area(),
+            Popup::CommandPalette(command_palette) => command_palette.area(),
+            Popup::WorkspacePicker(workspace_picker) => workspace_picker.area(),
         }
     }
 }
@@ -11,22 +11,29 @@ use ratatui::{
 
 use crate::models::Project;
 
-use super::{Component, InputSubmit};
+use super::{Component, InputSubmit, SubmitError};
 
 pub struct ProjectDelete {
-    project_id: i32,
+    projects: Vec<Project>,
     conn: Rc<RefCell<SqliteConnection>>,
+    area: Rect,
 }
 
 impl ProjectDelete {
-    pub fn new(conn: Rc<RefCell<SqliteConnection>>, project_id: i32) -> Self {
-        Self { conn, project_id }
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>, projects: Vec<Project>) -> Self {
+        Self {
+            conn,
+            projects,
+            area: Rect::ZERO,
+        }
     }
 }
 
 impl InputSubmit for ProjectDelete {
-    fn submit(&self) {
-        Project::delete(&mut self.conn.borrow_mut(), self.project_id).unwrap();
+    fn submit(&mut self) -> Result<(), SubmitError> {
+        let ids: Vec<i32> = self.projects.iter().map(|project| project.id).collect();
+        Project::delete_many(&mut self.conn.borrow_mut(), &ids)?;
+        Ok(())
     }
 
     fn reset(&mut self) {}
@@ -34,18 +41,38 @@ impl InputSubmit for ProjectDelete {
 
 impl Component for ProjectDelete {
     fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
         frame.render_widget(Clear, area);
-        let text = vec![
-            Line::raw("Are you sure you want to delete this project?"),
+        let (question, title) = match self.projects.as_slice() {
+            [project] => (
+                "Are you sure you want to delete this project?".to_string(),
+                format!("Delete Project {}", project.id),
+            ),
+            projects => (
+                format!(
+                    "Are you sure you want to delete these {} projects?",
+                    projects.len()
+                ),
+                format!("Delete {} Projects", projects.len()),
+            ),
+        };
+        let mut text = vec![
+            Line::raw(question),
             Line::raw("This action cannot be undone."),
         ];
-        let paragraph = Paragraph::new(text).block(
-            Block::default()
-                .title(format!("Delete Project {}", self.project_id))
-                .borders(Borders::ALL),
+        text.extend(
+            self.projects
+                .iter()
+                .map(|project| Line::raw(format!("  {}: {}", project.id, project.title))),
         );
+        let paragraph =
+            Paragraph::new(text).block(Block::default().title(title).borders(Borders::ALL));
         frame.render_widget(paragraph, area);
     }
 
     fn handle_key_events(&mut self, _key: KeyEvent) {}
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
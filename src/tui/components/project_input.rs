@@ -1,6 +1,6 @@
 use diesel::SqliteConnection;
 use ratatui::{
-    crossterm::event::KeyEvent,
+    crossterm::event::{KeyEvent, MouseEvent},
     layout::Rect,
     style::{Modifier, Style},
     widgets::{Block, Borders},
@@ -13,12 +13,13 @@ use crate::models::{project_status::ProjectStatus, Project};
 
 use super::{
     multi_input::{Inputs, MultiInput},
-    Component, InputSubmit,
+    Component, InputSubmit, SubmitError,
 };
 
 pub struct ProjectInput {
     conn: Rc<RefCell<SqliteConnection>>,
     inputs: MultiInput<ProjectStatus>,
+    area: Rect,
 }
 
 impl ProjectInput {
@@ -26,24 +27,30 @@ impl ProjectInput {
         Self {
             conn,
             inputs: MultiInput::new(),
+            area: Rect::ZERO,
         }
     }
+
+    pub fn is_editing_text(&self) -> bool {
+        self.inputs.is_editing_text()
+    }
 }
 
 impl InputSubmit for ProjectInput {
-    fn submit(&self) {
+    fn submit(&mut self) -> Result<(), SubmitError> {
         let Inputs {
             title,
             description,
             status,
-        } = self.inputs.get_inputs();
+            ..
+        } = self.inputs.get_valid_inputs()?;
         Project::create(
             &mut self.conn.borrow_mut(),
             Some(title),
             Some(description),
             Some(*status),
-        )
-        .unwrap();
+        )?;
+        Ok(())
     }
 
     fn reset(&mut self) {
@@ -58,6 +65,7 @@ impl Component for ProjectInput {
             .title("Project Creation") // Optional: Add a title to the border
             .style(Style::default().add_modifier(Modifier::BOLD)); // Add styles if needed
 
+        self.area = area;
         let inner_area = block.inner(area);
         f.render_widget(block, area);
         self.inputs.render(f, inner_area);
@@ -66,4 +74,16 @@ impl Component for ProjectInput {
     fn handle_key_events(&mut self, key: KeyEvent) {
         self.inputs.handle_key_events(key);
     }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.inputs.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.inputs.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
@@ -1,13 +1,13 @@
 use std::{cell::RefCell, rc::Rc};
 
-use diesel::SqliteConnection;
+use diesel::{result::Error, SqliteConnection};
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     layout::Rect,
     Frame,
 };
 
-use crate::models::Project;
+use crate::models::{project_status::ProjectStatus, Project, ProjectRollup};
 
 use super::{
     list_selection::ListSelection, popup::Popup, project_delete::ProjectDelete,
@@ -21,45 +21,79 @@ pub struct ProjectList {
 }
 
 impl ProjectList {
-    pub fn new(conn: Rc<RefCell<SqliteConnection>>) -> Self {
-        let projects = Project::list(&mut conn.borrow_mut()).unwrap();
-        Self {
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>) -> Result<Self, Error> {
+        let projects = Project::list(&mut conn.borrow_mut())?;
+        Ok(Self {
             conn: Rc::clone(&conn),
-            projects: ListSelection::new(projects, "Projects"),
+            projects: ListSelection::new(projects, "Projects").multi_select(true),
             popup: None,
-        }
+        })
     }
     pub fn get_selected(&self) -> Option<&Project> {
         self.projects.selected()
     }
 
+    pub fn open_input(&mut self) {
+        self.popup = Some(Popup::ProjectInput(ProjectInput::new(Rc::clone(
+            &self.conn,
+        ))));
+    }
+
+    pub fn open_update(&mut self) {
+        if let Some(selected_project) = self.projects.selected() {
+            self.popup = Some(Popup::ProjectUpdate(ProjectUpdate::from_project(
+                Rc::clone(&self.conn),
+                selected_project,
+            )));
+        }
+    }
+
+    pub fn open_delete(&mut self) {
+        let targets: Vec<Project> = self.projects.targets().into_iter().cloned().collect();
+        if !targets.is_empty() {
+            self.popup = Some(Popup::ProjectDelete(ProjectDelete::new(
+                Rc::clone(&self.conn),
+                targets,
+            )))
+        }
+    }
+
+    pub fn set_status(&mut self, status: ProjectStatus) -> Result<(), Error> {
+        let ids: Vec<i32> = self.projects.targets().iter().map(|p| p.id).collect();
+        Project::update_many(&mut self.conn.borrow_mut(), &ids, status)?;
+        Ok(())
+    }
+
+    /// Sums up the tasks of the selected project.
+    pub fn get_selected_rollup(&self) -> Option<ProjectRollup> {
+        let project = self.projects.selected()?;
+        Project::rollup(&mut self.conn.borrow_mut(), project.id).ok()
+    }
+
+    pub fn get_all(&self) -> &[Project] {
+        self.projects.get_items()
+    }
+
     fn handle_list_key_events(&mut self, key: KeyEvent) {
+        // Control chords such as Ctrl-d and Ctrl-u are list motions, not actions.
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return self.projects.handle_key_events(key);
+        }
+        if let KeyCode::Char('a' | 'u' | 'd') = key.code {
+            // A count or `g` typed before an action doesn't carry over to the next motion.
+            self.projects.clear_pending();
+        }
         match key.code {
-            KeyCode::Char('a') => {
-                self.popup = Some(Popup::ProjectInput(ProjectInput::new(Rc::clone(
-                    &self.conn,
-                ))));
-            }
-            KeyCode::Char('u') => {
-                if let Some(selected_project) = self.projects.selected() {
-                    self.popup = Some(Popup::ProjectUpdate(ProjectUpdate::from_project(
-                        Rc::clone(&self.conn),
-                        selected_project,
-                    )));
-                }
-            }
-            KeyCode::Char('d') => {
-                self.popup = Some(Popup::ProjectDelete(ProjectDelete::new(
-                    Rc::clone(&self.conn),
-                    self.projects.selected().unwrap().id,
-                )))
-            }
+            KeyCode::Char('a') => self.open_input(),
+            KeyCode::Char('u') => self.open_update(),
+            KeyCode::Char('d') => self.open_delete(),
             _ => self.projects.handle_key_events(key),
         }
     }
-    pub fn refresh(&mut self) {
-        self.projects
-            .set_items(Project::list(&mut self.conn.borrow_mut()).unwrap());
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        let projects = Project::list(&mut self.conn.borrow_mut())?;
+        self.projects.set_items(projects);
+        Ok(())
     }
     pub fn switch_active(&mut self) {
         self.projects.switch_active();
@@ -74,4 +108,19 @@ impl Component for ProjectList {
     fn handle_key_events(&mut self, key: KeyEvent) {
         self.handle_list_key_events(key)
     }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.projects.click(mouse.column, mouse.row) {
+                    self.open_update();
+                }
+            }
+            _ => self.projects.handle_mouse_events(mouse),
+        }
+    }
+
+    fn area(&self) -> Rect {
+        self.projects.area()
+    }
 }
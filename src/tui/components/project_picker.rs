@@ -0,0 +1,206 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent},
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::models::Project;
+
+use super::{list_selection::ListSelection, Component};
+
+/// An entry of the project picker: a project, or no project at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectChoice(Option<Project>);
+
+impl ProjectChoice {
+    fn id(&self) -> Option<i32> {
+        self.0.as_ref().map(|project| project.id)
+    }
+}
+
+impl<'a> From<ProjectChoice> for ListItem<'a> {
+    fn from(choice: ProjectChoice) -> Self {
+        match choice.0 {
+            Some(project) => project.into(),
+            None => ListItem::new("No project"),
+        }
+    }
+}
+
+/// Picks the project of a task. Typing filters the projects by title, the arrow keys move the
+/// selection.
+pub struct ProjectPicker {
+    projects: Vec<Project>,
+    filter: String,
+    choices: ListSelection<ProjectChoice>,
+    /// The project selected when the picker is created or reset.
+    default_id: Option<i32>,
+}
+
+impl ProjectPicker {
+    pub fn new(projects: Vec<Project>, default_id: Option<i32>) -> Self {
+        let mut picker = Self {
+            projects,
+            filter: String::new(),
+            choices: ListSelection::new(Vec::new(), "Project").wrap_around(false),
+            default_id,
+        };
+        picker.reset();
+        picker
+    }
+
+    pub fn switch_active(&mut self) {
+        self.choices.switch_active();
+    }
+
+    /// The id of the selected project, or `None` if "No project" is selected.
+    pub fn selected_id(&self) -> Option<i32> {
+        self.choices.selected().and_then(ProjectChoice::id)
+    }
+
+    pub fn set_selected_id(&mut self, id: Option<i32>) {
+        let choice = self
+            .choices
+            .get_items()
+            .iter()
+            .find(|choice| choice.id() == id)
+            .cloned();
+        if let Some(choice) = choice {
+            self.choices.set_selected(choice);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.filter.clear();
+        self.apply_filter();
+        self.set_selected_id(self.default_id);
+    }
+
+    /// Shows "No project" and the projects whose title contains the filter.
+    fn apply_filter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        let mut choices = vec![ProjectChoice(None)];
+        choices.extend(
+            self.projects
+                .iter()
+                .filter(|project| project.title.to_lowercase().contains(&filter))
+                .cloned()
+                .map(|project| ProjectChoice(Some(project))),
+        );
+        // While filtering, select the best match rather than "No project".
+        let first_match = usize::from(!filter.is_empty() && choices.len() > 1);
+        self.choices.set_items(choices);
+        for _ in 0..first_match {
+            self.choices.select_next();
+        }
+    }
+}
+
+impl Component for ProjectPicker {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.choices.render(f, area);
+        if !self.filter.is_empty() && area.height > 2 && area.width > 2 {
+            let filter_area = Rect {
+                x: area.x + 1,
+                y: area.bottom() - 1,
+                width: area.width - 2,
+                height: 1,
+            };
+            let filter = Line::styled(
+                format!("filter: {}", self.filter),
+                Style::default().fg(Color::Yellow),
+            );
+            f.render_widget(filter, filter_area);
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char(c) if !ctrl => {
+                self.filter.push(c);
+                self.apply_filter();
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.apply_filter();
+            }
+            _ => self.choices.handle_key_events(key),
+        }
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.choices.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.filter.push_str(text.trim());
+        self.apply_filter();
+    }
+
+    fn area(&self) -> Rect {
+        self.choices.area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::project_status::ProjectStatus;
+    use crate::models::slugify;
+
+    fn project(id: i32, title: &str) -> Project {
+        Project {
+            id,
+            title: title.to_string(),
+            description: None,
+            status: ProjectStatus::Active,
+            slug: slugify(title),
+        }
+    }
+
+    fn picker(default_id: Option<i32>) -> ProjectPicker {
+        ProjectPicker::new(vec![project(1, "Garden"), project(2, "Garage")], default_id)
+    }
+
+    fn type_filter(picker: &mut ProjectPicker, filter: &str) {
+        for c in filter.chars() {
+            picker.handle_key_events(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_default_selection() {
+        assert_eq!(picker(None).selected_id(), None);
+        assert_eq!(picker(Some(2)).selected_id(), Some(2));
+    }
+
+    #[test]
+    fn test_filter_selects_first_match() {
+        let mut picker = picker(None);
+        type_filter(&mut picker, "rag");
+        assert_eq!(picker.selected_id(), Some(2));
+
+        // "No project" stays available while filtering.
+        picker.handle_key_events(KeyEvent::from(KeyCode::Up));
+        assert_eq!(picker.selected_id(), None);
+
+        type_filter(&mut picker, "xyz");
+        assert_eq!(picker.selected_id(), None);
+    }
+
+    #[test]
+    fn test_backspace_and_reset_clear_filter() {
+        let mut picker = picker(Some(1));
+        type_filter(&mut picker, "gard");
+        picker.handle_key_events(KeyEvent::from(KeyCode::Backspace));
+        assert_eq!(picker.choices.get_items().len(), 3);
+
+        picker.reset();
+        assert_eq!(picker.filter, "");
+        assert_eq!(picker.selected_id(), Some(1));
+    }
+}
@@ -2,7 +2,7 @@ use std::{cell::RefCell, rc::Rc};
 
 use diesel::SqliteConnection;
 use ratatui::{
-    crossterm::event::KeyEvent,
+    crossterm::event::{KeyEvent, MouseEvent},
     layout::Rect,
     style::{Modifier, Style},
     widgets::{Block, Borders},
@@ -13,13 +13,14 @@ use crate::models::{project_status::ProjectStatus, Project};
 
 use super::{
     multi_input::{Inputs, MultiInput},
-    Component, InputSubmit,
+    Component, InputSubmit, SubmitError,
 };
 
 pub struct ProjectUpdate {
     inputs: MultiInput<ProjectStatus>,
     conn: Rc<RefCell<SqliteConnection>>,
     project_id: i32,
+    area: Rect,
 }
 
 impl ProjectUpdate {
@@ -34,6 +35,7 @@ impl ProjectUpdate {
             conn,
             project_id,
             inputs: MultiInput::new(),
+            area: Rect::ZERO,
         };
         update.inputs.set_inputs(title, description, status);
         update
@@ -47,15 +49,20 @@ impl ProjectUpdate {
             project.status,
         )
     }
+
+    pub fn is_editing_text(&self) -> bool {
+        self.inputs.is_editing_text()
+    }
 }
 
 impl InputSubmit for ProjectUpdate {
-    fn submit(&self) {
+    fn submit(&mut self) -> Result<(), SubmitError> {
         let Inputs {
             title,
             description,
             status,
-        } = self.inputs.get_inputs();
+            ..
+        } = self.inputs.get_valid_inputs()?;
 
         Project::update(
             &mut self.conn.borrow_mut(),
@@ -63,8 +70,8 @@ impl InputSubmit for ProjectUpdate {
             Some(title),
             Some(description),
             Some(*status),
-        )
-        .unwrap();
+        )?;
+        Ok(())
     }
 
     fn reset(&mut self) {
@@ -79,6 +86,7 @@ impl Component for ProjectUpdate {
             .title(format!("Project Update for project {}", self.project_id)) // Optional: Add a title to the border
             .style(Style::default().add_modifier(Modifier::BOLD)); // Add styles if needed
 
+        self.area = area;
         let inner_area = block.inner(area);
         f.render_widget(block, area);
         self.inputs.render(f, inner_area);
@@ -86,4 +94,16 @@ impl Component for ProjectUpdate {
     fn handle_key_events(&mut self, key: KeyEvent) {
         self.inputs.handle_key_events(key);
     }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.inputs.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.inputs.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
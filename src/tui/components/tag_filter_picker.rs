@@ -0,0 +1,65 @@
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    widgets::ListItem,
+    Frame,
+};
+
+use super::{list_selection::ListSelection, Component};
+
+/// An entry of the tag filter picker: a tag, or `None` to show all tasks again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagChoice(pub Option<String>);
+
+impl<'a> From<TagChoice> for ListItem<'a> {
+    fn from(choice: TagChoice) -> Self {
+        match choice.0 {
+            Some(tag) => ListItem::new(tag),
+            None => ListItem::new("All tasks"),
+        }
+    }
+}
+
+/// Picks the tag the task list is narrowed to, starting at the current one.
+pub struct TagFilterPicker {
+    choices: ListSelection<TagChoice>,
+}
+
+impl TagFilterPicker {
+    pub fn new(tags: Vec<String>, current: Option<&str>) -> Self {
+        let current = TagChoice(current.map(str::to_string));
+        let choices: Vec<TagChoice> = std::iter::once(TagChoice(None))
+            .chain(tags.into_iter().map(|tag| TagChoice(Some(tag))))
+            .collect();
+        // The current tag is gone once no task has it anymore.
+        let has_current = choices.contains(&current);
+        let mut choices = ListSelection::new(choices, "Filter by tag").wrap_around(false);
+        if has_current {
+            choices.set_selected(current);
+        }
+        choices.switch_active();
+        Self { choices }
+    }
+
+    pub fn selected(&self) -> Option<&TagChoice> {
+        self.choices.selected()
+    }
+}
+
+impl Component for TagFilterPicker {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.choices.render(f, area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.choices.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.choices.handle_mouse_events(mouse);
+    }
+
+    fn area(&self) -> Rect {
+        self.choices.area()
+    }
+}
@@ -0,0 +1,75 @@
+use std::{cell::RefCell, rc::Rc};
+
+use diesel::SqliteConnection;
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    Frame,
+};
+
+use crate::models::TaskComment;
+
+use super::{user_input::UserInput, Component, InputSubmit, SubmitError};
+
+/// Popup for commenting on a task.
+pub struct TaskCommentInput {
+    conn: Rc<RefCell<SqliteConnection>>,
+    task_id: i32,
+    comment: UserInput,
+}
+
+impl TaskCommentInput {
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>, task_id: i32) -> Self {
+        Self {
+            conn,
+            task_id,
+            comment: UserInput::new(format!("Comment on task {}", task_id), true),
+        }
+    }
+}
+
+impl InputSubmit for TaskCommentInput {
+    fn submit(&mut self) -> Result<(), SubmitError> {
+        let comment = self.comment.get_input().trim();
+        if comment.is_empty() {
+            self.comment
+                .set_error(Some("Comment must not be empty".to_string()));
+            return Err(SubmitError::Invalid);
+        }
+        TaskComment::add(&mut self.conn.borrow_mut(), self.task_id, comment)?;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.comment.reset();
+        self.comment.set_error(None);
+    }
+}
+
+impl Component for TaskCommentInput {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        // A single line of input only needs the top of the popup area.
+        let input_area = Rect {
+            height: area.height.min(3),
+            ..area
+        };
+        self.comment.render(f, input_area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.comment.set_error(None);
+        self.comment.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.comment.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.comment.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.comment.area()
+    }
+}
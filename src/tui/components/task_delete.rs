@@ -11,22 +11,29 @@ use ratatui::{
 
 use crate::models::Task;
 
-use super::{Component, InputSubmit};
+use super::{Component, InputSubmit, SubmitError};
 
 pub struct TaskDelete {
-    task_id: i32,
+    tasks: Vec<Task>,
     conn: Rc<RefCell<SqliteConnection>>,
+    area: Rect,
 }
 
 impl TaskDelete {
-    pub fn new(conn: Rc<RefCell<SqliteConnection>>, task_id: i32) -> Self {
-        Self { conn, task_id }
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>, tasks: Vec<Task>) -> Self {
+        Self {
+            conn,
+            tasks,
+            area: Rect::ZERO,
+        }
     }
 }
 
 impl InputSubmit for TaskDelete {
-    fn submit(&self) {
-        Task::delete(&mut self.conn.borrow_mut(), self.task_id).unwrap();
+    fn submit(&mut self) -> Result<(), SubmitError> {
+        let ids: Vec<i32> = self.tasks.iter().map(|task| task.id).collect();
+        Task::delete_many(&mut self.conn.borrow_mut(), &ids)?;
+        Ok(())
     }
 
     fn reset(&mut self) {}
@@ -34,18 +41,38 @@ impl InputSubmit for TaskDelete {
 
 impl Component for TaskDelete {
     fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
         frame.render_widget(Clear, area);
-        let text = vec![
-            Line::raw("Are you sure you want to delete this task?"),
+        let (question, title) = match self.tasks.as_slice() {
+            [task] => (
+                "Are you sure you want to delete this task?".to_string(),
+                format!("Delete Task {}", task.id),
+            ),
+            tasks => (
+                format!(
+                    "Are you sure you want to delete these {} tasks?",
+                    tasks.len()
+                ),
+                format!("Delete {} Tasks", tasks.len()),
+            ),
+        };
+        let mut text = vec![
+            Line::raw(question),
             Line::raw("This action cannot be undone."),
         ];
-        let paragraph = Paragraph::new(text).block(
-            Block::default()
-                .title(format!("Delete Task {}", self.task_id))
-                .borders(Borders::ALL),
+        text.extend(
+            self.tasks
+                .iter()
+                .map(|task| Line::raw(format!("  {}: {}", task.id, task.title))),
         );
+        let paragraph =
+            Paragraph::new(text).block(Block::default().title(title).borders(Borders::ALL));
         frame.render_widget(paragraph, area);
     }
 
     fn handle_key_events(&mut self, _key: KeyEvent) {}
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
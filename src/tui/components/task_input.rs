@@ -1,6 +1,6 @@
 use diesel::SqliteConnection;
 use ratatui::{
-    crossterm::event::KeyEvent,
+    crossterm::event::{KeyEvent, MouseEvent},
     layout::Rect,
     style::{Modifier, Style},
     widgets::{Block, Borders},
@@ -9,42 +9,53 @@ use ratatui::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::models::{task_status::TaskStatus, Task};
+use crate::models::{task_status::TaskStatus, Project, Task};
 
 use super::{
     multi_input::{Inputs, MultiInput},
-    Component, InputSubmit,
+    project_picker::ProjectPicker,
+    Component, InputSubmit, SubmitError,
 };
 
 pub struct TaskInput {
     conn: Rc<RefCell<SqliteConnection>>,
     inputs: MultiInput<TaskStatus>,
+    area: Rect,
 }
 
 impl TaskInput {
-    pub fn new(conn: Rc<RefCell<SqliteConnection>>) -> Self {
+    /// Creates the form with the project picker defaulting to `project_id`.
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>, project_id: Option<i32>) -> Self {
+        let projects = Project::list(&mut conn.borrow_mut()).unwrap_or_default();
         Self {
             conn,
-            inputs: MultiInput::new(),
+            inputs: MultiInput::new().with_project_picker(ProjectPicker::new(projects, project_id)),
+            area: Rect::ZERO,
         }
     }
+
+    pub fn is_editing_text(&self) -> bool {
+        self.inputs.is_editing_text()
+    }
 }
 
 impl InputSubmit for TaskInput {
-    fn submit(&self) {
+    fn submit(&mut self) -> Result<(), SubmitError> {
         let Inputs {
             title,
             description,
             status,
-        } = self.inputs.get_inputs();
+            project_id,
+            ..
+        } = self.inputs.get_valid_inputs()?;
         Task::create(
             &mut self.conn.borrow_mut(),
             Some(title),
             Some(description),
             Some(*status),
-            None,
-        )
-        .unwrap();
+            project_id,
+        )?;
+        Ok(())
     }
 
     fn reset(&mut self) {
@@ -59,6 +70,7 @@ impl Component for TaskInput {
             .title("Task Creation") // Optional: Add a title to the border
             .style(Style::default().add_modifier(Modifier::BOLD)); // Add styles if needed
 
+        self.area = area;
         let inner_area = block.inner(area);
         f.render_widget(block, area);
         self.inputs.render(f, inner_area);
@@ -67,4 +79,16 @@ impl Component for TaskInput {
     fn handle_key_events(&mut self, key: KeyEvent) {
         self.inputs.handle_key_events(key);
     }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.inputs.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.inputs.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
@@ -0,0 +1,44 @@
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    Frame,
+};
+
+use crate::models::Link;
+
+use super::{list_selection::ListSelection, Component};
+
+/// Picks which link of a task to open, for tasks with more than one.
+pub struct TaskLinkPicker {
+    links: ListSelection<Link>,
+}
+
+impl TaskLinkPicker {
+    pub fn new(links: Vec<Link>) -> Self {
+        let mut links = ListSelection::new(links, "Open link").wrap_around(false);
+        links.switch_active();
+        Self { links }
+    }
+
+    pub fn selected(&self) -> Option<&Link> {
+        self.links.selected()
+    }
+}
+
+impl Component for TaskLinkPicker {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.links.render(f, area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.links.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.links.handle_mouse_events(mouse);
+    }
+
+    fn area(&self) -> Rect {
+        self.links.area()
+    }
+}
@@ -2,62 +2,231 @@ use std::{cell::RefCell, rc::Rc};
 
 use diesel::SqliteConnection;
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     layout::Rect,
     Frame,
 };
 
-use crate::models::Task;
+use crate::config;
+use crate::models::{
+    task_status::TaskStatus, Link, ProjectLookupError, Task, TaskActivity, TaskDependency,
+    TaskFilter, TaskTag, TaskUpdateError,
+};
 
 use super::{
-    list_selection::ListSelection, popup::Popup, task_delete::TaskDelete, task_input::TaskInput,
-    task_update::TaskUpdate, Component,
+    list_selection::ListSelection, popup::Popup, task_comment_input::TaskCommentInput,
+    task_delete::TaskDelete, task_input::TaskInput, task_quick_add::TaskQuickAdd,
+    task_reason_input::TaskReasonInput, task_tag_input::TaskTagInput, task_update::TaskUpdate,
+    Component,
 };
 
 pub struct TaskList {
     conn: Rc<RefCell<SqliteConnection>>,
     tasks: ListSelection<Task>,
     pub popup: Option<Popup>,
+    /// The project new tasks are assigned to by default.
+    default_project_id: Option<i32>,
+    /// Only tasks with this tag are listed.
+    tag_filter: Option<String>,
 }
 
 impl TaskList {
-    pub fn new(conn: Rc<RefCell<SqliteConnection>>) -> Self {
-        let tasks = Task::list(&mut conn.borrow_mut()).unwrap();
-        Self {
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>) -> Result<Self, ProjectLookupError> {
+        let tasks = Task::list(&mut conn.borrow_mut())?;
+        Ok(Self {
             conn: Rc::clone(&conn),
-            tasks: ListSelection::new(tasks, "Tasks"),
+            tasks: ListSelection::new(tasks, "Tasks").multi_select(true),
             popup: None,
-        }
+            default_project_id: None,
+            tag_filter: None,
+        })
     }
     pub fn get_selected(&self) -> Option<&Task> {
         self.tasks.selected()
     }
 
-    fn handle_list_key_events(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('a') => {
-                self.popup = Some(Popup::TaskInput(TaskInput::new(Rc::clone(&self.conn))));
+    pub fn get_all(&self) -> &[Task] {
+        self.tasks.get_items()
+    }
+
+    pub fn set_default_project(&mut self, project_id: Option<i32>) {
+        self.default_project_id = project_id;
+    }
+
+    pub fn tag_filter(&self) -> Option<&str> {
+        self.tag_filter.as_deref()
+    }
+
+    /// Narrows the list to the tasks with the tag, or lists all tasks again for `None`.
+    pub fn set_tag_filter(&mut self, tag: Option<String>) -> Result<(), ProjectLookupError> {
+        self.tag_filter = tag;
+        self.refresh()
+    }
+
+    pub fn open_input(&mut self) {
+        self.popup = Some(Popup::TaskInput(TaskInput::new(
+            Rc::clone(&self.conn),
+            self.default_project_id,
+        )));
+    }
+
+    pub fn open_quick_add(&mut self) {
+        self.popup = Some(Popup::TaskQuickAdd(TaskQuickAdd::new(Rc::clone(
+            &self.conn,
+        ))));
+    }
+
+    pub fn open_update(&mut self) {
+        if let Some(selected_task) = self.tasks.selected() {
+            self.popup = Some(Popup::TaskUpdate(TaskUpdate::from_task(
+                Rc::clone(&self.conn),
+                selected_task,
+            )));
+        }
+    }
+
+    pub fn open_delete(&mut self) {
+        let targets: Vec<Task> = self.tasks.targets().into_iter().cloned().collect();
+        if !targets.is_empty() {
+            self.popup = Some(Popup::TaskDelete(TaskDelete::new(
+                Rc::clone(&self.conn),
+                targets,
+            )))
+        }
+    }
+
+    pub fn open_tag(&mut self) {
+        let ids = self.target_ids();
+        if !ids.is_empty() {
+            self.popup = Some(Popup::TaskTagInput(TaskTagInput::new(
+                Rc::clone(&self.conn),
+                ids,
+            )));
+        }
+    }
+
+    pub fn open_comment(&mut self) {
+        if let Some(selected_task) = self.tasks.selected() {
+            self.popup = Some(Popup::TaskCommentInput(TaskCommentInput::new(
+                Rc::clone(&self.conn),
+                selected_task.id,
+            )));
+        }
+    }
+
+    /// Ids of the marked tasks, or of the selected task if none are marked.
+    fn target_ids(&self) -> Vec<i32> {
+        self.tasks.targets().iter().map(|task| task.id).collect()
+    }
+
+    /// Moves the marked or selected tasks to `status`, asking for a reason first if the
+    /// workflow requires one.
+    pub fn set_status(&mut self, status: TaskStatus) -> Result<(), TaskUpdateError> {
+        let ids = self.target_ids();
+        let changes = self
+            .tasks
+            .targets()
+            .iter()
+            .any(|task| task.status != status);
+        if changes && config::get().workflow.requires_reason(status) {
+            self.popup = Some(Popup::TaskReasonInput(TaskReasonInput::new(
+                Rc::clone(&self.conn),
+                ids,
+                status,
+            )));
+            return Ok(());
+        }
+        Task::update_many(
+            &mut self.conn.borrow_mut(),
+            &ids,
+            None,
+            None,
+            Some(status),
+            None,
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn move_to_project(&mut self, project_id: i32) -> Result<(), TaskUpdateError> {
+        let ids = self.target_ids();
+        Task::update_many(
+            &mut self.conn.borrow_mut(),
+            &ids,
+            None,
+            None,
+            None,
+            Some(project_id),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn get_selected_tags(&self) -> Vec<String> {
+        match self.tasks.selected() {
+            Some(task) => {
+                TaskTag::list_for_task(&mut self.conn.borrow_mut(), task.id).unwrap_or_default()
             }
-            KeyCode::Char('u') => {
-                if let Some(selected_task) = self.tasks.selected() {
-                    self.popup = Some(Popup::TaskUpdate(TaskUpdate::from_task(
-                        Rc::clone(&self.conn),
-                        selected_task,
-                    )));
-                }
+            None => Vec::new(),
+        }
+    }
+
+    /// The tasks the selected task waits for.
+    pub fn get_selected_blockers(&self) -> Vec<Task> {
+        match self.tasks.selected() {
+            Some(task) => {
+                TaskDependency::blockers(&mut self.conn.borrow_mut(), task.id).unwrap_or_default()
             }
-            KeyCode::Char('d') => {
-                self.popup = Some(Popup::TaskDelete(TaskDelete::new(
-                    Rc::clone(&self.conn),
-                    self.tasks.selected().unwrap().id,
-                )))
+            None => Vec::new(),
+        }
+    }
+
+    /// Links added to the selected task, followed by the ones in its description.
+    pub fn get_selected_links(&self) -> Vec<Link> {
+        match self.tasks.selected() {
+            Some(task) => {
+                Link::list_for_task(&mut self.conn.borrow_mut(), task.id).unwrap_or_default()
             }
+            None => Vec::new(),
+        }
+    }
+
+    /// Comments, status changes and commits of the selected task, oldest first.
+    pub fn get_selected_activity(&self) -> Vec<TaskActivity> {
+        match self.tasks.selected() {
+            Some(task) => TaskActivity::list_for_task(&mut self.conn.borrow_mut(), task.id)
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_list_key_events(&mut self, key: KeyEvent) {
+        // Control chords such as Ctrl-d and Ctrl-u are list motions, not actions.
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return self.tasks.handle_key_events(key);
+        }
+        if let KeyCode::Char('a' | 'n' | 'u' | 'd' | 't' | 'c') = key.code {
+            // A count or `g` typed before an action doesn't carry over to the next motion.
+            self.tasks.clear_pending();
+        }
+        match key.code {
+            KeyCode::Char('a') => self.open_input(),
+            KeyCode::Char('n') => self.open_quick_add(),
+            KeyCode::Char('u') => self.open_update(),
+            KeyCode::Char('d') => self.open_delete(),
+            KeyCode::Char('t') => self.open_tag(),
+            KeyCode::Char('c') => self.open_comment(),
             _ => self.tasks.handle_key_events(key),
         }
     }
-    pub fn refresh(&mut self) {
-        self.tasks
-            .set_items(Task::list(&mut self.conn.borrow_mut()).unwrap());
+    pub fn refresh(&mut self) -> Result<(), ProjectLookupError> {
+        let filter = TaskFilter {
+            tag: self.tag_filter.clone(),
+            ..TaskFilter::default()
+        };
+        let tasks = Task::list_filtered(&mut self.conn.borrow_mut(), &filter)?;
+        self.tasks.set_items(tasks);
+        Ok(())
     }
     pub fn switch_active(&mut self) {
         self.tasks.switch_active();
@@ -72,4 +241,19 @@ impl Component for TaskList {
     fn handle_key_events(&mut self, key: KeyEvent) {
         self.handle_list_key_events(key)
     }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.tasks.click(mouse.column, mouse.row) {
+                    self.open_update();
+                }
+            }
+            _ => self.tasks.handle_mouse_events(mouse),
+        }
+    }
+
+    fn area(&self) -> Rect {
+        self.tasks.area()
+    }
 }
@@ -0,0 +1,86 @@
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::Local;
+use diesel::SqliteConnection;
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    Frame,
+};
+
+use crate::models::{QuickAdd, QuickAddError, Task};
+
+use super::{user_input::UserInput, Component, InputSubmit, SubmitError};
+
+/// One-line popup for adding a task in quick-add syntax, such as
+/// `Fix login bug +backend !high due:fri @project-name`.
+pub struct TaskQuickAdd {
+    conn: Rc<RefCell<SqliteConnection>>,
+    input: UserInput,
+}
+
+impl TaskQuickAdd {
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>) -> Self {
+        Self {
+            conn,
+            input: UserInput::new(
+                "Quick add: title +tag !priority due:date @project".to_string(),
+                true,
+            ),
+        }
+    }
+
+    fn invalid(&mut self, error: String) -> SubmitError {
+        self.input.set_error(Some(error));
+        SubmitError::Invalid
+    }
+}
+
+impl InputSubmit for TaskQuickAdd {
+    fn submit(&mut self) -> Result<(), SubmitError> {
+        let today = Local::now().date_naive();
+        let quick_add = match QuickAdd::parse(self.input.get_input(), today) {
+            Ok(quick_add) => quick_add,
+            Err(error) => return Err(self.invalid(error)),
+        };
+        let result = Task::quick_add(&mut self.conn.borrow_mut(), &quick_add);
+        match result {
+            Ok(_) => Ok(()),
+            Err(QuickAddError::Database(error)) => Err(SubmitError::Database(error)),
+            Err(error) => Err(self.invalid(error.to_string())),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.input.reset();
+        self.input.set_error(None);
+    }
+}
+
+impl Component for TaskQuickAdd {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        // A single line of input only needs the top of the popup area.
+        let input_area = Rect {
+            height: area.height.min(3),
+            ..area
+        };
+        self.input.render(f, input_area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.input.set_error(None);
+        self.input.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.input.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.input.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.input.area()
+    }
+}
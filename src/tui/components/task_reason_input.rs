@@ -0,0 +1,125 @@
+use std::{cell::RefCell, rc::Rc};
+
+use diesel::SqliteConnection;
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    Frame,
+};
+
+use crate::models::{task_status::TaskStatus, Task, TaskUpdateError};
+
+use super::{user_input::UserInput, Component, InputSubmit, SubmitError};
+
+/// Popup asking why one or more tasks move to a status the workflow wants a reason for.
+pub struct TaskReasonInput {
+    conn: Rc<RefCell<SqliteConnection>>,
+    task_ids: Vec<i32>,
+    status: TaskStatus,
+    reason: UserInput,
+}
+
+impl TaskReasonInput {
+    pub fn new(
+        conn: Rc<RefCell<SqliteConnection>>,
+        task_ids: Vec<i32>,
+        status: TaskStatus,
+    ) -> Self {
+        let title = match task_ids.as_slice() {
+            [task_id] => format!("Why does task {} move to {}?", task_id, status),
+            task_ids => format!("Why do {} tasks move to {}?", task_ids.len(), status),
+        };
+        Self {
+            conn,
+            task_ids,
+            status,
+            reason: UserInput::new(title, true),
+        }
+    }
+}
+
+impl InputSubmit for TaskReasonInput {
+    fn submit(&mut self) -> Result<(), SubmitError> {
+        let reason = self.reason.get_input().trim();
+        if reason.is_empty() {
+            self.reason
+                .set_error(Some("Reason must not be empty".to_string()));
+            return Err(SubmitError::Invalid);
+        }
+        let result = Task::update_many(
+            &mut self.conn.borrow_mut(),
+            &self.task_ids,
+            None,
+            None,
+            Some(self.status),
+            None,
+            Some(reason),
+        );
+        match result {
+            Ok(_) => Ok(()),
+            Err(TaskUpdateError::Workflow(error)) => {
+                self.reason.set_error(Some(error.to_string()));
+                Err(SubmitError::Invalid)
+            }
+            Err(TaskUpdateError::Database(error)) => Err(error.into()),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reason.reset();
+        self.reason.set_error(None);
+    }
+}
+
+impl Component for TaskReasonInput {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        // A single line of input only needs the top of the popup area.
+        let input_area = Rect {
+            height: area.height.min(3),
+            ..area
+        };
+        self.reason.render(f, input_area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.reason.set_error(None);
+        self.reason.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.reason.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.reason.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.reason.area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::run_migrations;
+    use crate::models::TaskStatusChange;
+    use diesel::Connection;
+
+    #[test]
+    fn test_submit_records_reason() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        run_migrations(&mut conn);
+        let task = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+        let conn = Rc::new(RefCell::new(conn));
+        let mut input = TaskReasonInput::new(Rc::clone(&conn), vec![task.id], TaskStatus::Blocked);
+
+        assert!(matches!(input.submit(), Err(SubmitError::Invalid)));
+        input.handle_paste("Waiting on paint");
+        input.submit().unwrap();
+
+        let changes = TaskStatusChange::list_for_task(&mut conn.borrow_mut(), task.id).unwrap();
+        assert_eq!(changes[0].to_status, TaskStatus::Blocked);
+        assert_eq!(changes[0].reason.as_deref(), Some("Waiting on paint"));
+    }
+}
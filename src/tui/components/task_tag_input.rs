@@ -0,0 +1,85 @@
+use std::{cell::RefCell, rc::Rc};
+
+use diesel::SqliteConnection;
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    Frame,
+};
+
+use crate::models::TaskTag;
+
+use super::{user_input::UserInput, Component, InputSubmit, SubmitError};
+
+/// Popup for adding a tag to one or more tasks.
+pub struct TaskTagInput {
+    conn: Rc<RefCell<SqliteConnection>>,
+    task_ids: Vec<i32>,
+    tag: UserInput,
+}
+
+impl TaskTagInput {
+    pub fn new(conn: Rc<RefCell<SqliteConnection>>, task_ids: Vec<i32>) -> Self {
+        let title = match task_ids.as_slice() {
+            [task_id] => format!("Tag task {}", task_id),
+            task_ids => format!("Tag {} tasks", task_ids.len()),
+        };
+        Self {
+            conn,
+            task_ids,
+            tag: UserInput::new(title, true),
+        }
+    }
+}
+
+impl InputSubmit for TaskTagInput {
+    fn submit(&mut self) -> Result<(), SubmitError> {
+        let tag = self.tag.get_input().trim();
+        let error = if tag.is_empty() {
+            Some("Tag must not be empty")
+        } else if tag.contains(char::is_whitespace) {
+            Some("Tag must not contain spaces")
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            self.tag.set_error(Some(error.to_string()));
+            return Err(SubmitError::Invalid);
+        }
+        TaskTag::add_many(&mut self.conn.borrow_mut(), &self.task_ids, tag)?;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.tag.reset();
+        self.tag.set_error(None);
+    }
+}
+
+impl Component for TaskTagInput {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        // A single line of input only needs the top of the popup area.
+        let input_area = Rect {
+            height: area.height.min(3),
+            ..area
+        };
+        self.tag.render(f, input_area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.tag.set_error(None);
+        self.tag.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.tag.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.tag.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.tag.area()
+    }
+}
@@ -2,71 +2,88 @@ use std::{cell::RefCell, rc::Rc};
 
 use diesel::SqliteConnection;
 use ratatui::{
-    crossterm::event::KeyEvent,
+    crossterm::event::{KeyEvent, MouseEvent},
     layout::Rect,
     style::{Modifier, Style},
     widgets::{Block, Borders},
     Frame,
 };
 
-use crate::models::{task_status::TaskStatus, Task};
+use crate::events;
+use crate::models::{
+    task_status::TaskStatus, workflow::WorkflowError, Project, Task, TaskUpdateError,
+};
 
 use super::{
     multi_input::{Inputs, MultiInput},
-    Component, InputSubmit,
+    project_picker::ProjectPicker,
+    Component, InputSubmit, SubmitError,
 };
 
 pub struct TaskUpdate {
     inputs: MultiInput<TaskStatus>,
     conn: Rc<RefCell<SqliteConnection>>,
     task_id: i32,
+    area: Rect,
 }
 
 impl TaskUpdate {
-    pub fn new(
-        conn: Rc<RefCell<SqliteConnection>>,
-        task_id: i32,
-        title: String,
-        description: Option<String>,
-        status: TaskStatus,
-    ) -> Self {
+    pub fn from_task(conn: Rc<RefCell<SqliteConnection>>, task: &Task) -> Self {
+        let projects = Project::list(&mut conn.borrow_mut()).unwrap_or_default();
         let mut update = Self {
             conn,
-            task_id,
-            inputs: MultiInput::new(),
+            task_id: task.id,
+            inputs: MultiInput::new()
+                .with_project_picker(ProjectPicker::new(projects, task.project_id))
+                .with_reason_input(),
+            area: Rect::ZERO,
         };
-        update.inputs.set_inputs(title, description, status);
+        update
+            .inputs
+            .set_inputs(task.title.clone(), task.description.clone(), task.status);
         update
     }
 
-    pub fn from_task(conn: Rc<RefCell<SqliteConnection>>, task: &Task) -> Self {
-        Self::new(
-            conn,
-            task.id,
-            task.title.clone(),
-            task.description.clone(),
-            task.status,
-        )
+    pub fn is_editing_text(&self) -> bool {
+        self.inputs.is_editing_text()
     }
 }
 
 impl InputSubmit for TaskUpdate {
-    fn submit(&self) {
+    fn submit(&mut self) -> Result<(), SubmitError> {
         let Inputs {
             title,
             description,
             status,
-        } = self.inputs.get_inputs();
+            project_id,
+            reason,
+        } = self.inputs.get_valid_inputs()?;
 
-        Task::update(
-            &mut self.conn.borrow_mut(),
-            self.task_id,
-            Some(title),
-            Some(description),
-            Some(*status),
-            None,
-        )
-        .unwrap();
+        let result = events::transaction(&mut self.conn.borrow_mut(), |conn| {
+            Task::update_with_reason(
+                conn,
+                self.task_id,
+                Some(title),
+                Some(description),
+                Some(*status),
+                None,
+                reason,
+            )?;
+            Task::set_project(conn, self.task_id, project_id)?;
+            Ok(())
+        });
+        match result {
+            Ok(()) => Ok(()),
+            Err(TaskUpdateError::Workflow(error @ WorkflowError::ReasonRequired(_))) => {
+                self.inputs.set_reason_error(Some(error.to_string()));
+                Err(SubmitError::Invalid)
+            }
+            Err(TaskUpdateError::Workflow(error)) => {
+                self.inputs.set_status_error(Some(error.to_string()));
+                Err(SubmitError::Invalid)
+            }
+            Err(TaskUpdateError::Database(error)) => Err(error.into()),
+        }
     }
 
     fn reset(&mut self) {
@@ -81,6 +98,7 @@ impl Component for TaskUpdate {
             .title(format!("Task Update for task {}", self.task_id)) // Optional: Add a title to the border
             .style(Style::default().add_modifier(Modifier::BOLD)); // Add styles if needed
 
+        self.area = area;
         let inner_area = block.inner(area);
         f.render_widget(block, area);
         self.inputs.render(f, inner_area);
@@ -88,4 +106,16 @@ impl Component for TaskUpdate {
     fn handle_key_events(&mut self, key: KeyEvent) {
         self.inputs.handle_key_events(key);
     }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.inputs.handle_mouse_events(mouse);
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.inputs.handle_paste(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
 }
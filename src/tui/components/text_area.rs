@@ -0,0 +1,446 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Position, Rect},
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use super::Component;
+use unicode_width::UnicodeWidthChar;
+
+/// A multi-line text input with word-wrap and scrolling.
+pub struct TextArea {
+    /// Title of the text area
+    title: String,
+    /// Current value of the text area, lines separated by `\n`
+    text: String,
+    /// Position of the cursor in the text, in characters
+    cursor: usize,
+    /// First visual line shown, counted after wrapping
+    scroll: usize,
+    /// Indicates whether the text area is active.
+    active: bool,
+    /// Area the text area was last rendered into.
+    area: Rect,
+}
+
+impl TextArea {
+    pub const fn new(title: String, active: bool) -> Self {
+        Self {
+            title,
+            text: String::new(),
+            cursor: 0,
+            scroll: 0,
+            active,
+            area: Rect::ZERO,
+        }
+    }
+
+    pub fn switch_active(&mut self) {
+        self.active = !self.active;
+    }
+
+    pub fn get_input(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_input(&mut self, input: String) {
+        self.text = input;
+        self.cursor = self.text.chars().count();
+    }
+
+    pub fn reset(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.scroll = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Returns the byte index of a character position.
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .map(|(i, _)| i)
+            .nth(char_index)
+            .unwrap_or(self.text.len())
+    }
+
+    /// Returns the line and column of the cursor, both in characters.
+    fn cursor_position(&self) -> (usize, usize) {
+        let before_cursor = self.text.chars().take(self.cursor);
+        let (mut row, mut column) = (0, 0);
+        for c in before_cursor {
+            if c == '\n' {
+                row += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+        }
+        (row, column)
+    }
+
+    /// Returns the character position of a line and column, clamped to the line length.
+    fn char_index(&self, row: usize, column: usize) -> usize {
+        let mut index = 0;
+        for (i, line) in self.text.split('\n').enumerate() {
+            let line_len = line.chars().count();
+            if i == row {
+                return index + column.min(line_len);
+            }
+            index += line_len + 1;
+        }
+        self.len()
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let index = self.byte_index(self.cursor);
+        self.text.insert(index, c);
+        self.cursor += 1;
+    }
+
+    fn insert_text(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let index = self.byte_index(self.cursor);
+        self.text.insert_str(index, &text);
+        self.cursor += text.chars().count();
+    }
+
+    fn delete_char_before(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.delete_char_at_cursor();
+        }
+    }
+
+    fn delete_char_at_cursor(&mut self) {
+        if self.cursor < self.len() {
+            let index = self.byte_index(self.cursor);
+            self.text.remove(index);
+        }
+    }
+
+    fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.len());
+    }
+
+    fn move_up(&mut self) {
+        let (row, column) = self.cursor_position();
+        if row > 0 {
+            self.cursor = self.char_index(row - 1, column);
+        }
+    }
+
+    fn move_down(&mut self) {
+        let (row, column) = self.cursor_position();
+        if row + 1 < self.text.split('\n').count() {
+            self.cursor = self.char_index(row + 1, column);
+        }
+    }
+
+    fn move_line_start(&mut self) {
+        let (row, _) = self.cursor_position();
+        self.cursor = self.char_index(row, 0);
+    }
+
+    fn move_line_end(&mut self) {
+        let (row, _) = self.cursor_position();
+        self.cursor = self.char_index(row, usize::MAX);
+    }
+
+    /// Moves the cursor to the start of the current or previous word.
+    fn move_word_left(&mut self) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut index = self.cursor;
+        while index > 0 && !is_word_char(chars[index - 1]) {
+            index -= 1;
+        }
+        while index > 0 && is_word_char(chars[index - 1]) {
+            index -= 1;
+        }
+        self.cursor = index;
+    }
+
+    /// Moves the cursor to the end of the current or next word.
+    fn move_word_right(&mut self) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut index = self.cursor;
+        while index < chars.len() && !is_word_char(chars[index]) {
+            index += 1;
+        }
+        while index < chars.len() && is_word_char(chars[index]) {
+            index += 1;
+        }
+        self.cursor = index;
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Display width of characters, so wide ones such as CJK take two columns.
+fn display_width(chars: &[char]) -> usize {
+    chars.iter().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Splits a line into the character ranges of its visual lines when word-wrapped to `width`
+/// columns.
+///
+/// Lines are broken after the last space that fits. Words longer than the width are broken
+/// wherever they hit it.
+fn wrap_line(line: &[char], width: usize) -> Vec<(usize, usize)> {
+    let width = width.max(1);
+    let mut segments = Vec::new();
+    let mut start = 0;
+    loop {
+        // The end of the characters fitting into the width, but at least one.
+        let mut end = start;
+        let mut used = 0;
+        while end < line.len() {
+            used += line[end].width().unwrap_or(0);
+            if used > width && end > start {
+                break;
+            }
+            end += 1;
+        }
+        if end == line.len() {
+            break;
+        }
+        let split = (start + 1..=end)
+            .rev()
+            .find(|&i| line[i - 1] == ' ')
+            .unwrap_or(end);
+        segments.push((start, split));
+        start = split;
+    }
+    segments.push((start, line.len()));
+    segments
+}
+
+impl Component for TextArea {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
+        frame.render_widget(Clear, area);
+        let block = Block::bordered().title(&*self.title);
+        let inner_area = block.inner(area);
+        let width = inner_area.width as usize;
+        let height = inner_area.height as usize;
+
+        let (cursor_row, cursor_column) = self.cursor_position();
+        let mut visual_lines = Vec::new();
+        let mut visual_cursor = (0, 0);
+        for (row, line) in self.text.split('\n').enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            let segments = wrap_line(&chars, width);
+            if row == cursor_row {
+                // The cursor belongs to the first segment it is inside of, or the last one if
+                // it sits at the end of the line.
+                let (index, (start, _)) = segments
+                    .iter()
+                    .enumerate()
+                    .find(|(_, (_, end))| cursor_column < *end)
+                    .unwrap_or((segments.len() - 1, segments.last().unwrap()));
+                let column = display_width(&chars[*start..cursor_column]);
+                visual_cursor = (visual_lines.len() + index, column);
+            }
+            visual_lines.extend(
+                segments
+                    .into_iter()
+                    .map(|(start, end)| chars[start..end].iter().collect::<String>()),
+            );
+        }
+
+        // Scroll just enough to keep the cursor in view.
+        if visual_cursor.0 < self.scroll {
+            self.scroll = visual_cursor.0;
+        } else if height > 0 && visual_cursor.0 >= self.scroll + height {
+            self.scroll = visual_cursor.0 + 1 - height;
+        }
+
+        let visible_lines: Vec<Line> = visual_lines
+            .into_iter()
+            .skip(self.scroll)
+            .take(height)
+            .map(Line::from)
+            .collect();
+        frame.render_widget(Paragraph::new(visible_lines).block(block), area);
+
+        if self.active && height > 0 {
+            frame.set_cursor_position(Position::new(
+                inner_area.x + (visual_cursor.1 as u16).min(inner_area.width.saturating_sub(1)),
+                inner_area.y + (visual_cursor.0 - self.scroll) as u16,
+            ));
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('b') if alt => self.move_word_left(),
+            KeyCode::Char('f') if alt => self.move_word_right(),
+            KeyCode::Left if ctrl || alt => self.move_word_left(),
+            KeyCode::Right if ctrl || alt => self.move_word_right(),
+            KeyCode::Char(_) if ctrl || alt => {}
+            KeyCode::Char(to_insert) => self.insert_char(to_insert),
+            KeyCode::Enter => self.insert_char('\n'),
+            KeyCode::Backspace => self.delete_char_before(),
+            KeyCode::Delete => self.delete_char_at_cursor(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Up => self.move_up(),
+            KeyCode::Down => self.move_down(),
+            KeyCode::Home => self.move_line_start(),
+            KeyCode::End => self.move_line_end(),
+            _ => {}
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.insert_text(text);
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(text_area: &mut TextArea, text: &str) {
+        for c in text.chars() {
+            let key = match c {
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            text_area.handle_key_events(KeyEvent::from(key));
+        }
+    }
+
+    fn press(text_area: &mut TextArea, code: KeyCode, modifiers: KeyModifiers) {
+        text_area.handle_key_events(KeyEvent::new(code, modifiers));
+    }
+
+    #[test]
+    fn test_newlines() {
+        let mut text_area = TextArea::new("Description".to_string(), true);
+        type_text(&mut text_area, "first\nsecond");
+
+        assert_eq!(text_area.get_input(), "first\nsecond");
+        assert_eq!(text_area.cursor_position(), (1, 6));
+    }
+
+    #[test]
+    fn test_backspace_joins_lines() {
+        let mut text_area = TextArea::new("Description".to_string(), true);
+        type_text(&mut text_area, "first\nsecond");
+        press(&mut text_area, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut text_area, KeyCode::Backspace, KeyModifiers::NONE);
+
+        assert_eq!(text_area.get_input(), "firstsecond");
+        assert_eq!(text_area.cursor_position(), (0, 5));
+    }
+
+    #[test]
+    fn test_vertical_movement_clamps_column() {
+        let mut text_area = TextArea::new("Description".to_string(), true);
+        text_area.set_input("a longer line\nshort\nanother long line".to_string());
+
+        press(&mut text_area, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(text_area.cursor_position(), (1, 5));
+        press(&mut text_area, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(text_area.cursor_position(), (0, 5));
+        press(&mut text_area, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(text_area.cursor_position(), (0, 5));
+        press(&mut text_area, KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(text_area.cursor_position(), (0, 13));
+        press(&mut text_area, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut text_area, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(text_area.cursor_position(), (2, 5));
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let mut text_area = TextArea::new("Description".to_string(), true);
+        text_area.set_input("fix the_login bug\nsoon".to_string());
+
+        press(&mut text_area, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(text_area.cursor_position(), (1, 0));
+        press(&mut text_area, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(text_area.cursor_position(), (0, 14));
+        press(&mut text_area, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(text_area.cursor_position(), (0, 4));
+        press(&mut text_area, KeyCode::Char('f'), KeyModifiers::ALT);
+        assert_eq!(text_area.cursor_position(), (0, 13));
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut text_area = TextArea::new("Description".to_string(), true);
+        text_area.set_input("ab\ncd".to_string());
+        press(&mut text_area, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut text_area, KeyCode::Delete, KeyModifiers::NONE);
+        press(&mut text_area, KeyCode::Delete, KeyModifiers::NONE);
+
+        assert_eq!(text_area.get_input(), "abd");
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        let line: Vec<char> = "the quick brown fox".chars().collect();
+        assert_eq!(wrap_line(&line, 10), vec![(0, 10), (10, 19)]);
+        assert_eq!(
+            wrap_line(&line, 8),
+            vec![(0, 4), (4, 10), (10, 16), (16, 19)]
+        );
+        assert_eq!(wrap_line(&line, 30), vec![(0, 19)]);
+
+        let word: Vec<char> = "abcdefgh".chars().collect();
+        assert_eq!(wrap_line(&word, 3), vec![(0, 3), (3, 6), (6, 8)]);
+
+        assert_eq!(wrap_line(&[], 5), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_wrap_line_by_display_width() {
+        // Each of these takes two columns.
+        let wide: Vec<char> = "日本語の文章".chars().collect();
+        assert_eq!(wrap_line(&wide, 4), vec![(0, 2), (2, 4), (4, 6)]);
+        assert_eq!(wrap_line(&wide, 5), vec![(0, 2), (2, 4), (4, 6)]);
+
+        let mixed: Vec<char> = "ab 日本 cd".chars().collect();
+        assert_eq!(wrap_line(&mixed, 7), vec![(0, 3), (3, 8)]);
+        assert_eq!(wrap_line(&mixed, 8), vec![(0, 6), (6, 8)]);
+
+        // A character wider than the area still gets a line of its own.
+        assert_eq!(wrap_line(&wide[..2], 1), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_cursor_after_wide_characters() {
+        let mut text_area = TextArea::new("Description".to_string(), true);
+        text_area.set_input("日本".to_string());
+        let backend = ratatui::backend::TestBackend::new(20, 5);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+
+        terminal
+            .draw(|frame| text_area.render(frame, frame.area()))
+            .unwrap();
+
+        // Inside the border, after two characters of two columns each.
+        terminal
+            .backend_mut()
+            .assert_cursor_position(Position::new(5, 1));
+    }
+}
@@ -1,10 +1,12 @@
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     layout::{Position, Rect},
-    style::Style,
+    style::{Color, Style},
+    text::Line,
     widgets::{Block, Clear, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthChar;
 
 use super::Component;
 
@@ -15,8 +17,14 @@ pub struct UserInput {
     input: String,
     /// Position of cursor in the editor area.
     character_index: usize,
+    /// Index of the first character shown, for input longer than the box is wide.
+    scroll: usize,
     /// Indicates whether the input box is active.
     active: bool,
+    /// Validation error rendered below the input.
+    error: Option<String>,
+    /// Area the input box was last rendered into.
+    area: Rect,
 }
 
 impl UserInput {
@@ -25,7 +33,10 @@ impl UserInput {
             title,
             input: String::new(),
             character_index: 0,
+            scroll: 0,
             active,
+            error: None,
+            area: Rect::ZERO,
         }
     }
 
@@ -42,6 +53,10 @@ impl UserInput {
         self.set_cursor_max_position();
     }
 
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
     fn move_cursor_left(&mut self) {
         let cursor_moved_left = self.character_index.saturating_sub(1);
         self.character_index = self.clamp_cursor(cursor_moved_left);
@@ -52,6 +67,38 @@ impl UserInput {
         self.character_index = self.clamp_cursor(cursor_moved_right);
     }
 
+    /// Moves the cursor to the start of the current or previous word.
+    fn move_word_left(&mut self) {
+        self.character_index = self.word_start(self.character_index, char::is_alphanumeric);
+    }
+
+    /// Moves the cursor to the end of the current or next word.
+    fn move_word_right(&mut self) {
+        let chars: Vec<char> = self.input.chars().collect();
+        let mut index = self.character_index;
+        while index < chars.len() && !chars[index].is_alphanumeric() {
+            index += 1;
+        }
+        while index < chars.len() && chars[index].is_alphanumeric() {
+            index += 1;
+        }
+        self.character_index = index;
+    }
+
+    /// Returns the index of the start of the word before `index`, where words consist of
+    /// characters matching `is_word_char`.
+    fn word_start(&self, index: usize, is_word_char: fn(char) -> bool) -> usize {
+        let chars: Vec<char> = self.input.chars().take(index).collect();
+        let mut start = chars.len();
+        while start > 0 && !is_word_char(chars[start - 1]) {
+            start -= 1;
+        }
+        while start > 0 && is_word_char(chars[start - 1]) {
+            start -= 1;
+        }
+        start
+    }
+
     fn enter_char(&mut self, new_char: char) {
         let index = self.byte_index();
         self.input.insert(index, new_char);
@@ -59,40 +106,70 @@ impl UserInput {
         self.move_cursor_right();
     }
 
+    /// Inserts pasted text at the cursor. Line breaks are replaced by spaces, since the input
+    /// holds a single line.
+    fn enter_text(&mut self, text: &str) {
+        let text: String = text
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .map(|c| if c == '\r' || c == '\n' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        let index = self.byte_index();
+        self.input.insert_str(index, &text);
+        self.character_index += text.chars().count();
+    }
+
     /// Returns the byte index based on the character position.
     ///
     /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
     /// the byte index based on the index of the character.
     fn byte_index(&self) -> usize {
+        self.byte_index_of(self.character_index)
+    }
+
+    fn byte_index_of(&self, character_index: usize) -> usize {
         self.input
             .char_indices()
             .map(|(i, _)| i)
-            .nth(self.character_index)
+            .nth(character_index)
             .unwrap_or(self.input.len())
     }
 
     fn delete_char(&mut self) {
         let is_not_cursor_leftmost = self.character_index != 0;
         if is_not_cursor_leftmost {
-            // Method "remove" is not used on the saved text for deleting the selected char.
-            // Reason: Using remove on String works on bytes instead of the chars.
-            // Using remove would require special care because of char boundaries.
-
-            let current_index = self.character_index;
-            let from_left_to_current_index = current_index - 1;
-
-            // Getting all characters before the selected character.
-            let before_char_to_delete = self.input.chars().take(from_left_to_current_index);
-            // Getting all characters after selected character.
-            let after_char_to_delete = self.input.chars().skip(current_index);
-
-            // Put all characters together except the selected one.
-            // By leaving the selected one out, it is forgotten and therefore deleted.
-            self.input = before_char_to_delete.chain(after_char_to_delete).collect();
-            self.move_cursor_left();
+            self.delete_range(self.character_index - 1, self.character_index);
+        }
+    }
+
+    fn delete_char_at_cursor(&mut self) {
+        if self.character_index < self.input.chars().count() {
+            self.delete_range(self.character_index, self.character_index + 1);
         }
     }
 
+    /// Deletes the whitespace separated word before the cursor, like Ctrl-W in a shell.
+    fn delete_word_before_cursor(&mut self) {
+        let start = self.word_start(self.character_index, |c| !c.is_whitespace());
+        self.delete_range(start, self.character_index);
+    }
+
+    fn delete_to_start(&mut self) {
+        self.delete_range(0, self.character_index);
+    }
+
+    fn delete_to_end(&mut self) {
+        self.delete_range(self.character_index, self.input.chars().count());
+    }
+
+    /// Deletes the characters from `start` up to `end` and leaves the cursor at `start`.
+    fn delete_range(&mut self, start: usize, end: usize) {
+        let range = self.byte_index_of(start)..self.byte_index_of(end);
+        self.input.replace_range(range, "");
+        self.character_index = start;
+    }
+
     fn clamp_cursor(&self, new_cursor_pos: usize) -> usize {
         new_cursor_pos.clamp(0, self.input.chars().count())
     }
@@ -105,24 +182,66 @@ impl UserInput {
         self.character_index = self.input.chars().count();
     }
 
+    /// Scrolls horizontally so the cursor stays within `width` columns.
+    fn scroll_to_cursor(&mut self, width: usize) {
+        if self.character_index < self.scroll {
+            self.scroll = self.character_index;
+        }
+        while self.scroll < self.character_index && self.cursor_column() >= width {
+            self.scroll += 1;
+        }
+    }
+
+    /// Display width of the visible text before the cursor.
+    fn cursor_column(&self) -> usize {
+        self.input
+            .chars()
+            .skip(self.scroll)
+            .take(self.character_index - self.scroll)
+            .map(|c| c.width().unwrap_or(0))
+            .sum()
+    }
+
+    /// Returns the character index displayed at `column` of the visible text.
+    fn character_index_at(&self, column: usize) -> usize {
+        let mut width = 0;
+        for (index, c) in self.input.chars().enumerate().skip(self.scroll) {
+            width += c.width().unwrap_or(0);
+            if width > column {
+                return index;
+            }
+        }
+        self.input.chars().count()
+    }
+
     pub fn reset(&mut self) {
         self.input.clear();
         self.reset_cursor();
+        self.scroll = 0;
     }
 }
 
 impl Component for UserInput {
     fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
+        // Leave one column after the text for the cursor.
+        self.scroll_to_cursor(area.width.saturating_sub(3) as usize);
         frame.render_widget(Clear, area);
-        let input = Paragraph::new(self.input.as_str())
-            .style(Style::default())
-            .block(Block::bordered().title(&*self.title));
+        let visible = &self.input[self.byte_index_of(self.scroll)..];
+        let mut block = Block::bordered().title(&*self.title);
+        if let Some(error) = &self.error {
+            let error_style = Style::default().fg(Color::Red);
+            block = block
+                .border_style(error_style)
+                .title_bottom(Line::styled(error.as_str(), error_style));
+        }
+        let input = Paragraph::new(visible).style(Style::default()).block(block);
         frame.render_widget(input, area);
         if self.active {
             frame.set_cursor_position(Position::new(
-                // Draw the cursor at the current position in the input field.
-                // This position is can be controlled via the left and right arrow key
-                area.x + self.character_index as u16 + 1,
+                // Draw the cursor at the display column of the current position in the input
+                // field, which differs from the character index for wide characters.
+                area.x + self.cursor_column() as u16 + 1,
                 // Move one line down, from the border to the input line
                 area.y + 1,
             ));
@@ -130,12 +249,142 @@ impl Component for UserInput {
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
         match key.code {
+            KeyCode::Char('a') if ctrl => self.reset_cursor(),
+            KeyCode::Char('e') if ctrl => self.set_cursor_max_position(),
+            KeyCode::Char('w') if ctrl => self.delete_word_before_cursor(),
+            KeyCode::Char('u') if ctrl => self.delete_to_start(),
+            KeyCode::Char('k') if ctrl => self.delete_to_end(),
+            KeyCode::Char('b') if alt => self.move_word_left(),
+            KeyCode::Char('f') if alt => self.move_word_right(),
+            KeyCode::Char(_) if ctrl || alt => {}
             KeyCode::Char(to_insert) => self.enter_char(to_insert),
             KeyCode::Backspace => self.delete_char(),
+            KeyCode::Delete => self.delete_char_at_cursor(),
+            KeyCode::Left if ctrl || alt => self.move_word_left(),
+            KeyCode::Right if ctrl || alt => self.move_word_right(),
             KeyCode::Left => self.move_cursor_left(),
             KeyCode::Right => self.move_cursor_right(),
+            KeyCode::Home => self.reset_cursor(),
+            KeyCode::End => self.set_cursor_max_position(),
             _ => {}
         }
     }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.enter_text(text);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+            && self.contains(mouse.column, mouse.row)
+        {
+            // Place the cursor under the click, accounting for the left border.
+            let column = mouse.column.saturating_sub(self.area.x + 1) as usize;
+            self.character_index = self.character_index_at(column);
+        }
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(text: &str) -> UserInput {
+        let mut input = UserInput::new("Test".to_string(), true);
+        input.set_input(text.to_string());
+        input
+    }
+
+    fn press(input: &mut UserInput, code: KeyCode, modifiers: KeyModifiers) {
+        input.handle_key_events(KeyEvent::new(code, modifiers));
+    }
+
+    #[test]
+    fn test_home_end_and_delete() {
+        let mut input = input("hello");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.get_input(), "ello");
+
+        press(&mut input, KeyCode::End, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Char('!'), KeyModifiers::NONE);
+        assert_eq!(input.get_input(), "ello!");
+    }
+
+    #[test]
+    fn test_kill_commands() {
+        let mut input = input("fix the login-bug now");
+        press(&mut input, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(input.get_input(), "fix the login-bug ");
+        press(&mut input, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(input.get_input(), "fix the ");
+
+        press(&mut input, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut input, KeyCode::Char('f'), KeyModifiers::ALT);
+        press(&mut input, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(input.get_input(), "fix");
+
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(input.get_input(), "x");
+        assert_eq!(input.character_index, 0);
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let mut input = input("one two-three");
+        press(&mut input, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(input.character_index, 8);
+        press(&mut input, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(input.character_index, 4);
+        press(&mut input, KeyCode::Char('f'), KeyModifiers::ALT);
+        assert_eq!(input.character_index, 7);
+    }
+
+    #[test]
+    fn test_control_chords_do_not_insert() {
+        let mut input = input("abc");
+        press(&mut input, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(input.get_input(), "abc");
+    }
+
+    #[test]
+    fn test_paste_inserts_single_line() {
+        let mut input = input("ab");
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        input.handle_paste("1\n2\n");
+        assert_eq!(input.get_input(), "a1 2b");
+        assert_eq!(input.character_index, 4);
+    }
+
+    #[test]
+    fn test_scrolls_to_keep_cursor_visible() {
+        let mut input = input("abcdefghij");
+        input.scroll_to_cursor(4);
+        assert_eq!(input.scroll, 7);
+        assert_eq!(input.cursor_column(), 3);
+
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        input.scroll_to_cursor(4);
+        assert_eq!(input.scroll, 0);
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let mut input = input("日本語");
+        assert_eq!(input.cursor_column(), 6);
+        assert_eq!(input.character_index_at(3), 1);
+
+        input.scroll_to_cursor(4);
+        assert_eq!(input.scroll, 2);
+        assert_eq!(input.cursor_column(), 2);
+    }
 }
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::Rect,
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::workspaces::Workspaces;
+
+use super::{list_selection::ListSelection, Component};
+
+/// An entry of the workspace picker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceChoice {
+    pub name: String,
+    database: PathBuf,
+    /// Whether the app currently shows this workspace.
+    current: bool,
+}
+
+impl<'a> From<WorkspaceChoice> for ListItem<'a> {
+    fn from(choice: WorkspaceChoice) -> Self {
+        let marker = if choice.current { '*' } else { ' ' };
+        ListItem::new(format!(
+            "{} {}: {}",
+            marker,
+            choice.name,
+            choice.database.display()
+        ))
+    }
+}
+
+/// Picks the workspace to switch to, starting at the current one.
+pub struct WorkspacePicker {
+    choices: ListSelection<WorkspaceChoice>,
+}
+
+impl WorkspacePicker {
+    pub fn new(workspaces: &Workspaces, current: Option<&str>) -> Self {
+        let choices: Vec<WorkspaceChoice> = workspaces
+            .workspaces
+            .iter()
+            .map(|(name, database)| WorkspaceChoice {
+                name: name.clone(),
+                database: database.clone(),
+                current: current == Some(name.as_str()),
+            })
+            .collect();
+        let current_choice = choices.iter().find(|choice| choice.current).cloned();
+        let mut choices = ListSelection::new(choices, "Switch workspace").wrap_around(false);
+        if let Some(choice) = current_choice {
+            choices.set_selected(choice);
+        }
+        choices.switch_active();
+        Self { choices }
+    }
+
+    pub fn selected(&self) -> Option<&WorkspaceChoice> {
+        self.choices.selected()
+    }
+}
+
+impl Component for WorkspacePicker {
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.choices.render(f, area);
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) {
+        self.choices.handle_key_events(key);
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) {
+        self.choices.handle_mouse_events(mouse);
+    }
+
+    fn area(&self) -> Rect {
+        self.choices.area()
+    }
+}
@@ -0,0 +1,253 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Renders a small subset of Markdown into styled lines.
+///
+/// Supported are ATX headings (`# Title`), bullet and numbered lists, fenced code blocks and
+/// the inline elements code spans, `**bold**`, `*italic*` and `[links](url)`. Everything else
+/// is rendered as plain text.
+pub fn render_markdown(text: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::from(Span::styled(line.to_string(), code_style())));
+            continue;
+        }
+        lines.push(render_block_line(line));
+    }
+    lines
+}
+
+fn render_block_line(line: &str) -> Line<'static> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if let Some((level, heading)) = parse_heading(trimmed) {
+        let style = match level {
+            1 => heading_style().add_modifier(Modifier::UNDERLINED),
+            _ => heading_style(),
+        };
+        return Line::from(
+            render_inline(heading)
+                .into_iter()
+                .map(|span| span.patch_style(style))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = trimmed.strip_prefix(bullet) {
+            let mut spans = vec![Span::raw(format!("{}• ", indent))];
+            spans.extend(render_inline(item));
+            return Line::from(spans);
+        }
+    }
+
+    let mut spans = render_inline(trimmed);
+    if !indent.is_empty() {
+        spans.insert(0, Span::raw(indent.to_string()));
+    }
+    Line::from(spans)
+}
+
+/// Returns the level and text of an ATX heading such as `## Notes`.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if rest.is_empty() {
+        return Some((level, rest));
+    }
+    rest.strip_prefix(' ')
+        .map(|heading| (level, heading.trim()))
+}
+
+/// Splits a line into spans for code spans, emphasis and links.
+fn render_inline(text: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        // Underscores inside words such as snake_case are not emphasis.
+        let intraword = c == '_' && plain.ends_with(char::is_alphanumeric);
+        let element = match c {
+            _ if intraword => None,
+            '`' => delimited(rest, "`").map(|(inner, len)| (vec![code_span(inner)], len)),
+            '*' | '_' if rest[1..].starts_with(c) => {
+                let delimiter = if c == '*' { "**" } else { "__" };
+                delimited(rest, delimiter).map(|(inner, len)| {
+                    (
+                        styled(inner, Style::new().add_modifier(Modifier::BOLD)),
+                        len,
+                    )
+                })
+            }
+            '*' | '_' => delimited(rest, &rest[..1]).map(|(inner, len)| {
+                (
+                    styled(inner, Style::new().add_modifier(Modifier::ITALIC)),
+                    len,
+                )
+            }),
+            '[' => link(rest),
+            _ => None,
+        };
+        match element {
+            Some((element_spans, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.extend(element_spans);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+/// Finds text wrapped in `delimiter` at the start of `text`. Returns the inner text and the
+/// length of the whole element including delimiters.
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, usize)> {
+    let inner_start = delimiter.len();
+    let inner_len = text[inner_start..].find(delimiter)?;
+    if inner_len == 0 {
+        return None;
+    }
+    let inner = &text[inner_start..inner_start + inner_len];
+    if inner.starts_with(char::is_whitespace) || inner.ends_with(char::is_whitespace) {
+        return None;
+    }
+    Some((inner, inner_start + inner_len + delimiter.len()))
+}
+
+/// Parses a `[label](url)` link at the start of `text`.
+fn link(text: &str) -> Option<(Vec<Span<'static>>, usize)> {
+    let label_end = text.find("](")?;
+    let url_end = label_end + 2 + text[label_end + 2..].find(')')?;
+    let label = &text[1..label_end];
+    let url = &text[label_end + 2..url_end];
+    let spans = vec![
+        Span::styled(
+            label.to_string(),
+            Style::new()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::UNDERLINED),
+        ),
+        Span::styled(format!(" ({})", url), Style::new().fg(Color::DarkGray)),
+    ];
+    Some((spans, url_end + 1))
+}
+
+fn styled(text: &str, style: Style) -> Vec<Span<'static>> {
+    render_inline(text)
+        .into_iter()
+        .map(|span| span.patch_style(style))
+        .collect()
+}
+
+fn code_span(text: &str) -> Span<'static> {
+    Span::styled(text.to_string(), code_style())
+}
+
+fn code_style() -> Style {
+    Style::new().fg(Color::Yellow)
+}
+
+fn heading_style() -> Style {
+    Style::new()
+        .fg(Color::LightMagenta)
+        .add_modifier(Modifier::BOLD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_headings() {
+        let lines = render_markdown("# Title\n### Sub title\n#hashtag");
+
+        assert_eq!(plain_text(&lines[0]), "Title");
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD | Modifier::UNDERLINED));
+        assert_eq!(plain_text(&lines[1]), "Sub title");
+        assert_eq!(plain_text(&lines[2]), "#hashtag");
+    }
+
+    #[test]
+    fn test_bullet_lists() {
+        let lines = render_markdown("- one\n  * nested\n1. numbered");
+
+        assert_eq!(plain_text(&lines[0]), "• one");
+        assert_eq!(plain_text(&lines[1]), "  • nested");
+        assert_eq!(plain_text(&lines[2]), "1. numbered");
+    }
+
+    #[test]
+    fn test_code_spans_and_blocks() {
+        let lines = render_markdown("run `cargo test` now\n```\nlet *x* = 1;\n```");
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(plain_text(&lines[0]), "run cargo test now");
+        assert_eq!(lines[0].spans[1].content, "cargo test");
+        assert_eq!(lines[0].spans[1].style, code_style());
+        assert_eq!(lines[0].spans[2].content, " now");
+        // Markdown is not interpreted inside code blocks.
+        assert_eq!(plain_text(&lines[1]), "let *x* = 1;");
+    }
+
+    #[test]
+    fn test_emphasis() {
+        let lines = render_markdown("**bold** and *italic* but 2 * 3 * 4 and snake_case_name");
+
+        assert_eq!(
+            plain_text(&lines[0]),
+            "bold and italic but 2 * 3 * 4 and snake_case_name"
+        );
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert!(lines[0].spans[2]
+            .style
+            .add_modifier
+            .contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_links() {
+        let lines = render_markdown("see [the docs](https://example.com) or [not a link");
+
+        assert_eq!(
+            plain_text(&lines[0]),
+            "see the docs (https://example.com) or [not a link"
+        );
+        assert_eq!(lines[0].spans[1].content, "the docs");
+    }
+}
@@ -1,4 +1,74 @@
 pub mod app;
 mod components;
+mod markdown;
 mod utils;
 mod widgets;
+
+use clap::Parser;
+use ratatui::crossterm::{
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute,
+};
+use ratatui::DefaultTerminal;
+use std::io::{self, stdout};
+use std::panic;
+use std::sync::Once;
+
+/// Command line of `roll-tui`. It also describes the key bindings for the man page.
+#[derive(Debug, Parser)]
+#[command(name = "roll-tui", version)]
+#[command(about = "Terminal user interface for On a Roll")]
+#[command(long_about = "Terminal user interface for On a Roll.
+
+Key bindings:
+  Tab            Switch between tasks and projects
+  j, k, Up, Down Move the selection
+  a              Add a task or project
+  n              Quick-add a task, see `roll help quick-add`
+  u              Update the selected task or project
+  d              Delete the selected task or project
+  t              Tag the selected task
+  c              Comment on the selected task
+  o              Open a link of the selected task, see `roll help config`
+  J, K           Scroll the activity feed of the selected task
+  e              Edit the selected task in $VISUAL or $EDITOR
+  W              Switch to another workspace, see `roll workspace`
+  :, Ctrl-P      Open the command palette
+  Enter, Ctrl-S  Submit a form; Enter adds a line in text areas
+  Esc            Close a popup
+  q              Quit")]
+pub struct TuiCli {
+    /// Use the database of this workspace, see `roll workspace`
+    #[arg(long)]
+    pub workspace: Option<String>,
+}
+
+/// Sets up the terminal like [`ratatui::init`], also capturing the mouse and pasted text.
+/// A panic puts the terminal back as [`restore`] does.
+pub fn init() -> io::Result<DefaultTerminal> {
+    let terminal = ratatui::init();
+    static PANIC_HOOK: Once = Once::new();
+    PANIC_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            disable_input_capture();
+            hook(info);
+        }));
+    });
+    if let Err(e) = execute!(stdout(), EnableBracketedPaste, EnableMouseCapture) {
+        restore();
+        return Err(e);
+    }
+    Ok(terminal)
+}
+
+/// Puts the terminal back after [`init`].
+pub fn restore() {
+    disable_input_capture();
+    ratatui::restore();
+}
+
+fn disable_input_capture() {
+    // Nothing more can be done if this fails, and the rest must be restored anyway.
+    execute!(stdout(), DisableBracketedPaste, DisableMouseCapture).ok();
+}
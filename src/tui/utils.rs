@@ -34,9 +34,46 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+/// Scores how well `query` fuzzy-matches `candidate`.
+///
+/// Every character of the query has to appear in the candidate in the same order, ignoring
+/// case. Consecutive matches and matches at the start of a word score higher, gaps between
+/// matched characters lower the score. Returns `None` if the query does not match at all.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.chars().flat_map(char::to_lowercase) {
+        if query_char.is_whitespace() {
+            continue;
+        }
+        let offset = candidate[position..]
+            .iter()
+            .position(|&c| c == query_char)?;
+        let index = position + offset;
+
+        score += 1;
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        if let Some(previous) = previous_match {
+            if previous + 1 == index {
+                score += 5;
+            }
+            score -= offset as i64;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
 #[cfg(test)]
 mod tests {
-    use crate::tui::utils::centered_rect;
+    use crate::tui::utils::{centered_rect, fuzzy_score};
     use ratatui::layout::Rect;
 
     #[test]
@@ -45,4 +82,24 @@ mod tests {
         let centered = centered_rect(50, 50, area);
         assert_eq!(centered, Rect::new(25, 25, 50, 50));
     }
+
+    #[test]
+    fn test_fuzzy_score_matches_subsequence() {
+        assert!(fuzzy_score("nt", "New task").is_some());
+        assert!(fuzzy_score("NEW", "new task").is_some());
+        assert!(fuzzy_score("", "anything").is_some());
+        assert_eq!(fuzzy_score("tn", "New task"), None);
+        assert_eq!(fuzzy_score("xyz", "New task"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_tighter_matches() {
+        let tight = fuzzy_score("block", "Set task status: Blocked").unwrap();
+        let loose = fuzzy_score("block", "Bulk unlock").unwrap();
+        assert!(tight > loose);
+
+        let word_start = fuzzy_score("np", "New project").unwrap();
+        let mid_word = fuzzy_score("np", "Unpin").unwrap();
+        assert!(word_start > mid_word);
+    }
 }
@@ -0,0 +1,170 @@
+//! Named workspaces, each with its own database, kept in `~/.config/on-a-roll/workspaces.toml`
+//! or the file named by `$ROLL_WORKSPACES`:
+//!
+//! ```toml
+//! active = "work"
+//!
+//! [workspaces]
+//! personal = "/home/me/tasks/personal.db"
+//! work = "/home/me/tasks/work.db"
+//! ```
+//!
+//! Unlike the config file, roll writes this file itself when workspaces are added, removed or
+//! switched.
+use crate::config::APP_DIR;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const WORKSPACES_ENV: &str = "ROLL_WORKSPACES";
+const WORKSPACES_FILE: &str = "workspaces.toml";
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Workspaces {
+    /// The workspace used when none is given with `--workspace`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    /// Database path of each workspace, by name.
+    pub workspaces: BTreeMap<String, PathBuf>,
+}
+
+impl Workspaces {
+    /// Path of the workspaces file: `$ROLL_WORKSPACES` if set, otherwise `workspaces.toml` in
+    /// the `on-a-roll` directory of the platform's config directory.
+    pub fn path() -> Option<PathBuf> {
+        match env::var_os(WORKSPACES_ENV) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|dir| dir.join(APP_DIR).join(WORKSPACES_FILE)),
+        }
+    }
+
+    /// Loads the workspaces file, or no workspaces if there is none.
+    pub fn load() -> Result<Self, String> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("No config directory found, set ROLL_WORKSPACES")?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let text = toml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Database path of a workspace.
+    pub fn get(&self, name: &str) -> Result<&Path, String> {
+        self.workspaces
+            .get(name)
+            .map(PathBuf::as_path)
+            .ok_or_else(|| format!("Unknown workspace '{}'", name))
+    }
+
+    /// Name and database path of the active workspace, if there is one.
+    pub fn active(&self) -> Option<(&str, &Path)> {
+        let name = self.active.as_deref()?;
+        self.get(name).ok().map(|path| (name, path))
+    }
+
+    /// Adds a workspace. It only becomes the active one when switched to, so adding one
+    /// doesn't change which database is used.
+    pub fn add(&mut self, name: &str, database: PathBuf) -> Result<(), String> {
+        validate_name(name)?;
+        if self.workspaces.contains_key(name) {
+            return Err(format!("Workspace '{}' already exists", name));
+        }
+        self.workspaces.insert(name.to_string(), database);
+        Ok(())
+    }
+
+    /// Removes a workspace, leaving its database in place. Removing the active workspace
+    /// leaves none active.
+    pub fn remove(&mut self, name: &str) -> Result<PathBuf, String> {
+        let database = self
+            .workspaces
+            .remove(name)
+            .ok_or_else(|| format!("Unknown workspace '{}'", name))?;
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        Ok(database)
+    }
+
+    pub fn switch(&mut self, name: &str) -> Result<(), String> {
+        self.get(name)?;
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid workspace name '{}', use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_switch_remove() {
+        let mut workspaces = Workspaces::default();
+        workspaces.add("personal", PathBuf::from("/p.db")).unwrap();
+        workspaces.add("work", PathBuf::from("/w.db")).unwrap();
+        assert_eq!(workspaces.active(), None);
+        assert!(workspaces.add("work", PathBuf::from("/x.db")).is_err());
+        assert!(workspaces.add("my work", PathBuf::from("/x.db")).is_err());
+
+        workspaces.switch("work").unwrap();
+        assert_eq!(workspaces.active(), Some(("work", Path::new("/w.db"))));
+        assert!(workspaces.switch("play").is_err());
+
+        assert_eq!(workspaces.remove("work").unwrap(), PathBuf::from("/w.db"));
+        assert_eq!(workspaces.active(), None);
+        assert!(workspaces.remove("work").is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/workspaces.toml");
+        assert_eq!(Workspaces::load_from(&path).unwrap(), Workspaces::default());
+
+        let mut workspaces = Workspaces::default();
+        workspaces.add("work", PathBuf::from("/w.db")).unwrap();
+        workspaces.save_to(&path).unwrap();
+
+        assert_eq!(Workspaces::load_from(&path).unwrap(), workspaces);
+        fs::write(&path, "current = \"work\"\n").unwrap();
+        assert!(Workspaces::load_from(&path).is_err());
+    }
+}
@@ -2,10 +2,13 @@ mod common;
 
 use clap::Parser;
 use common::establish_test_connection;
+use on_a_roll::workspaces::Workspaces;
 use on_a_roll::{
     cli::{run_cli, Cli},
-    models::Project,
+    db::discovery::DatabaseLocation,
+    models::{task_status::TaskStatus, Project, Task, TaskComment, TaskFilter, TaskLink},
 };
+use std::str::FromStr;
 
 #[test]
 fn test_create_project_via_cli() {
@@ -104,3 +107,373 @@ fn test_list_projects_via_cli() {
     assert_eq!(projects[0].title, "Project 1");
     assert_eq!(projects[1].title, "Project 2");
 }
+
+fn run(conn: &mut diesel::SqliteConnection, args: &[&str]) {
+    let cli = Cli::parse_from(std::iter::once("roll").chain(args.iter().copied()));
+    run_cli(cli, conn);
+}
+
+fn create_tasks(conn: &mut diesel::SqliteConnection, count: usize) -> Vec<Task> {
+    (1..=count)
+        .map(|i| Task::create(conn, Some(&format!("Task {}", i)), None, None, None).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_update_task_range_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 4);
+
+    let range = format!("{}-{}", tasks[1].id, tasks[2].id);
+    run(
+        &mut conn,
+        &["task", "update", &range, "--status", "Completed"],
+    );
+
+    let statuses: Vec<TaskStatus> = Task::list(&mut conn)
+        .unwrap()
+        .into_iter()
+        .map(|task| task.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            TaskStatus::Todo,
+            TaskStatus::Completed,
+            TaskStatus::Completed,
+            TaskStatus::Todo
+        ]
+    );
+}
+
+#[test]
+fn test_update_tasks_where_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 3);
+    Task::update(
+        &mut conn,
+        tasks[0].id,
+        None,
+        None,
+        Some(TaskStatus::Blocked),
+        None,
+    )
+    .unwrap();
+    Task::update(
+        &mut conn,
+        tasks[2].id,
+        None,
+        None,
+        Some(TaskStatus::Blocked),
+        None,
+    )
+    .unwrap();
+
+    run(
+        &mut conn,
+        &[
+            "task",
+            "update",
+            "--where",
+            "status=Blocked",
+            "-s",
+            "On Hold",
+        ],
+    );
+
+    let on_hold = Task::list(&mut conn)
+        .unwrap()
+        .into_iter()
+        .filter(|task| task.status == TaskStatus::OnHold)
+        .count();
+    assert_eq!(on_hold, 2);
+}
+
+#[test]
+fn test_update_tasks_dry_run_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 2);
+
+    let first = tasks[0].id.to_string();
+    let second = tasks[1].id.to_string();
+    run(
+        &mut conn,
+        &[
+            "task",
+            "update",
+            &first,
+            &second,
+            "--title",
+            "Renamed",
+            "--dry-run",
+        ],
+    );
+
+    assert_eq!(Task::list(&mut conn).unwrap(), tasks);
+}
+
+#[test]
+fn test_update_tasks_missing_id_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 1);
+
+    let id = tasks[0].id.to_string();
+    run(
+        &mut conn,
+        &["task", "update", &id, "9999", "--title", "Renamed"],
+    );
+
+    assert_eq!(Task::list(&mut conn).unwrap(), tasks);
+}
+
+#[test]
+fn test_delete_tasks_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 12);
+
+    let range = format!("{}-{}", tasks[0].id, tasks[10].id);
+    run(&mut conn, &["task", "delete", &range, "--yes"]);
+
+    assert_eq!(Task::list(&mut conn).unwrap(), vec![tasks[11].clone()]);
+}
+
+#[test]
+fn test_quick_add_via_cli() {
+    let mut conn = establish_test_connection();
+    Project::create(&mut conn, Some("Home"), None, None).unwrap();
+
+    run(&mut conn, &["add", "Water", "plants", "+garden", "@home"]);
+
+    let tasks = Task::list(&mut conn).unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].title, "Water plants");
+    assert!(tasks[0].project_id.is_some());
+}
+
+#[test]
+fn test_refer_to_projects_by_slug_via_cli() {
+    let mut conn = establish_test_connection();
+    run(&mut conn, &["project", "add", "Garden Shed"]);
+    run(&mut conn, &["project", "add", "Kitchen"]);
+    let garden = Project::list(&mut conn).unwrap()[0].clone();
+
+    run(
+        &mut conn,
+        &["task", "add", "Paint walls", "", "Todo", "garden-shed"],
+    );
+    run(&mut conn, &["task", "add", "Buy tiles"]);
+    let tasks = Task::list(&mut conn).unwrap();
+    assert_eq!(tasks[0].project_id, Some(garden.id));
+
+    let id = tasks[1].id.to_string();
+    run(&mut conn, &["task", "update", &id, "--project", "gard"]);
+    assert_eq!(
+        Task::find(&mut conn, tasks[1].id).unwrap().project_id,
+        Some(garden.id)
+    );
+
+    run(
+        &mut conn,
+        &["project", "update", "garden-shed", "--title", "Shed"],
+    );
+    assert_eq!(Project::find(&mut conn, garden.id).unwrap().title, "Shed");
+
+    // An unknown project leaves the task untouched.
+    run(&mut conn, &["task", "update", &id, "--project", "attic"]);
+    assert_eq!(
+        Task::find(&mut conn, tasks[1].id).unwrap().project_id,
+        Some(garden.id)
+    );
+}
+
+#[test]
+fn test_manpages_via_cli() {
+    let mut conn = establish_test_connection();
+    let out_dir = tempfile::tempdir().unwrap();
+
+    run(
+        &mut conn,
+        &["manpage", "--out-dir", out_dir.path().to_str().unwrap()],
+    );
+
+    for page in ["roll.1", "roll-task-update.1", "roll-help.1", "roll-tui.1"] {
+        let path = out_dir.path().join(page);
+        assert!(path.exists(), "{} was not written", page);
+    }
+    assert!(!out_dir.path().join("roll-__complete.1").exists());
+    let tui_page = std::fs::read_to_string(out_dir.path().join("roll-tui.1")).unwrap();
+    assert!(tui_page.contains("Key bindings"));
+}
+
+#[test]
+fn test_status_spellings_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 3);
+
+    for (task, status) in tasks.iter().zip(["in_progress", "WIP", "done"]) {
+        run(
+            &mut conn,
+            &["task", "update", &task.id.to_string(), "-s", status],
+        );
+    }
+
+    let statuses: Vec<TaskStatus> = Task::list(&mut conn)
+        .unwrap()
+        .into_iter()
+        .map(|task| task.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            TaskStatus::InProgress,
+            TaskStatus::InProgress,
+            TaskStatus::Completed
+        ]
+    );
+    assert!(Cli::try_parse_from(["roll", "task", "update", "1", "-s", "someday"]).is_err());
+}
+
+#[test]
+fn test_block_and_list_ready_via_cli() {
+    let mut conn = establish_test_connection();
+    let tasks = create_tasks(&mut conn, 2);
+    let (task, blocker) = (tasks[0].id.to_string(), tasks[1].id.to_string());
+
+    run(&mut conn, &["task", "block", &task, "--on", &blocker]);
+    run(&mut conn, &["task", "deps", &task]);
+    let ready = TaskFilter::from_str("ready=true").unwrap();
+    assert_eq!(
+        Task::list_filtered(&mut conn, &ready).unwrap(),
+        vec![Task::find(&mut conn, tasks[1].id).unwrap()]
+    );
+    assert_eq!(
+        Task::find(&mut conn, tasks[0].id).unwrap().status,
+        TaskStatus::Blocked
+    );
+
+    run(&mut conn, &["task", "update", &blocker, "-s", "done"]);
+    assert_eq!(
+        Task::find(&mut conn, tasks[0].id).unwrap().status,
+        TaskStatus::Todo
+    );
+}
+
+#[test]
+fn test_comment_via_cli() {
+    let mut conn = establish_test_connection();
+    let task = create_tasks(&mut conn, 1).remove(0);
+    let id = task.id.to_string();
+
+    run(
+        &mut conn,
+        &["task", "comment", &id, "Waiting", "for", "review"],
+    );
+    run(&mut conn, &["task", "comment", &id, "  "]);
+    run(&mut conn, &["task", "read", &id]);
+
+    let comments = TaskComment::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].body, "Waiting for review");
+}
+
+#[test]
+fn test_link_via_cli() {
+    let mut conn = establish_test_connection();
+    let task = create_tasks(&mut conn, 1).remove(0);
+    let id = task.id.to_string();
+
+    run(
+        &mut conn,
+        &[
+            "task",
+            "link",
+            &id,
+            "https://example.com/pr/1",
+            "--label",
+            "PR",
+        ],
+    );
+    run(&mut conn, &["task", "link", &id, "docs/spec.md"]);
+    run(&mut conn, &["task", "read", &id]);
+
+    let links = TaskLink::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].label.as_deref(), Some("PR"));
+    let spec = std::env::current_dir().unwrap().join("docs/spec.md");
+    assert_eq!(links[1].target, spec.display().to_string());
+
+    run(
+        &mut conn,
+        &["task", "unlink", &id, "https://example.com/pr/1"],
+    );
+    assert_eq!(
+        TaskLink::list_for_task(&mut conn, task.id).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_init_via_cli() {
+    let mut conn = establish_test_connection();
+    let dir = tempfile::tempdir().unwrap();
+    let dir_arg = dir.path().to_str().unwrap();
+
+    run(&mut conn, &["init", dir_arg]);
+
+    let database = dir.path().join(".roll/roll.db");
+    assert!(database.is_file());
+    let location = DatabaseLocation::find_local(&dir.path().join("src")).unwrap();
+    assert_eq!(
+        location.map(|location| location.url),
+        Some(database.display().to_string())
+    );
+    // Running it again keeps the database.
+    run(&mut conn, &["init", dir_arg]);
+    assert!(database.is_file());
+}
+
+#[test]
+fn test_workspaces_via_cli() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspaces_file = dir.path().join("workspaces.toml");
+    let work = dir.path().join("work.db");
+    let personal = dir.path().join("personal.db");
+    // Runs the roll binary, so the workspaces file is only set for it and not for the tests
+    // running alongside.
+    let roll = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_roll"))
+            .args(args)
+            .env("ROLL_WORKSPACES", &workspaces_file)
+            .env("ROLL_CONFIG", dir.path().join("config.toml"))
+            .output()
+            .unwrap();
+        (
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+
+    roll(&["workspace", "add", "work", work.to_str().unwrap()]);
+    roll(&["workspace", "add", "personal", personal.to_str().unwrap()]);
+    // Adding workspaces doesn't make one active.
+    let workspaces = Workspaces::load_from(&workspaces_file).unwrap();
+    assert_eq!(workspaces.active(), None);
+    roll(&["workspace", "switch", "personal"]);
+
+    let workspaces = Workspaces::load_from(&workspaces_file).unwrap();
+    assert_eq!(workspaces.active(), Some(("personal", personal.as_path())));
+    assert_eq!(workspaces.get("work").unwrap(), work.as_path());
+
+    let (stdout, _) = roll(&["where", "--workspace", "work"]);
+    assert_eq!(
+        stdout.trim(),
+        format!("{} (workspace work)", work.display())
+    );
+    let (_, stderr) = roll(&["where", "--workspace", "play"]);
+    assert!(stderr.starts_with("Error finding the database"));
+
+    roll(&["workspace", "remove", "personal"]);
+    let workspaces = Workspaces::load_from(&workspaces_file).unwrap();
+    assert_eq!(workspaces.active(), None);
+    assert_eq!(workspaces.workspaces.len(), 1);
+}
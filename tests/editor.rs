@@ -0,0 +1,61 @@
+mod common;
+
+use common::establish_test_connection;
+use on_a_roll::editor::edit_task_with;
+use on_a_roll::models::{task_status::TaskStatus, Project, Task};
+
+#[test]
+fn test_edit_task_saves_changes() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Old title"), None, None, None).unwrap();
+
+    let editor = "sed -i -e s/Old/New/ -e s/Todo/Blocked/ -e $a\\Waiting_on_review";
+    let updated = edit_task_with(&mut conn, task.id, editor).unwrap().unwrap();
+
+    assert_eq!(updated.title, "New title");
+    assert_eq!(updated.status, TaskStatus::Blocked);
+    assert_eq!(updated.description.as_deref(), Some("Waiting_on_review"));
+}
+
+#[test]
+fn test_edit_task_unchanged() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Title"), None, None, None).unwrap();
+
+    assert_eq!(edit_task_with(&mut conn, task.id, "true").unwrap(), None);
+    assert_eq!(Task::find(&mut conn, task.id).unwrap(), task);
+}
+
+#[test]
+fn test_edit_task_aborts_on_empty_document() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Title"), None, None, None).unwrap();
+
+    assert_eq!(
+        edit_task_with(&mut conn, task.id, "truncate -s 0").unwrap(),
+        None
+    );
+    assert_eq!(Task::find(&mut conn, task.id).unwrap(), task);
+}
+
+#[test]
+fn test_edit_task_failing_editor() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Title"), None, None, None).unwrap();
+
+    assert!(edit_task_with(&mut conn, task.id, "false").is_err());
+    assert!(edit_task_with(&mut conn, task.id + 1, "true").is_err());
+}
+
+#[test]
+fn test_edit_task_removes_project() {
+    let mut conn = establish_test_connection();
+    let project = Project::create(&mut conn, Some("Garden"), None, None).unwrap();
+    let task = Task::create(&mut conn, Some("Title"), None, None, Some(project.id)).unwrap();
+
+    let editor = "sed -i s/^project_id:.*/project_id:/";
+    let updated = edit_task_with(&mut conn, task.id, editor).unwrap().unwrap();
+
+    assert_eq!(updated.project_id, None);
+    assert_eq!(Task::find(&mut conn, task.id).unwrap().project_id, None);
+}
@@ -0,0 +1,205 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::events::{self, Event};
+use on_a_roll::models::project_status::ProjectStatus;
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::{Project, Task};
+use std::sync::{Mutex, Once};
+use std::thread::{self, ThreadId};
+
+static SUBSCRIBE: Once = Once::new();
+static EVENTS: Mutex<Vec<(ThreadId, Event)>> = Mutex::new(Vec::new());
+
+/// Takes the events published so far by the current test. Listeners run on the thread making
+/// the change, so tests running in parallel don't see each other's events.
+fn take_events() -> Vec<Event> {
+    SUBSCRIBE.call_once(|| {
+        events::subscribe(|event| {
+            EVENTS
+                .lock()
+                .unwrap()
+                .push((thread::current().id(), event.clone()))
+        })
+    });
+    let current = thread::current().id();
+    let mut all = EVENTS.lock().unwrap();
+    let (own, others) = all.drain(..).partition(|(thread, _)| *thread == current);
+    *all = others;
+    own.into_iter().map(|(_, event)| event).collect()
+}
+
+fn names(events: &[Event]) -> Vec<&'static str> {
+    events.iter().map(Event::name).collect()
+}
+
+#[test]
+fn test_task_changes_emit_events() {
+    let mut conn = establish_test_connection();
+    take_events();
+
+    let task = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+    assert_eq!(take_events(), vec![Event::TaskCreated(task.clone())]);
+
+    let updated = Task::update_with_reason(
+        &mut conn,
+        task.id,
+        Some("Paint walls"),
+        None,
+        Some(TaskStatus::InProgress),
+        None,
+        Some("Got the paint"),
+    )
+    .unwrap();
+    assert_eq!(
+        take_events(),
+        vec![
+            Event::TaskStatusChanged {
+                task: updated.clone(),
+                from: TaskStatus::Todo,
+                to: TaskStatus::InProgress,
+                reason: Some("Got the paint".to_string()),
+            },
+            Event::TaskUpdated(updated.clone()),
+        ]
+    );
+
+    // Nothing changed, so nothing happened.
+    Task::update(&mut conn, task.id, Some("Paint walls"), None, None, None).unwrap();
+    assert_eq!(take_events(), vec![]);
+
+    Task::delete(&mut conn, task.id).unwrap();
+    assert_eq!(take_events(), vec![Event::TaskDeleted(updated)]);
+    Task::delete(&mut conn, task.id).unwrap();
+    assert_eq!(take_events(), vec![]);
+}
+
+#[test]
+fn test_rolled_back_changes_emit_nothing() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+    take_events();
+
+    let result = Task::update_many(
+        &mut conn,
+        &[task.id, task.id + 1],
+        None,
+        None,
+        Some(TaskStatus::Completed),
+        None,
+        None,
+    );
+    assert!(result.is_err());
+    assert_eq!(take_events(), vec![]);
+
+    Task::update_many(
+        &mut conn,
+        &[task.id],
+        None,
+        None,
+        Some(TaskStatus::Completed),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(names(&take_events()), vec!["task_status_changed"]);
+}
+
+#[test]
+fn test_unblocking_emits_events_for_dependents() {
+    let mut conn = establish_test_connection();
+    let blocker = Task::create(&mut conn, Some("Buy paint"), None, None, None).unwrap();
+    let task = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+    Task::block(&mut conn, task.id, blocker.id).unwrap();
+    take_events();
+
+    Task::update(
+        &mut conn,
+        blocker.id,
+        None,
+        None,
+        Some(TaskStatus::Completed),
+        None,
+    )
+    .unwrap();
+
+    let changes: Vec<(i32, TaskStatus)> = take_events()
+        .into_iter()
+        .map(|event| match event {
+            Event::TaskStatusChanged { task, to, .. } => (task.id, to),
+            event => panic!("Unexpected event: {:?}", event),
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (blocker.id, TaskStatus::Completed),
+            (task.id, TaskStatus::Todo)
+        ]
+    );
+}
+
+#[test]
+fn test_project_changes_emit_events() {
+    let mut conn = establish_test_connection();
+    take_events();
+
+    let project = Project::create(&mut conn, Some("Garden Shed"), None, None).unwrap();
+    assert_eq!(take_events(), vec![Event::ProjectCreated(project.clone())]);
+
+    let updated = Project::update(
+        &mut conn,
+        project.id,
+        None,
+        None,
+        Some(ProjectStatus::Active),
+    )
+    .unwrap();
+    assert_eq!(
+        take_events(),
+        vec![Event::ProjectStatusChanged {
+            project: updated.clone(),
+            from: ProjectStatus::Planning,
+            to: ProjectStatus::Active,
+        }]
+    );
+
+    let updated =
+        Project::update(&mut conn, project.id, None, Some("Behind the house"), None).unwrap();
+    assert_eq!(take_events(), vec![Event::ProjectUpdated(updated.clone())]);
+
+    Project::delete(&mut conn, project.id).unwrap();
+    assert_eq!(take_events(), vec![Event::ProjectDeleted(updated)]);
+}
+
+#[test]
+fn test_rolled_back_savepoint_drops_only_its_events() {
+    let mut conn = establish_test_connection();
+    take_events();
+
+    let task = events::transaction(&mut conn, |conn| {
+        let task = Task::create(conn, Some("Paint"), None, None, None)?;
+        let inner: Result<(), diesel::result::Error> = events::transaction(conn, |conn| {
+            Task::update(conn, task.id, Some("Paint walls"), None, None, None)
+                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+            Err(diesel::result::Error::RollbackTransaction)
+        });
+        assert!(inner.is_err());
+        Ok::<_, diesel::result::Error>(task)
+    })
+    .unwrap();
+
+    assert_eq!(Task::find(&mut conn, task.id).unwrap().title, "Paint");
+    assert_eq!(take_events(), vec![Event::TaskCreated(task)]);
+}
+
+#[test]
+fn test_listener_can_subscribe() {
+    static NESTED: Once = Once::new();
+    let mut conn = establish_test_connection();
+    take_events();
+    events::subscribe(|_| NESTED.call_once(|| events::subscribe(|_| {})));
+
+    Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+    Task::create(&mut conn, Some("Sand"), None, None, None).unwrap();
+    assert_eq!(take_events().len(), 2);
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use clap::Parser;
+use common::establish_test_connection;
+use diesel::SqliteConnection;
+use on_a_roll::{
+    cli::{run_cli, Cli},
+    config::{self, Config},
+    models::{task_status::TaskStatus, Task, TaskActivity, TaskCommit, TaskStatusChange},
+};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=Roll", "-c", "user.email=roll@example.com"])
+        .args(args)
+        .output()
+        .expect("git must be installed");
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn init_repo() -> TempDir {
+    let repo = tempfile::tempdir().unwrap();
+    git(repo.path(), &["init", "--quiet"]);
+    git(
+        repo.path(),
+        &["commit", "--quiet", "--allow-empty", "-m", "Initial commit"],
+    );
+    repo
+}
+
+fn run(conn: &mut SqliteConnection, repo: &Path, args: &[&str]) {
+    let mut full_args = vec!["roll", "git", "-C", repo.to_str().unwrap()];
+    full_args.extend_from_slice(args);
+    run_cli(Cli::parse_from(full_args), conn);
+}
+
+fn init_config() {
+    // Every test in this file lets keywords move tasks.
+    config::init(Config::parse("[git]\nmove_tasks = true\n").unwrap());
+}
+
+#[test]
+fn test_start_creates_branch_and_moves_task() {
+    init_config();
+    let mut conn = establish_test_connection();
+    let repo = init_repo();
+    let task = Task::create(&mut conn, Some("Fix login bug"), None, None, None).unwrap();
+    let id = task.id.to_string();
+
+    run(&mut conn, repo.path(), &["start", &id]);
+
+    let branch = git(repo.path(), &["branch", "--show-current"]);
+    assert_eq!(branch, format!("{}-fix-login-bug", task.id));
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::InProgress
+    );
+
+    // Starting again checks out the existing branch.
+    git(repo.path(), &["checkout", "--quiet", "-"]);
+    run(&mut conn, repo.path(), &["start", &id]);
+    assert_eq!(git(repo.path(), &["branch", "--show-current"]), branch);
+}
+
+#[test]
+fn test_start_records_reason() {
+    init_config();
+    let mut conn = establish_test_connection();
+    let repo = init_repo();
+    let task = Task::create(&mut conn, Some("Fix login bug"), None, None, None).unwrap();
+
+    run(
+        &mut conn,
+        repo.path(),
+        &["start", &task.id.to_string(), "--reason", "Users complain"],
+    );
+
+    let changes = TaskStatusChange::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(changes[0].to_status, TaskStatus::InProgress);
+    assert_eq!(changes[0].reason.as_deref(), Some("Users complain"));
+}
+
+#[test]
+fn test_post_commit_records_and_moves_tasks() {
+    init_config();
+    let mut conn = establish_test_connection();
+    let repo = init_repo();
+    let mentioned = Task::create(&mut conn, Some("Mentioned"), None, None, None).unwrap();
+    let fixed = Task::create(&mut conn, Some("Fixed"), None, None, None).unwrap();
+    let message = format!(
+        "Rework login\n\nSee #{}, fixes #{} and #9999",
+        mentioned.id, fixed.id
+    );
+    git(
+        repo.path(),
+        &["commit", "--quiet", "--allow-empty", "-m", &message],
+    );
+    let sha = git(repo.path(), &["rev-parse", "HEAD"]);
+
+    run(&mut conn, repo.path(), &["hook", "post-commit"]);
+    // Running the hook twice records the commit once.
+    run(&mut conn, repo.path(), &["hook", "post-commit"]);
+
+    for task in [&mentioned, &fixed] {
+        let commits = TaskCommit::list_for_task(&mut conn, task.id).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].sha, sha);
+        assert_eq!(commits[0].summary, "Rework login");
+    }
+    assert_eq!(
+        Task::find(&mut conn, mentioned.id).unwrap().status,
+        TaskStatus::Todo
+    );
+    assert_eq!(
+        Task::find(&mut conn, fixed.id).unwrap().status,
+        TaskStatus::Completed
+    );
+    let activity = TaskActivity::list_for_task(&mut conn, fixed.id).unwrap();
+    assert!(activity
+        .iter()
+        .any(|entry| matches!(entry, TaskActivity::Commit(commit) if commit.sha == sha)));
+}
+
+#[test]
+fn test_hook_install() {
+    let mut conn = establish_test_connection();
+    let repo = init_repo();
+
+    run(&mut conn, repo.path(), &["hook", "install"]);
+
+    for hook in ["commit-msg", "post-commit"] {
+        let script = std::fs::read_to_string(repo.path().join(".git/hooks").join(hook)).unwrap();
+        assert!(script.contains("roll git hook"));
+    }
+}
+
+#[test]
+fn test_hook_install_keeps_foreign_hooks() {
+    let mut conn = establish_test_connection();
+    let repo = init_repo();
+    let hook = repo.path().join(".git/hooks/post-commit");
+    std::fs::write(&hook, "#!/bin/sh\necho mine\n").unwrap();
+
+    run(&mut conn, repo.path(), &["hook", "install"]);
+    assert_eq!(
+        std::fs::read_to_string(&hook).unwrap(),
+        "#!/bin/sh\necho mine\n"
+    );
+
+    run(&mut conn, repo.path(), &["hook", "install", "--force"]);
+    assert!(std::fs::read_to_string(&hook)
+        .unwrap()
+        .contains("roll git hook post-commit"));
+}
+
+#[test]
+fn test_hooks_never_block_commits() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+    let mut conn = establish_test_connection();
+    let repo = init_repo();
+    run(&mut conn, repo.path(), &["hook", "install"]);
+
+    // A PATH with git but without roll, and then with a roll that always fails.
+    let bin = tempfile::tempdir().unwrap();
+    let real_git = std::env::split_paths(&std::env::var_os("PATH").unwrap())
+        .map(|dir| dir.join("git"))
+        .find(|path| path.is_file())
+        .expect("git must be installed");
+    symlink(real_git, bin.path().join("git")).unwrap();
+    let commit = |message: &str| {
+        Command::new("git")
+            .arg("-C")
+            .arg(repo.path())
+            .args(["-c", "user.name=Roll", "-c", "user.email=roll@example.com"])
+            .args(["commit", "--quiet", "--allow-empty", "-m", message])
+            .env("PATH", bin.path())
+            .status()
+            .unwrap()
+    };
+    assert!(commit("Without roll").success());
+
+    let roll = bin.path().join("roll");
+    std::fs::write(&roll, "#!/bin/sh\nexit 1\n").unwrap();
+    std::fs::set_permissions(&roll, std::fs::Permissions::from_mode(0o755)).unwrap();
+    assert!(commit("With a failing roll").success());
+
+    assert_eq!(
+        git(repo.path(), &["log", "-1", "--format=%s"]),
+        "With a failing roll"
+    );
+}
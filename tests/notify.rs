@@ -0,0 +1,179 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::config::{self, Config};
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::{Project, Task};
+use on_a_roll::notify;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+/// Bodies posted to the stand-in webhook, which fails the first request to test retries.
+type Received = Arc<Mutex<Vec<Value>>>;
+
+struct Setup {
+    received: Received,
+    hooks_dir: PathBuf,
+    /// Held by each test, as [`notify::wait`] waits for the deliveries of all of them.
+    lock: Mutex<()>,
+}
+
+/// Starts the stand-in webhook and writes the hook scripts, then loads a config using both.
+/// Every test in this file shares them, as the config is set once per process.
+fn setup() -> &'static Setup {
+    static SETUP: OnceLock<Setup> = OnceLock::new();
+    SETUP.get_or_init(|| {
+        let received = Received::default();
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let address = server.server_addr().to_ip().unwrap();
+        let bodies = received.clone();
+        thread::spawn(move || {
+            for (attempt, mut request) in server.incoming_requests().enumerate() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let status = if attempt == 0 { 500 } else { 200 };
+                if status == 200 {
+                    bodies.lock().unwrap().push(serde_json::from_str(&body).unwrap());
+                }
+                request
+                    .respond(tiny_http::Response::empty(status))
+                    .unwrap();
+            }
+        });
+
+        let hooks_dir = tempfile::tempdir().unwrap().keep();
+        let script = hooks_dir.join("on-project-deleted");
+        fs::write(
+            &script,
+            "#!/bin/sh\n{ echo \"$ROLL_EVENT\"; cat; echo; } >> \"$(dirname \"$0\")/deleted.log\"\n",
+        )
+        .unwrap();
+        make_executable(&script);
+        fs::write(hooks_dir.join("on-task-canceled"), "#!/bin/sh\necho broken >&2\nexit 3\n")
+            .unwrap();
+        make_executable(&hooks_dir.join("on-task-canceled"));
+
+        config::init(
+            Config::parse(&format!(
+                r#"
+[events]
+hooks_dir = "{}"
+retries = 1
+
+[[events.webhooks]]
+url = "http://{}/roll"
+events = ["task_completed"]
+"#,
+                hooks_dir.display(),
+                address
+            ))
+            .unwrap(),
+        );
+        Setup {
+            received,
+            hooks_dir,
+            lock: Mutex::new(()),
+        }
+    })
+}
+
+fn make_executable(path: &std::path::Path) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn test_webhook_receives_subscribed_events_with_retries() {
+    let setup = setup();
+    let _lock = setup.lock.lock().unwrap();
+    setup.received.lock().unwrap().clear();
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+    Task::update(
+        &mut conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::InProgress),
+        None,
+    )
+    .unwrap();
+    Task::update(
+        &mut conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::Completed),
+        None,
+    )
+    .unwrap();
+    notify::wait();
+
+    let received = setup.received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0]["event"], "task_status_changed");
+    assert_eq!(received[0]["task"]["title"], "Paint");
+    assert_eq!(received[0]["from"], "In Progress");
+    assert_eq!(received[0]["to"], "Completed");
+}
+
+#[test]
+fn test_hook_scripts_run_on_events() {
+    let setup = setup();
+    let _lock = setup.lock.lock().unwrap();
+    let mut conn = establish_test_connection();
+    let project = Project::create(&mut conn, Some("Garden Shed"), None, None).unwrap();
+    Project::delete(&mut conn, project.id).unwrap();
+    let task = Task::create(&mut conn, Some("Taxes"), None, None, None).unwrap();
+    Task::update(
+        &mut conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::Canceled),
+        None,
+    )
+    .unwrap();
+    notify::wait();
+
+    let log = fs::read_to_string(setup.hooks_dir.join("deleted.log")).unwrap();
+    let (name, body) = log.trim().split_once('\n').unwrap();
+    assert_eq!(name, "project_deleted");
+    let event: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(event["project"]["slug"], "garden-shed");
+
+    let errors = notify::take_errors();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("on-task-canceled"));
+    assert!(errors[0].ends_with("broken"));
+}
+
+#[test]
+fn test_events_of_separate_changes_arrive_in_order() {
+    let setup = setup();
+    let _lock = setup.lock.lock().unwrap();
+    setup.received.lock().unwrap().clear();
+    let mut conn = establish_test_connection();
+    for title in ["First", "Second", "Third"] {
+        let task = Task::create(&mut conn, Some(title), None, None, None).unwrap();
+        Task::update(
+            &mut conn,
+            task.id,
+            None,
+            None,
+            Some(TaskStatus::Completed),
+            None,
+        )
+        .unwrap();
+    }
+    notify::wait();
+
+    let received = setup.received.lock().unwrap();
+    let titles: Vec<&Value> = received
+        .iter()
+        .map(|event| &event["task"]["title"])
+        .collect();
+    assert_eq!(titles, ["First", "Second", "Third"]);
+}
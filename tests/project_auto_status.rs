@@ -0,0 +1,45 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::config::{self, Config};
+use on_a_roll::models::project_status::ProjectStatus;
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::{Project, Task};
+
+fn project_status(conn: &mut diesel::SqliteConnection, id: i32) -> ProjectStatus {
+    Project::find(conn, id).unwrap().status
+}
+
+#[test]
+fn test_projects_follow_their_tasks() {
+    config::init(Config::parse("[projects]\nauto_status = true\n").unwrap());
+    let mut conn = establish_test_connection();
+    let project = Project::create(&mut conn, Some("Garden"), None, None).unwrap();
+    let first = Task::create(&mut conn, Some("Dig"), None, None, Some(project.id)).unwrap();
+    let second = Task::create(&mut conn, Some("Plant"), None, None, Some(project.id)).unwrap();
+    assert_eq!(
+        project_status(&mut conn, project.id),
+        ProjectStatus::Planning
+    );
+
+    let set_status = |conn: &mut diesel::SqliteConnection, id, status| {
+        Task::update(conn, id, None, None, Some(status), None).unwrap();
+    };
+    set_status(&mut conn, first.id, TaskStatus::InProgress);
+    assert_eq!(project_status(&mut conn, project.id), ProjectStatus::Active);
+
+    set_status(&mut conn, second.id, TaskStatus::Blocked);
+    assert_eq!(
+        project_status(&mut conn, project.id),
+        ProjectStatus::Blocked
+    );
+
+    set_status(&mut conn, first.id, TaskStatus::Completed);
+    Task::delete(&mut conn, second.id).unwrap();
+    assert_eq!(
+        project_status(&mut conn, project.id),
+        ProjectStatus::Completed
+    );
+
+    Task::create(&mut conn, Some("Water"), None, None, Some(project.id)).unwrap();
+    assert_eq!(project_status(&mut conn, project.id), ProjectStatus::Active);
+}
@@ -2,8 +2,12 @@ mod common;
 use std::str::FromStr;
 
 use common::establish_test_connection;
+use diesel::prelude::*;
+use diesel_migrations::MigrationHarness;
+use on_a_roll::db::connection::{run_migrations, MIGRATIONS};
 use on_a_roll::models::{
-    project_status::ProjectStatus, Project, DEFAULT_PROJECT_STATUS, DEFAULT_PROJECT_TITLE,
+    project_status::ProjectStatus, task_status::TaskStatus, Project, ProjectLookupError, Task,
+    DEFAULT_PROJECT_STATUS, DEFAULT_PROJECT_TITLE,
 };
 #[test]
 fn test_create_project() {
@@ -88,3 +92,151 @@ fn test_list_projects() {
     let projects = Project::list(&mut conn).unwrap();
     assert_eq!(projects.len(), 2);
 }
+#[test]
+fn test_update_many_projects() {
+    let mut conn = establish_test_connection();
+    let first = Project::create(&mut conn, Some("Project 1"), None, None).unwrap();
+    let second = Project::create(&mut conn, Some("Project 2"), None, None).unwrap();
+
+    let updated =
+        Project::update_many(&mut conn, &[first.id, second.id], ProjectStatus::OnHold).unwrap();
+
+    assert_eq!(updated.len(), 2);
+    assert!(updated
+        .iter()
+        .all(|project| project.status == ProjectStatus::OnHold));
+}
+#[test]
+fn test_delete_many_projects() {
+    let mut conn = establish_test_connection();
+    let first = Project::create(&mut conn, Some("Project 1"), None, None).unwrap();
+    let second = Project::create(&mut conn, Some("Project 2"), None, None).unwrap();
+
+    let num_deleted = Project::delete_many(&mut conn, &[first.id, second.id]).unwrap();
+    assert_eq!(num_deleted, 2);
+}
+
+#[test]
+fn test_create_project_generates_unique_slug() {
+    let mut conn = establish_test_connection();
+    let first = Project::create(&mut conn, Some("Garden Shed"), None, None).unwrap();
+    let second = Project::create(&mut conn, Some("garden_shed"), None, None).unwrap();
+    let third = Project::create(&mut conn, Some("Garden  shed!"), None, None).unwrap();
+
+    assert_eq!(first.slug, "garden-shed");
+    assert_eq!(second.slug, "garden-shed-2");
+    assert_eq!(third.slug, "garden-shed-3");
+}
+#[test]
+fn test_slug_is_stable_on_rename() {
+    let mut conn = establish_test_connection();
+    let project = Project::create(&mut conn, Some("Garden"), None, None).unwrap();
+    let renamed = Project::update(&mut conn, project.id, Some("Backyard"), None, None).unwrap();
+
+    assert_eq!(renamed.slug, "garden");
+}
+fn resolve(conn: &mut diesel::SqliteConnection, reference: &str) -> i32 {
+    Project::resolve(conn, reference).unwrap().id
+}
+#[test]
+fn test_resolve_project() {
+    let mut conn = establish_test_connection();
+    let garden = Project::create(&mut conn, Some("Garden"), None, None).unwrap();
+    let garage = Project::create(&mut conn, Some("Garage Sale"), None, None).unwrap();
+
+    assert_eq!(resolve(&mut conn, &garden.id.to_string()), garden.id);
+    assert_eq!(resolve(&mut conn, "garden"), garden.id);
+    assert_eq!(resolve(&mut conn, "GARDEN"), garden.id);
+    assert_eq!(resolve(&mut conn, "garage sale"), garage.id);
+    assert_eq!(resolve(&mut conn, "garage"), garage.id);
+    assert!(matches!(
+        Project::resolve(&mut conn, "gar"),
+        Err(ProjectLookupError::Ambiguous(_, candidates)) if candidates.len() == 2
+    ));
+    assert!(matches!(
+        Project::resolve(&mut conn, "shed"),
+        Err(ProjectLookupError::NotFound(_))
+    ));
+    assert!(matches!(
+        Project::resolve(&mut conn, "999"),
+        Err(ProjectLookupError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_project_rollups() {
+    let mut conn = establish_test_connection();
+    let garden = Project::create(&mut conn, Some("Garden"), None, None).unwrap();
+    let kitchen = Project::create(&mut conn, Some("Kitchen"), None, None).unwrap();
+    let add_task = |conn: &mut diesel::SqliteConnection, project_id, status| {
+        Task::create(conn, Some("Task"), None, Some(status), Some(project_id)).unwrap()
+    };
+    add_task(&mut conn, garden.id, TaskStatus::Completed);
+    let oldest_open = add_task(&mut conn, garden.id, TaskStatus::Blocked);
+    add_task(&mut conn, garden.id, TaskStatus::Todo);
+    add_task(&mut conn, kitchen.id, TaskStatus::Completed);
+    Task::create(&mut conn, Some("No project"), None, None, None).unwrap();
+
+    let rollups = Project::rollups(&mut conn).unwrap();
+    assert_eq!(rollups.len(), 2);
+    let rollup = &rollups[&garden.id];
+    assert_eq!(
+        rollup.counts,
+        vec![
+            (TaskStatus::Todo, 1),
+            (TaskStatus::Blocked, 1),
+            (TaskStatus::Completed, 1),
+        ]
+    );
+    assert_eq!(rollup.percent_complete(), Some(33));
+    assert_eq!(rollup.oldest_open_task, Some(oldest_open.id));
+    assert_eq!(Project::rollup(&mut conn, garden.id).unwrap(), *rollup);
+    assert_eq!(
+        rollup.suggested_status(garden.status).map(|s| s.status),
+        Some(ProjectStatus::Blocked)
+    );
+    assert_eq!(
+        rollups[&kitchen.id]
+            .suggested_status(kitchen.status)
+            .map(|s| s.status),
+        Some(ProjectStatus::Completed)
+    );
+    // Without auto_status in the config, the suggestion is only shown.
+    assert_eq!(
+        Project::find(&mut conn, kitchen.id).unwrap().status,
+        ProjectStatus::Planning
+    );
+
+    let applied = Project::apply_suggested_status(&mut conn, kitchen.id).unwrap();
+    assert_eq!(applied.unwrap().status, ProjectStatus::Completed);
+    assert_eq!(
+        Project::apply_suggested_status(&mut conn, kitchen.id).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_migration_slugs_match_slugify() {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    for migration in conn.pending_migrations(MIGRATIONS).unwrap() {
+        if migration.name().to_string().ends_with("add_project_slug") {
+            break;
+        }
+        conn.run_migration(&migration).unwrap();
+    }
+    diesel::sql_query(
+        "INSERT INTO projects (title, status) \
+         VALUES ('Foo, Bar!!  Baz', 'active'), ('Foo Bar Baz', 'active'), ('!!', 'active')",
+    )
+    .execute(&mut conn)
+    .unwrap();
+
+    run_migrations(&mut conn);
+
+    let slugs: Vec<String> = Project::list(&mut conn)
+        .unwrap()
+        .into_iter()
+        .map(|project| project.slug)
+        .collect();
+    assert_eq!(slugs, vec!["foo-bar-baz", "foo-bar-baz-2", "project"]);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::establish_test_connection;
+use on_a_roll::models::task_priority::TaskPriority;
+use on_a_roll::models::{Project, QuickAdd, QuickAddError, Task, TaskTag};
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 10, 16).unwrap()
+}
+
+#[test]
+fn test_quick_add_creates_task_with_tags_and_project() {
+    let mut conn = establish_test_connection();
+    let project = Project::create(&mut conn, Some("Project Name"), None, None).unwrap();
+    let quick_add = QuickAdd::parse(
+        "Fix login bug +backend #urgent !high due:fri @project-name",
+        today(),
+    )
+    .unwrap();
+
+    let task = Task::quick_add(&mut conn, &quick_add).unwrap();
+
+    assert_eq!(task.title, "Fix login bug");
+    assert_eq!(task.project_id, Some(project.id));
+    assert_eq!(task.priority, Some(TaskPriority::High));
+    assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2024, 10, 18));
+    assert_eq!(
+        TaskTag::list_for_task(&mut conn, task.id).unwrap(),
+        vec!["backend", "urgent"]
+    );
+}
+
+#[test]
+fn test_quick_add_unknown_project() {
+    let mut conn = establish_test_connection();
+    let quick_add = QuickAdd::parse("Task +tag @nowhere", today()).unwrap();
+
+    let result = Task::quick_add(&mut conn, &quick_add);
+
+    assert!(matches!(result, Err(QuickAddError::UnknownProject(ref name)) if name == "nowhere"));
+    assert!(Task::list(&mut conn).unwrap().is_empty());
+}
+
+#[test]
+fn test_quick_add_ambiguous_project() {
+    let mut conn = establish_test_connection();
+    Project::create(&mut conn, Some("Garden shed"), None, None).unwrap();
+    Project::create(&mut conn, Some("garden-shed"), None, None).unwrap();
+    let quick_add = QuickAdd::parse("Task @Garden_Shed", today()).unwrap();
+
+    let error = Task::quick_add(&mut conn, &quick_add).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Project name Garden_Shed is ambiguous, it matches: Garden shed, garden-shed"
+    );
+}
@@ -0,0 +1,287 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::{Project, Task};
+use on_a_roll::server::Server;
+use serde_json::{json, Value};
+use std::thread;
+
+/// Starts a server on a free port with a fresh database, returning its base URL.
+fn start_server(token: Option<&str>, setup: impl FnOnce(&mut diesel::SqliteConnection)) -> String {
+    let mut conn = establish_test_connection();
+    setup(&mut conn);
+    let server = Server::bind("127.0.0.1:0", token.map(str::to_string)).unwrap();
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run(&mut conn));
+    format!("http://{}", address)
+}
+
+/// Sends a request, returning the status code and the JSON body, `null` if there is none.
+fn send(request: ureq::Request, body: Option<Value>) -> (u16, Value) {
+    let result = match body {
+        Some(body) => request
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string()),
+        None => request.call(),
+    };
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => panic!("Request failed: {}", e),
+    };
+    let status = response.status();
+    let text = response.into_string().unwrap();
+    let body = if text.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(&text).unwrap()
+    };
+    (status, body)
+}
+
+#[test]
+fn test_task_crud() {
+    let base = start_server(None, |conn| {
+        Project::create(conn, Some("Garden Shed"), None, None).unwrap();
+    });
+
+    let (status, task) = send(
+        ureq::post(&format!("{}/tasks", base)),
+        Some(json!({"title": "Paint walls", "status": "in_progress", "project": "garden"})),
+    );
+    assert_eq!(status, 201);
+    assert_eq!(task["title"], "Paint walls");
+    assert_eq!(task["status"], "In Progress");
+    assert_eq!(task["project_id"], 1);
+    let id = task["id"].as_i64().unwrap();
+
+    let (status, task) = send(
+        ureq::request("PATCH", &format!("{}/tasks/{}", base, id)),
+        Some(json!({"status": "done", "description": "Two coats"})),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(task["status"], "Completed");
+    assert_eq!(task["description"], "Two coats");
+
+    let (status, task) = send(ureq::get(&format!("{}/tasks/{}", base, id)), None);
+    assert_eq!(status, 200);
+    assert_eq!(task["title"], "Paint walls");
+
+    let (status, body) = send(ureq::delete(&format!("{}/tasks/{}", base, id)), None);
+    assert_eq!((status, body), (204, Value::Null));
+
+    let (status, body) = send(ureq::get(&format!("{}/tasks/{}", base, id)), None);
+    assert_eq!(status, 404);
+    assert!(body["error"].is_string());
+}
+
+#[test]
+fn test_list_tasks_with_filter() {
+    let base = start_server(None, |conn| {
+        let project = Project::create(conn, Some("Garden Shed"), None, None).unwrap();
+        Task::create(conn, Some("Paint"), None, None, Some(project.id)).unwrap();
+        Task::create(
+            conn,
+            Some("Fix roof"),
+            None,
+            Some(TaskStatus::InProgress),
+            Some(project.id),
+        )
+        .unwrap();
+        Task::create(conn, Some("Taxes"), None, None, None).unwrap();
+    });
+
+    let (status, tasks) = send(ureq::get(&format!("{}/tasks", base)), None);
+    assert_eq!(status, 200);
+    assert_eq!(tasks.as_array().unwrap().len(), 3);
+
+    let (status, tasks) = send(
+        ureq::get(&format!(
+            "{}/tasks?project=garden-shed&status=in+progress",
+            base
+        )),
+        None,
+    );
+    assert_eq!(status, 200);
+    let titles: Vec<&str> = tasks
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["Fix roof"]);
+
+    let (status, body) = send(ureq::get(&format!("{}/tasks?color=red", base)), None);
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], "Unknown filter key: color");
+
+    let (status, body) = send(ureq::get(&format!("{}/tasks?project=attic", base)), None);
+    assert_eq!(status, 404);
+    assert_eq!(body["error"], "Project not found: attic");
+}
+
+#[test]
+fn test_project_crud() {
+    let base = start_server(None, |_| {});
+
+    let (status, project) = send(
+        ureq::post(&format!("{}/projects", base)),
+        Some(json!({"title": "Garden Shed", "status": "active"})),
+    );
+    assert_eq!(status, 201);
+    assert_eq!(project["slug"], "garden-shed");
+    assert_eq!(project["status"], "Active");
+
+    let (status, project) = send(
+        ureq::request("PATCH", &format!("{}/projects/garden-shed", base)),
+        Some(json!({"description": "Behind the house"})),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(project["description"], "Behind the house");
+
+    let (status, projects) = send(ureq::get(&format!("{}/projects", base)), None);
+    assert_eq!(status, 200);
+    assert_eq!(projects[0]["title"], "Garden Shed");
+
+    let (status, _) = send(ureq::delete(&format!("{}/projects/1", base)), None);
+    assert_eq!(status, 204);
+    let (status, _) = send(ureq::get(&format!("{}/projects/1", base)), None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn test_invalid_requests() {
+    let base = start_server(None, |_| {});
+
+    let (status, body) = send(
+        ureq::post(&format!("{}/tasks", base)),
+        Some(json!({"title": "Paint", "status": "someday"})),
+    );
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("someday"));
+
+    let (status, _) = send(
+        ureq::post(&format!("{}/tasks", base)),
+        Some(json!({"title": "Paint", "color": "red"})),
+    );
+    assert_eq!(status, 400);
+
+    let (status, body) = send(
+        ureq::request("PATCH", &format!("{}/tasks/1", base)),
+        Some(json!({})),
+    );
+    assert_eq!(status, 400);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Nothing to change"));
+    let (status, _) = send(
+        ureq::post(&format!("{}/projects", base)),
+        Some(json!({"title": "Shed"})),
+    );
+    assert_eq!(status, 201);
+    let (status, _) = send(
+        ureq::request("PATCH", &format!("{}/projects/1", base)),
+        Some(json!({})),
+    );
+    assert_eq!(status, 400);
+
+    let description = "x".repeat(2 * 1024 * 1024);
+    let (status, _) = send(
+        ureq::post(&format!("{}/tasks", base)),
+        Some(json!({"title": "Paint", "description": description})),
+    );
+    assert_eq!(status, 413);
+
+    let (status, _) = send(ureq::get(&format!("{}/tasks/abc", base)), None);
+    assert_eq!(status, 400);
+    let (status, _) = send(ureq::put(&format!("{}/tasks/1", base)), None);
+    assert_eq!(status, 405);
+    let (status, _) = send(ureq::get(&format!("{}/notes", base)), None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn test_bearer_token() {
+    let base = start_server(Some("s3cret"), |_| {});
+
+    let (status, body) = send(ureq::get(&format!("{}/tasks", base)), None);
+    assert_eq!(status, 401);
+    assert!(body["error"].is_string());
+
+    let (status, _) = send(
+        ureq::get(&format!("{}/tasks", base)).set("Authorization", "Bearer wrong"),
+        None,
+    );
+    assert_eq!(status, 401);
+
+    let (status, _) = send(
+        ureq::get(&format!("{}/tasks", base)).set("Authorization", "Bearer s3cre"),
+        None,
+    );
+    assert_eq!(status, 401);
+
+    let (status, tasks) = send(
+        ureq::get(&format!("{}/tasks", base)).set("Authorization", "Bearer s3cret"),
+        None,
+    );
+    assert_eq!(status, 200);
+    assert_eq!(tasks, json!([]));
+
+    // The scheme is case-insensitive.
+    let (status, _) = send(
+        ureq::get(&format!("{}/tasks", base)).set("Authorization", "bearer s3cret"),
+        None,
+    );
+    assert_eq!(status, 200);
+}
+
+#[test]
+fn test_cross_site_requests() {
+    let base = start_server(None, |_| {});
+    let status = |result: Result<ureq::Response, ureq::Error>| match result {
+        Ok(response) => response.status(),
+        Err(ureq::Error::Status(code, _)) => code,
+        Err(e) => panic!("Request failed: {}", e),
+    };
+
+    // A form on another site can post text/plain without asking the browser first.
+    let form = status(
+        ureq::post(&format!("{}/tasks", base))
+            .set("Content-Type", "text/plain")
+            .set("Origin", "https://evil.example")
+            .send_string(r#"{"title": "Pwned"}"#),
+    );
+    assert_eq!(form, 403);
+    let plain = status(
+        ureq::post(&format!("{}/tasks", base))
+            .set("Content-Type", "text/plain")
+            .send_string(r#"{"title": "Pwned"}"#),
+    );
+    assert_eq!(plain, 415);
+    let (_, tasks) = send(ureq::get(&format!("{}/tasks", base)), None);
+    assert_eq!(tasks, json!([]));
+
+    let (status, _) = send(
+        ureq::get(&format!("{}/tasks", base)).set("Origin", "https://evil.example"),
+        None,
+    );
+    assert_eq!(status, 403);
+    let (status, _) = send(
+        ureq::get(&format!("{}/tasks", base)).set("Host", "evil.example"),
+        None,
+    );
+    assert_eq!(status, 403);
+
+    let (status, _) = send(
+        ureq::post(&format!("{}/tasks", base)).set("Origin", "http://localhost:7878"),
+        Some(json!({"title": "Water plants"})),
+    );
+    assert_eq!(status, 201);
+    let (status, _) = send(
+        ureq::get(&format!("{}/tasks", base))
+            .set("Content-Type", "application/json; charset=utf-8"),
+        None,
+    );
+    assert_eq!(status, 200);
+}
@@ -0,0 +1,68 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::{Task, TaskActivity, TaskComment};
+
+#[test]
+fn test_add_comment() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    TaskComment::add(&mut conn, task.id, "  Looked into it  ").unwrap();
+    TaskComment::add(&mut conn, task.id, "Found the cause").unwrap();
+
+    let bodies: Vec<String> = TaskComment::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .into_iter()
+        .map(|comment| comment.body)
+        .collect();
+    assert_eq!(bodies, vec!["Looked into it", "Found the cause"]);
+}
+
+#[test]
+fn test_add_comment_to_missing_task() {
+    let mut conn = establish_test_connection();
+
+    assert!(TaskComment::add(&mut conn, 9999, "Hello").is_err());
+}
+
+#[test]
+fn test_activity_interleaves_comments_and_status_changes() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+    TaskComment::add(&mut conn, task.id, "Starting").unwrap();
+    Task::update(
+        &mut conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::Blocked),
+        None,
+    )
+    .unwrap();
+
+    let activity = TaskActivity::list_for_task(&mut conn, task.id).unwrap();
+
+    assert_eq!(activity.len(), 2);
+    assert!(activity.iter().any(
+        |entry| matches!(entry, TaskActivity::Comment(comment) if comment.body == "Starting")
+    ));
+    assert!(activity.iter().any(|entry| matches!(
+        entry,
+        TaskActivity::StatusChange(change) if change.to_status == TaskStatus::Blocked
+    )));
+    assert!(activity.windows(2).all(|pair| pair[0].at() <= pair[1].at()));
+}
+
+#[test]
+fn test_delete_task_removes_comments() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+    TaskComment::add(&mut conn, task.id, "Soon gone").unwrap();
+
+    Task::delete(&mut conn, task.id).unwrap();
+
+    assert!(TaskComment::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,192 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::{DependencyError, Task, TaskDependency, TaskFilter};
+
+fn create_task(conn: &mut diesel::SqliteConnection, title: &str) -> Task {
+    Task::create(conn, Some(title), None, None, None).unwrap()
+}
+
+fn ids(tasks: Vec<Task>) -> Vec<i32> {
+    tasks.into_iter().map(|task| task.id).collect()
+}
+
+fn set_status(conn: &mut diesel::SqliteConnection, id: i32, status: TaskStatus) -> Task {
+    Task::update(conn, id, None, None, Some(status), None).unwrap()
+}
+
+#[test]
+fn test_add_dependency() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let blocker = create_task(&mut conn, "Review");
+
+    TaskDependency::add(&mut conn, task.id, blocker.id).unwrap();
+    let added = TaskDependency::add(&mut conn, task.id, blocker.id).unwrap();
+
+    assert_eq!(added, 0);
+    assert_eq!(
+        ids(TaskDependency::blockers(&mut conn, task.id).unwrap()),
+        vec![blocker.id]
+    );
+    assert_eq!(
+        ids(TaskDependency::dependents(&mut conn, blocker.id).unwrap()),
+        vec![task.id]
+    );
+}
+
+#[test]
+fn test_add_dependency_errors() {
+    let mut conn = establish_test_connection();
+    let first = create_task(&mut conn, "First");
+    let second = create_task(&mut conn, "Second");
+    let third = create_task(&mut conn, "Third");
+    TaskDependency::add(&mut conn, second.id, first.id).unwrap();
+    TaskDependency::add(&mut conn, third.id, second.id).unwrap();
+
+    assert!(matches!(
+        TaskDependency::add(&mut conn, first.id, first.id),
+        Err(DependencyError::SelfDependency(_))
+    ));
+    assert!(matches!(
+        TaskDependency::add(&mut conn, first.id, 9999),
+        Err(DependencyError::TaskNotFound(9999))
+    ));
+    match TaskDependency::add(&mut conn, first.id, third.id) {
+        Err(DependencyError::Cycle(chain)) => {
+            assert_eq!(chain, vec![first.id, third.id, second.id, first.id])
+        }
+        result => panic!("Expected a cycle, got {:?}", result),
+    }
+    assert!(TaskDependency::blockers(&mut conn, first.id)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_block_and_auto_unblock() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let review = create_task(&mut conn, "Review");
+    let tests = create_task(&mut conn, "Tests");
+    set_status(&mut conn, task.id, TaskStatus::InProgress);
+
+    let blocked = Task::block(&mut conn, task.id, review.id).unwrap();
+    assert_eq!(blocked.status, TaskStatus::Blocked);
+    Task::block(&mut conn, task.id, tests.id).unwrap();
+
+    set_status(&mut conn, review.id, TaskStatus::Completed);
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::Blocked
+    );
+
+    set_status(&mut conn, tests.id, TaskStatus::Completed);
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::InProgress
+    );
+}
+
+#[test]
+fn test_canceled_blocker_releases_task() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let review = create_task(&mut conn, "Review");
+    set_status(&mut conn, task.id, TaskStatus::InProgress);
+    Task::block(&mut conn, task.id, review.id).unwrap();
+
+    set_status(&mut conn, review.id, TaskStatus::Canceled);
+
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::InProgress
+    );
+}
+
+#[test]
+fn test_block_on_completed_task() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let done = create_task(&mut conn, "Review");
+    set_status(&mut conn, done.id, TaskStatus::Completed);
+
+    let task = Task::block(&mut conn, task.id, done.id).unwrap();
+
+    assert_eq!(task.status, TaskStatus::Todo);
+}
+
+#[test]
+fn test_unblock() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let blocker = create_task(&mut conn, "Review");
+    Task::block(&mut conn, task.id, blocker.id).unwrap();
+
+    assert_eq!(Task::unblock(&mut conn, task.id, blocker.id).unwrap(), 1);
+    assert_eq!(Task::unblock(&mut conn, task.id, blocker.id).unwrap(), 0);
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::Todo
+    );
+}
+
+#[test]
+fn test_ready_filter() {
+    let mut conn = establish_test_connection();
+    let free = create_task(&mut conn, "Free");
+    let waiting = create_task(&mut conn, "Waiting");
+    let unblocked = create_task(&mut conn, "Unblocked");
+    let blocker = create_task(&mut conn, "Blocker");
+    let done = create_task(&mut conn, "Done");
+    let dropped = create_task(&mut conn, "Dropped");
+    TaskDependency::add(&mut conn, waiting.id, blocker.id).unwrap();
+    TaskDependency::add(&mut conn, unblocked.id, done.id).unwrap();
+    TaskDependency::add(&mut conn, unblocked.id, dropped.id).unwrap();
+    set_status(&mut conn, done.id, TaskStatus::Completed);
+    set_status(&mut conn, dropped.id, TaskStatus::Canceled);
+
+    let ready = |conn: &mut diesel::SqliteConnection, ready| {
+        let filter = TaskFilter {
+            ready: Some(ready),
+            ..TaskFilter::default()
+        };
+        ids(Task::list_filtered(conn, &filter).unwrap())
+    };
+
+    assert_eq!(
+        ready(&mut conn, true),
+        vec![free.id, unblocked.id, blocker.id, done.id, dropped.id]
+    );
+    assert_eq!(ready(&mut conn, false), vec![waiting.id]);
+}
+
+#[test]
+fn test_delete_task_removes_dependencies() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let blocker = create_task(&mut conn, "Review");
+    TaskDependency::add(&mut conn, task.id, blocker.id).unwrap();
+
+    Task::delete(&mut conn, blocker.id).unwrap();
+
+    assert!(TaskDependency::blockers(&mut conn, task.id)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_delete_blocker_releases_task() {
+    let mut conn = establish_test_connection();
+    let task = create_task(&mut conn, "Deploy");
+    let blocker = create_task(&mut conn, "Review");
+    set_status(&mut conn, task.id, TaskStatus::InProgress);
+    Task::block(&mut conn, task.id, blocker.id).unwrap();
+
+    Task::delete(&mut conn, blocker.id).unwrap();
+
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::InProgress
+    );
+}
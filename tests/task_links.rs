@@ -0,0 +1,89 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::models::{Link, Task, TaskLink};
+
+#[test]
+fn test_add_link() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    TaskLink::add(&mut conn, task.id, "https://example.com/pr/1", Some("PR")).unwrap();
+    TaskLink::add(&mut conn, task.id, " /tmp/notes.md ", Some("  ")).unwrap();
+
+    let links: Vec<Link> = TaskLink::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .into_iter()
+        .map(Link::from)
+        .collect();
+    assert_eq!(
+        links,
+        vec![
+            Link::new("https://example.com/pr/1", Some("PR")),
+            Link::new("/tmp/notes.md", None),
+        ]
+    );
+}
+
+#[test]
+fn test_relinking_updates_label() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    TaskLink::add(&mut conn, task.id, "https://example.com", None).unwrap();
+    TaskLink::add(&mut conn, task.id, "https://example.com", Some("Docs")).unwrap();
+
+    let links = TaskLink::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].label.as_deref(), Some("Docs"));
+}
+
+#[test]
+fn test_add_link_to_missing_task() {
+    let mut conn = establish_test_connection();
+
+    assert!(TaskLink::add(&mut conn, 9999, "https://example.com", None).is_err());
+}
+
+#[test]
+fn test_links_include_description() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(
+        &mut conn,
+        Some("Task"),
+        Some("Fixed in [the PR](https://example.com/pr/1), see https://example.com/spec."),
+        None,
+        None,
+    )
+    .unwrap();
+    TaskLink::add(&mut conn, task.id, "https://example.com/pr/1", Some("PR")).unwrap();
+
+    assert_eq!(
+        Link::list_for_task(&mut conn, task.id).unwrap(),
+        vec![
+            Link::new("https://example.com/pr/1", Some("PR")),
+            Link::new("https://example.com/spec", None),
+        ]
+    );
+}
+
+#[test]
+fn test_remove_link_and_delete_task() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+    TaskLink::add(&mut conn, task.id, "https://example.com/a", None).unwrap();
+    TaskLink::add(&mut conn, task.id, "https://example.com/b", None).unwrap();
+
+    assert_eq!(
+        TaskLink::remove(&mut conn, task.id, "https://example.com/a").unwrap(),
+        1
+    );
+    assert_eq!(
+        TaskLink::remove(&mut conn, task.id, "https://example.com/a").unwrap(),
+        0
+    );
+    Task::delete(&mut conn, task.id).unwrap();
+
+    assert!(TaskLink::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .is_empty());
+}
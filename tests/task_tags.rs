@@ -0,0 +1,94 @@
+mod common;
+use common::establish_test_connection;
+use on_a_roll::models::{Task, TaskFilter, TaskTag};
+
+#[test]
+fn test_add_tag() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Tagged"), None, None, None).unwrap();
+
+    TaskTag::add(&mut conn, task.id, "backend").unwrap();
+    TaskTag::add(&mut conn, task.id, "urgent").unwrap();
+
+    let tags = TaskTag::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(tags, vec!["backend", "urgent"]);
+}
+
+#[test]
+fn test_add_tag_twice() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Tagged"), None, None, None).unwrap();
+
+    TaskTag::add(&mut conn, task.id, "backend").unwrap();
+    let added = TaskTag::add(&mut conn, task.id, "backend").unwrap();
+
+    assert_eq!(added, 0);
+    assert_eq!(
+        TaskTag::list_for_task(&mut conn, task.id).unwrap(),
+        vec!["backend"]
+    );
+}
+
+#[test]
+fn test_add_many_tags() {
+    let mut conn = establish_test_connection();
+    let first = Task::create(&mut conn, Some("Task 1"), None, None, None).unwrap();
+    let second = Task::create(&mut conn, Some("Task 2"), None, None, None).unwrap();
+
+    let added = TaskTag::add_many(&mut conn, &[first.id, second.id], "sprint-3").unwrap();
+
+    assert_eq!(added, 2);
+    assert_eq!(
+        TaskTag::list_for_task(&mut conn, second.id).unwrap(),
+        vec!["sprint-3"]
+    );
+}
+
+#[test]
+fn test_remove_tag() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Tagged"), None, None, None).unwrap();
+    TaskTag::add(&mut conn, task.id, "backend").unwrap();
+
+    let removed = TaskTag::remove(&mut conn, task.id, "backend").unwrap();
+
+    assert_eq!(removed, 1);
+    assert!(TaskTag::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_delete_task_removes_tags() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Tagged"), None, None, None).unwrap();
+    TaskTag::add(&mut conn, task.id, "backend").unwrap();
+
+    Task::delete(&mut conn, task.id).unwrap();
+
+    assert!(TaskTag::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_list_tasks_by_tag() {
+    let mut conn = establish_test_connection();
+    let first = Task::create(&mut conn, Some("Task 1"), None, None, None).unwrap();
+    let second = Task::create(&mut conn, Some("Task 2"), None, None, None).unwrap();
+    TaskTag::add_many(&mut conn, &[first.id, second.id], "backend").unwrap();
+    TaskTag::add(&mut conn, second.id, "urgent").unwrap();
+
+    assert_eq!(
+        TaskTag::list_all(&mut conn).unwrap(),
+        vec!["backend", "urgent"]
+    );
+    let filter = TaskFilter {
+        tag: Some("urgent".to_string()),
+        ..TaskFilter::default()
+    };
+    assert_eq!(
+        Task::list_filtered(&mut conn, &filter).unwrap(),
+        vec![second]
+    );
+}
@@ -1,9 +1,12 @@
 mod common;
 use common::establish_test_connection;
+use on_a_roll::models::project_status::ProjectStatus;
+use on_a_roll::models::status_name::StatusName;
 use on_a_roll::models::task_status::TaskStatus;
-use on_a_roll::models::Task;
+use on_a_roll::models::{Project, Task, TaskStatusChange, TaskUpdateError};
 use on_a_roll::models::{DEFAULT_TASK_STATUS, DEFAULT_TASK_TITLE};
 use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 #[test]
 fn test_create_task() {
@@ -110,7 +113,10 @@ fn test_update_task_error() {
     let result = Task::update(&mut conn, 9999, Some("Non-existent"), None, None, None); // Non-existent ID
 
     assert!(
-        matches!(result, Err(diesel::result::Error::NotFound)),
+        matches!(
+            result,
+            Err(TaskUpdateError::Database(diesel::result::Error::NotFound))
+        ),
         "Expected a NotFound error"
     );
 }
@@ -136,3 +142,182 @@ fn test_delete_task_error() {
     );
     assert_eq!(result.unwrap(), 0, "Expected 0 rows to be deleted");
 }
+
+#[test]
+fn test_update_many_tasks() {
+    let mut conn = establish_test_connection();
+    let first = Task::create(&mut conn, Some("Task 1"), None, None, None).unwrap();
+    let second = Task::create(&mut conn, Some("Task 2"), None, None, None).unwrap();
+    let untouched = Task::create(&mut conn, Some("Task 3"), None, None, None).unwrap();
+
+    let updated = Task::update_many(
+        &mut conn,
+        &[first.id, second.id],
+        None,
+        None,
+        Some(TaskStatus::Completed),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(updated.len(), 2);
+    assert!(updated
+        .iter()
+        .all(|task| task.status == TaskStatus::Completed));
+    assert_eq!(
+        Task::find(&mut conn, untouched.id).unwrap().status,
+        DEFAULT_TASK_STATUS
+    );
+}
+
+#[test]
+fn test_update_many_tasks_rolls_back_on_error() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    let result = Task::update_many(
+        &mut conn,
+        &[task.id, 9999],
+        None,
+        None,
+        Some(TaskStatus::Blocked),
+        None,
+        None,
+    );
+
+    assert!(matches!(
+        result,
+        Err(TaskUpdateError::Database(diesel::result::Error::NotFound))
+    ));
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        DEFAULT_TASK_STATUS
+    );
+}
+
+#[test]
+fn test_delete_many_tasks() {
+    let mut conn = establish_test_connection();
+    let first = Task::create(&mut conn, Some("Task 1"), None, None, None).unwrap();
+    let second = Task::create(&mut conn, Some("Task 2"), None, None, None).unwrap();
+    Task::create(&mut conn, Some("Task 3"), None, None, None).unwrap();
+
+    let num_deleted = Task::delete_many(&mut conn, &[first.id, second.id]).unwrap();
+
+    assert_eq!(num_deleted, 2);
+    assert_eq!(Task::list(&mut conn).unwrap().len(), 1);
+}
+
+#[test]
+fn test_set_project() {
+    let mut conn = establish_test_connection();
+    let project = Project::create(&mut conn, Some("Project"), None, None).unwrap();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    let task = Task::set_project(&mut conn, task.id, Some(project.id)).unwrap();
+    assert_eq!(task.project_id, Some(project.id));
+
+    let task = Task::set_project(&mut conn, task.id, None).unwrap();
+    assert_eq!(task.project_id, None);
+}
+
+#[test]
+fn test_parse_task_status_spellings() {
+    for input in [
+        "In Progress",
+        "in_progress",
+        "IN-PROGRESS",
+        "in progress",
+        "wip",
+        "Doing",
+    ] {
+        assert_eq!(TaskStatus::from_str(input), Ok(TaskStatus::InProgress));
+    }
+    assert_eq!(TaskStatus::from_str("done"), Ok(TaskStatus::Completed));
+    assert_eq!(TaskStatus::from_str("cancelled"), Ok(TaskStatus::Canceled));
+    assert!(TaskStatus::from_str("in-prog").is_err());
+    for status in TaskStatus::iter() {
+        assert_eq!(TaskStatus::from_str(&status.to_string()), Ok(status));
+    }
+}
+
+#[test]
+fn test_status_key_matches_database_form() {
+    use diesel::dsl::sql;
+    use diesel::prelude::*;
+    use diesel::sql_types::Text;
+    use on_a_roll::schema::{projects, tasks};
+
+    let mut conn = establish_test_connection();
+    for status in TaskStatus::iter() {
+        let task = Task::create(&mut conn, None, None, Some(status), None).unwrap();
+        let stored: String = tasks::table
+            .find(task.id)
+            .select(sql::<Text>("status"))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(stored, status.key());
+        assert_eq!(TaskStatus::from_str(&stored), Ok(status));
+    }
+    for status in ProjectStatus::iter() {
+        let project = Project::create(&mut conn, None, None, Some(status)).unwrap();
+        let stored: String = projects::table
+            .find(project.id)
+            .select(sql::<Text>("status"))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(stored, status.key());
+    }
+}
+
+#[test]
+fn test_status_changes_are_recorded() {
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    Task::update_with_reason(
+        &mut conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::Blocked),
+        None,
+        Some(" Waiting on the API "),
+    )
+    .unwrap();
+    // Keeping the status is not a change.
+    Task::update(
+        &mut conn,
+        task.id,
+        None,
+        None,
+        Some(TaskStatus::Blocked),
+        None,
+    )
+    .unwrap();
+    Task::update(&mut conn, task.id, Some("Renamed"), None, None, None).unwrap();
+    Task::update(&mut conn, task.id, None, None, Some(TaskStatus::Todo), None).unwrap();
+
+    let changes: Vec<_> = TaskStatusChange::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .into_iter()
+        .map(|change| (change.from_status, change.to_status, change.reason))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                TaskStatus::Todo,
+                TaskStatus::Blocked,
+                Some("Waiting on the API".to_string())
+            ),
+            (TaskStatus::Blocked, TaskStatus::Todo, None),
+        ]
+    );
+
+    Task::delete(&mut conn, task.id).unwrap();
+    assert!(TaskStatusChange::list_for_task(&mut conn, task.id)
+        .unwrap()
+        .is_empty());
+}
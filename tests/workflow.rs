@@ -0,0 +1,153 @@
+mod common;
+use clap::Parser;
+use common::establish_test_connection;
+use on_a_roll::cli::{run_cli, Cli};
+use on_a_roll::config::{self, Config};
+use on_a_roll::editor::edit_task_with;
+use on_a_roll::models::task_status::TaskStatus;
+use on_a_roll::models::workflow::WorkflowError;
+use on_a_roll::models::{Task, TaskStatusChange, TaskUpdateError};
+
+// Every test in this file runs with the same workflow, as the config is set once per process.
+fn init_workflow() {
+    config::init(
+        Config::parse(
+            r#"
+[workflow]
+reason_required = ["blocked"]
+terminal = ["completed"]
+
+[workflow.transitions]
+todo = ["in_progress"]
+in_progress = ["blocked", "completed"]
+"#,
+        )
+        .unwrap(),
+    );
+}
+
+fn set_status(
+    conn: &mut diesel::SqliteConnection,
+    id: i32,
+    status: TaskStatus,
+    reason: Option<&str>,
+) -> Result<Task, TaskUpdateError> {
+    Task::update_with_reason(conn, id, None, None, Some(status), None, reason)
+}
+
+#[test]
+fn test_update_follows_workflow() {
+    init_workflow();
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+
+    assert!(matches!(
+        set_status(&mut conn, task.id, TaskStatus::Completed, None),
+        Err(TaskUpdateError::Workflow(WorkflowError::NotAllowed { .. }))
+    ));
+    set_status(&mut conn, task.id, TaskStatus::InProgress, None).unwrap();
+    assert!(matches!(
+        set_status(&mut conn, task.id, TaskStatus::Blocked, None),
+        Err(TaskUpdateError::Workflow(WorkflowError::ReasonRequired(
+            TaskStatus::Blocked
+        )))
+    ));
+    set_status(&mut conn, task.id, TaskStatus::Completed, None).unwrap();
+    assert!(matches!(
+        set_status(&mut conn, task.id, TaskStatus::InProgress, None),
+        Err(TaskUpdateError::Workflow(WorkflowError::Terminal(
+            TaskStatus::Completed
+        )))
+    ));
+    // Other fields of a task in a terminal status can still change.
+    Task::update(&mut conn, task.id, Some("Renamed"), None, None, None).unwrap();
+}
+
+#[test]
+fn test_rejected_update_many_changes_nothing() {
+    init_workflow();
+    let mut conn = establish_test_connection();
+    let todo = Task::create(&mut conn, Some("Todo"), None, None, None).unwrap();
+    let started = Task::create(&mut conn, Some("Started"), None, None, None).unwrap();
+    set_status(&mut conn, started.id, TaskStatus::InProgress, None).unwrap();
+
+    let result = Task::update_many(
+        &mut conn,
+        &[started.id, todo.id],
+        None,
+        None,
+        Some(TaskStatus::Completed),
+        None,
+        None,
+    );
+
+    assert!(matches!(result, Err(TaskUpdateError::Workflow(_))));
+    assert_eq!(
+        Task::find(&mut conn, started.id).unwrap().status,
+        TaskStatus::InProgress
+    );
+}
+
+#[test]
+fn test_update_reason_via_cli() {
+    init_workflow();
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Task"), None, None, None).unwrap();
+    let run = |conn: &mut diesel::SqliteConnection, args: &[&str]| {
+        let cli = Cli::parse_from(std::iter::once("roll").chain(args.iter().copied()));
+        run_cli(cli, conn);
+    };
+    let id = task.id.to_string();
+
+    run(&mut conn, &["task", "update", &id, "-s", "wip"]);
+    run(&mut conn, &["task", "update", &id, "-s", "blocked"]);
+    assert_eq!(
+        Task::find(&mut conn, task.id).unwrap().status,
+        TaskStatus::InProgress
+    );
+
+    run(
+        &mut conn,
+        &[
+            "task",
+            "update",
+            &id,
+            "-s",
+            "blocked",
+            "-r",
+            "Waiting on review",
+        ],
+    );
+    let changes = TaskStatusChange::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[1].to_status, TaskStatus::Blocked);
+    assert_eq!(changes[1].reason.as_deref(), Some("Waiting on review"));
+}
+
+#[test]
+fn test_editor_reopens_on_workflow_error() {
+    init_workflow();
+    let mut conn = establish_test_connection();
+    let task = Task::create(&mut conn, Some("Paint"), None, None, None).unwrap();
+    set_status(&mut conn, task.id, TaskStatus::InProgress, None).unwrap();
+    // Blocks the task without a reason, then gives one once told it is missing.
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("edit.sh");
+    std::fs::write(
+        &script,
+        "if grep -q '^# Error: .*reason' \"$1\"; then\n\
+         sed -i 's/^reason:.*/reason: Waiting on review/' \"$1\"\n\
+         else\n\
+         sed -i 's/^status:.*/status: Blocked/' \"$1\"\n\
+         fi\n",
+    )
+    .unwrap();
+
+    let updated = edit_task_with(&mut conn, task.id, &format!("sh {}", script.display()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(updated.status, TaskStatus::Blocked);
+    let changes = TaskStatusChange::list_for_task(&mut conn, task.id).unwrap();
+    assert_eq!(changes[1].reason.as_deref(), Some("Waiting on review"));
+}